@@ -0,0 +1,23 @@
+//! `std` 無しで `softbody` の数学型とシミュレーションが使えることをビルド時に確認します。
+#![no_std]
+
+use softbody::core::{Mat2, Particle, Simulation, SimulationConfig, SoftBodyConfig, Spring, Vec2};
+
+/// `Vec2` / `Mat2` の演算（平方根・三角関数を含む）を一通り使います。
+pub fn math(a: Vec2, b: Vec2) -> f64 {
+    let m = Mat2::new(a, b);
+    let r = m.polar_decomposition();
+    let (l1, _, _, _) = m.symmetric_eigen();
+    (a + b).normalize().length() + Vec2::cross(a, b) + r.mul_vec(a).length() + l1
+}
+
+/// `alloc` 上で `Simulation` を構築して1ステップ進めます。
+pub fn simulate() -> usize {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let _ = sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 3, ..Default::default() });
+    sim.particles.push(Particle::new(0.0, 0.0));
+    let spring = Spring::new(0, 1, 0.5, sim.particles());
+    spring.solve(&mut sim.particles);
+    sim.step(1.0 / 60.0);
+    sim.particle_count()
+}
@@ -13,6 +13,18 @@
 //! - `Simulation`: シミュレーション全体を管理するコンテナ。重力や境界などのグローバルな設定を持ち、
 //!   シミュレーションのステップ実行を担います。
 //!
+//! ## feature
+//!
+//! - `std`（デフォルト）: 標準ライブラリを使用します。
+//! - `libm`: `std` を無効にした環境 (`default-features = false`) で、数学関数を `libm` で提供します。
+//!   `Simulation` を含む全ての型は `alloc` のみで動作します。
+//! - `deterministic`: 結果がプラットフォームに依存しうる数学関数（`hypot`, `atan2`, `sin`, `cos`, `powf`）を
+//!   `libm` のソフトウェア実装に置き換え、`hypot` は `sqrt(x² + y²)` で計算します。わずかな精度と速度を
+//!   引き換えに、異なるマシンでも同じ入力から同じ結果が得られます（リプレイの同期など）。
+//!   反復順序は常に決定的（格納順と `BTreeMap`、シード付き乱数）で、この feature の有無に依存しません。
+//! - `f32`: 浮動小数点数の型 `core::Scalar` を `f64` から `f32` に切り替え、質点などのメモリを半分にします。
+//!   精度と安定性の引き換えについては `core::Scalar` を参照してください。
+//!
 //! ## 使い方
 //!
 //! 1. `SimulationConfig` でシミュレーションのグローバル設定を定義します。
@@ -47,7 +59,7 @@
 //! };
 //!
 //! // 4. ソフトボディ追加
-//! sim.add_soft_body(&body_config);
+//! sim.add_soft_body(&body_config).unwrap();
 //!
 //! // 5. シミュレーションループ
 //! for _ in 0..100 {
@@ -60,32 +72,132 @@
 //! }
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm", feature = "deterministic")))]
+compile_error!("`std` と `libm` のどちらかの feature を有効にしてください。");
+
+extern crate alloc;
+
 // モジュールを定義してコードを整理します。
 pub mod core {
-    use std::fmt;
-    use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+    use alloc::collections::{BTreeMap, VecDeque};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::fmt;
+    use core::ops::{Add, AddAssign, Mul, Range, Sub, SubAssign};
+    #[cfg(not(feature = "std"))]
+    use float::FloatExt;
+
+    /// 座標・質量・剛性など、ライブラリ全体で使う浮動小数点数の型。デフォルトは `f64` です。
+    ///
+    /// `f32` feature を有効にすると `f32` になり、`Vec2` や `Particle` のメモリと帯域が半分になります。
+    /// 代わりに有効桁数は約7桁に落ちるため、原点から遠い座標（10⁴ 程度以上）では位置の分解能が粗くなり、
+    /// 反復回数やサブステップが多いほど丸め誤差が溜まりやすくなります。剛性の高いバネや小さな `dt` では
+    /// 微小な補正が丸めで消え、静止状態がわずかに揺れることがあります。
+    #[cfg(not(feature = "f32"))]
+    pub type Scalar = f64;
+    /// 座標・質量・剛性など、ライブラリ全体で使う浮動小数点数の型。`f32` feature により `f32` です。
+    #[cfg(feature = "f32")]
+    pub type Scalar = f32;
+
+    #[cfg(not(feature = "f32"))]
+    use core::f64::consts;
+    #[cfg(feature = "f32")]
+    use core::f32::consts;
+
+    /// `Scalar` の数学関数。
+    ///
+    /// `sqrt` と `ceil` は IEEE 754 で結果が一意に決まるため、`std` 有効時は `Scalar` の固有メソッドを使い、
+    /// `std` が無い環境では `FloatExt` で `libm` の実装を提供します。
+    /// 結果がプラットフォームに依存しうる関数は自由関数として提供し、`std` 有効かつ `deterministic`
+    /// feature が無効の場合のみ `std` の実装を使います。それ以外では `libm` のソフトウェア実装を使うため、
+    /// どのプラットフォームでも同じ結果になります。
+    mod float {
+        use super::Scalar;
+
+        /// `Scalar` の精度に対応する `libm` の関数。
+        #[cfg(any(not(feature = "std"), feature = "deterministic"))]
+        #[allow(unused_imports)]
+        mod lm {
+            #[cfg(not(feature = "f32"))]
+            pub(super) use libm::{atan2, ceil, cos, hypot, pow, sin, sqrt};
+            #[cfg(feature = "f32")]
+            pub(super) use libm::{atan2f as atan2, ceilf as ceil, cosf as cos, hypotf as hypot, powf as pow, sinf as sin, sqrtf as sqrt};
+        }
+
+        #[cfg(not(feature = "std"))]
+        pub(super) trait FloatExt {
+            fn sqrt(self) -> Scalar;
+            fn ceil(self) -> Scalar;
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl FloatExt for Scalar {
+            fn sqrt(self) -> Scalar { lm::sqrt(self) }
+            fn ceil(self) -> Scalar { lm::ceil(self) }
+        }
+
+        #[cfg(all(feature = "std", not(feature = "deterministic")))]
+        mod imp {
+            use super::Scalar;
+
+            pub(in crate::core) fn hypot(x: Scalar, y: Scalar) -> Scalar { x.hypot(y) }
+            pub(in crate::core) fn atan2(y: Scalar, x: Scalar) -> Scalar { y.atan2(x) }
+            pub(in crate::core) fn sin(x: Scalar) -> Scalar { x.sin() }
+            pub(in crate::core) fn cos(x: Scalar) -> Scalar { x.cos() }
+            pub(in crate::core) fn powf(x: Scalar, n: Scalar) -> Scalar { x.powf(n) }
+        }
+
+        #[cfg(not(all(feature = "std", not(feature = "deterministic"))))]
+        mod imp {
+            use super::{lm, Scalar};
+
+            /// 決定的モードでは、実装ごとに異なりうる `hypot` の代わりに `sqrt(x² + y²)` を使います。
+            /// 非常に大きな値では途中でオーバーフローしうる代わりに、どこでも同じ結果になります。
+            #[cfg(feature = "deterministic")]
+            pub(in crate::core) fn hypot(x: Scalar, y: Scalar) -> Scalar { lm::sqrt(x * x + y * y) }
+            #[cfg(not(feature = "deterministic"))]
+            pub(in crate::core) fn hypot(x: Scalar, y: Scalar) -> Scalar { lm::hypot(x, y) }
+            pub(in crate::core) fn atan2(y: Scalar, x: Scalar) -> Scalar { lm::atan2(y, x) }
+            pub(in crate::core) fn sin(x: Scalar) -> Scalar { lm::sin(x) }
+            pub(in crate::core) fn cos(x: Scalar) -> Scalar { lm::cos(x) }
+            pub(in crate::core) fn powf(x: Scalar, n: Scalar) -> Scalar { lm::pow(x, n) }
+        }
+
+        pub(super) use imp::{atan2, cos, hypot, powf, sin};
+    }
 
     /// 2次元ベクトルを表す構造体。
     #[derive(Debug, Copy, Clone, PartialEq, Default)]
     pub struct Vec2 {
-        pub x: f64,
-        pub y: f64,
+        pub x: Scalar,
+        pub y: Scalar,
     }
 
     impl Vec2 {
         /// 新しい `Vec2` を作成します。
-        pub const fn new(x: f64, y: f64) -> Self {
+        pub const fn new(x: Scalar, y: Scalar) -> Self {
             Self { x, y }
         }
 
         /// ベクトルの長さを計算します。
-        pub fn length(&self) -> f64 {
-            self.x.hypot(self.y)
+        pub fn length(&self) -> Scalar {
+            float::hypot(self.x, self.y)
+        }
+
+        /// ベクトルの長さを `sqrt(x² + y²)` で直接計算します。
+        ///
+        /// `length` が使う `hypot` より速く、`sqrt` は IEEE 754 で結果が一意に決まるためプラットフォームに
+        /// 依存しません。その代わり、成分の大きさが約 `1e154` を超えると2乗の途中でオーバーフローして
+        /// `inf` に、約 `1e-154` を下回るとアンダーフローして `0` になります。
+        pub fn length_fast(&self) -> Scalar {
+            self.length_squared().sqrt()
         }
 
         /// ベクトルの長さの2乗を計算します。
         /// `sqrt` の呼び出しを避けるため、長さの比較などに利用すると高速です。
-        pub fn length_squared(&self) -> f64 {
+        pub fn length_squared(&self) -> Scalar {
             self.x * self.x + self.y * self.y
         }
 
@@ -93,22 +205,56 @@ pub mod core {
         /// 長さが0の場合はゼロベクトルを返します。
         pub fn normalize(&self) -> Self {
             let len = self.length();
-            if len > f64::EPSILON {
+            if len > Scalar::EPSILON {
                 *self * (1.0 / len)
             } else {
                 Vec2::new(0.0, 0.0)
             }
         }
 
+        /// 反時計回りに90度回転したベクトル `(-y, x)` を返します。
+        pub fn perpendicular(&self) -> Self {
+            Vec2::new(-self.y, self.x)
+        }
+
         /// 2つのベクトルの内積を計算します。
-        pub fn dot(a: Self, b: Self) -> f64 {
+        pub fn dot(a: Self, b: Self) -> Scalar {
             a.x * b.x + a.y * b.y
         }
 
         /// 2つのベクトルの外積（2Dではスカラー値）を計算します。
-        pub fn cross(a: Self, b: Self) -> f64 {
+        pub fn cross(a: Self, b: Self) -> Scalar {
             a.x * b.y - a.y * b.x
         }
+
+        /// 2点の中点を返します。
+        pub fn mid(a: Self, b: Self) -> Self {
+            (a + b) * 0.5
+        }
+
+        /// 2つのベクトルの成分ごとの最小値を返します。
+        pub fn min(a: Self, b: Self) -> Self {
+            Self::new(a.x.min(b.x), a.y.min(b.y))
+        }
+
+        /// 2つのベクトルの成分ごとの最大値を返します。
+        pub fn max(a: Self, b: Self) -> Self {
+            Self::new(a.x.max(b.x), a.y.max(b.y))
+        }
+
+        /// `target` へ向かって最大 `max_delta` だけ進んだ点を返します。
+        /// 残りの距離が `max_delta` 以下の場合は `target` そのものを返すため、行き過ぎることはありません。
+        /// 負の `max_delta` は `0` として扱います。
+        pub fn move_towards(&self, target: Vec2, max_delta: Scalar) -> Vec2 {
+            let max_delta = max_delta.max(0.0);
+            let delta = target - *self;
+            let dist = delta.length();
+            if dist <= max_delta {
+                target
+            } else {
+                *self + delta * (max_delta / dist)
+            }
+        }
     }
 
     // --- 演算子のオーバーロード ---
@@ -140,13 +286,42 @@ pub mod core {
         }
     }
 
-    impl Mul<f64> for Vec2 {
+    impl Mul<Scalar> for Vec2 {
         type Output = Self;
-        fn mul(self, rhs: f64) -> Self::Output {
+        fn mul(self, rhs: Scalar) -> Self::Output {
             Self::new(self.x * rhs, self.y * rhs)
         }
     }
 
+    /// `(x, y)` の形式で表示します。小数点以下の桁数は書式の精度指定（`{:.1}` など）に従い、
+    /// 指定が無い場合は3桁です。
+    impl fmt::Display for Vec2 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let precision = f.precision().unwrap_or(3);
+            write!(f, "({:.*}, {:.*})", precision, self.x, precision, self.y)
+        }
+    }
+
+    impl From<(Scalar, Scalar)> for Vec2 {
+        fn from((x, y): (Scalar, Scalar)) -> Self {
+            Self::new(x, y)
+        }
+    }
+
+    /// `f32` の座標は `f64` で正確に表現できるため、この変換で精度は失われません。
+    #[cfg(not(feature = "f32"))]
+    impl From<(f32, f32)> for Vec2 {
+        fn from((x, y): (f32, f32)) -> Self {
+            Self::new(x as f64, y as f64)
+        }
+    }
+
+    impl From<Vec2> for (Scalar, Scalar) {
+        fn from(v: Vec2) -> Self {
+            (v.x, v.y)
+        }
+    }
+
     /// 2x2 行列を表す構造体。列ベクトルでデータを保持します。
     #[derive(Debug, Copy, Clone, PartialEq, Default)]
     pub struct Mat2 {
@@ -183,7 +358,7 @@ pub mod core {
                 let c1 = Vec2::new(x.x * inv_len_x, x.y * inv_len_x);
                 let c2 = Vec2::new(-x.y * inv_len_x, x.x * inv_len_x);
                 Mat2::new(c1, c2)
-            } else if len_y > f64::EPSILON {
+            } else if len_y > Scalar::EPSILON {
                 let inv_len_y = 1.0 / len_y;
                 let c1 = Vec2::new(y.x * inv_len_y, y.y * inv_len_y);
                 let c2 = Vec2::new(y.y * inv_len_y, -y.x * inv_len_y);
@@ -193,6 +368,57 @@ pub mod core {
                 Mat2::new(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0))
             }
         }
+
+        /// `polar_decomposition` と同様に回転を抽出しますが、行列が退化に近い場合は `previous` に近い方を選びます。
+        ///
+        /// 質点が一直線に近く並ぶと `A_pq` の行列式が `0` に近づき、Shoemake の方法の2つの候補
+        /// （回転と鏡映）の優劣が誤差で入れ替わって、フレームごとに結果が反転することがあります。
+        /// 2つの候補を決める長さの差が、大きい方の `POLAR_DEGENERACY_TOLERANCE` 倍以下の場合は、
+        /// `previous` とのフロベニウス内積が大きい候補を返して時間的な連続性を保ちます。
+        pub fn polar_decomposition_coherent(&self, previous: &Mat2) -> Self {
+            let x = Vec2::new(self.c1.x + self.c2.y, self.c1.y - self.c2.x);
+            let y = Vec2::new(self.c1.x - self.c2.y, self.c1.y + self.c2.x);
+            let (len_x, len_y) = (x.length(), y.length());
+            let longer = len_x.max(len_y);
+            if longer <= Scalar::EPSILON || (len_x - len_y).abs() > Self::POLAR_DEGENERACY_TOLERANCE * longer {
+                return self.polar_decomposition();
+            }
+            let (x, y) = (x * (1.0 / len_x), y * (1.0 / len_y));
+            let rotation = Mat2::new(x, Vec2::new(-x.y, x.x));
+            let reflection = Mat2::new(y, Vec2::new(y.y, -y.x));
+            let similarity = |m: &Mat2| Vec2::dot(m.c1, previous.c1) + Vec2::dot(m.c2, previous.c2);
+            if similarity(&rotation) >= similarity(&reflection) { rotation } else { reflection }
+        }
+
+        /// `polar_decomposition_coherent` で、2つの候補を区別できないとみなす長さの差の相対的な閾値。
+        pub const POLAR_DEGENERACY_TOLERANCE: Scalar = 1e-2;
+
+        /// 対称行列の固有値分解を閉形式で行います。
+        ///
+        /// `(λ1, λ2, v1, v2)` を返します。`λ1 >= λ2` で、`v1`, `v2` は対応する正規直交な固有ベクトルです。
+        /// 極分解で得られる伸縮行列 `S` の主伸縮方向の解析などに使用します。
+        ///
+        /// 行列が対称であることを前提とします。非対称な場合は非対角成分の平均を用いた
+        /// 対称部分の分解結果になります。
+        pub fn symmetric_eigen(&self) -> (Scalar, Scalar, Vec2, Vec2) {
+            let a = self.c1.x;
+            let d = self.c2.y;
+            let b = 0.5 * (self.c1.y + self.c2.x);
+
+            let mean = 0.5 * (a + d);
+            let radius = float::hypot(0.5 * (a - d), b);
+            let (l1, l2) = (mean + radius, mean - radius);
+
+            let v1 = if b.abs() > Scalar::EPSILON {
+                Vec2::new(l1 - d, b).normalize()
+            } else if a >= d {
+                Vec2::new(1.0, 0.0)
+            } else {
+                Vec2::new(0.0, 1.0)
+            };
+            let v2 = Vec2::new(-v1.y, v1.x);
+            (l1, l2, v1, v2)
+        }
     }
     
     // --- 演算子のオーバーロード ---
@@ -203,13 +429,57 @@ pub mod core {
         }
     }
 
-    impl Mul<f64> for Mat2 {
+    impl Mul<Scalar> for Mat2 {
         type Output = Self;
-        fn mul(self, rhs: f64) -> Self::Output {
+        fn mul(self, rhs: Scalar) -> Self::Output {
             Self::new(self.c1 * rhs, self.c2 * rhs)
         }
     }
     
+    /// 行ごとに `[[m11, m12], [m21, m22]]` の形式で表示します（`c1`, `c2` は列）。
+    /// 小数点以下の桁数は `Vec2` と同様に書式の精度指定に従い、指定が無い場合は3桁です。
+    impl fmt::Display for Mat2 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let p = f.precision().unwrap_or(3);
+            write!(f, "[[{:.*}, {:.*}], [{:.*}, {:.*}]]", p, self.c1.x, p, self.c2.x, p, self.c1.y, p, self.c2.y)
+        }
+    }
+
+    /// 始点と終点で定義される線分。切断やスライスなどの幾何クエリに使用します。
+    #[derive(Debug, Copy, Clone, PartialEq, Default)]
+    pub struct Line {
+        pub start: Vec2,
+        pub end: Vec2,
+    }
+
+    impl Line {
+        /// 新しい `Line` を作成します。
+        pub const fn new(start: Vec2, end: Vec2) -> Self {
+            Self { start, end }
+        }
+
+        /// 始点から終点へ向かう単位ベクトルを返します。長さが0の場合はゼロベクトルを返します。
+        pub fn direction(&self) -> Vec2 {
+            (self.end - self.start).normalize()
+        }
+
+        /// 線分の長さを返します。
+        pub fn length(&self) -> Scalar {
+            (self.end - self.start).length()
+        }
+
+        /// パラメータ `t` の位置 `start + (end - start) * t` を返します。
+        /// `t` は `[0, 1]` に制限しないため、延長線上の点も得られます。
+        pub fn point_at(&self, t: Scalar) -> Vec2 {
+            self.start + (self.end - self.start) * t
+        }
+
+        /// 点 `p` に最も近い線分上の点を返します。
+        pub fn closest_point(&self, p: Vec2) -> Vec2 {
+            geometry::dist_sq_to_segment(p, self.start, self.end).1
+        }
+    }
+
     /// 質点を表す構造体。
     #[derive(Debug, Clone, PartialEq)]
     pub struct Particle {
@@ -217,14 +487,14 @@ pub mod core {
         pub prev_pos: Vec2,
         pub vel: Vec2,
         /// 質量の逆数。`0.0` の場合は固定質点（無限大の質量）を表します。
-        pub inv_mass: f64,
-        pub radius: f64,
+        pub inv_mass: Scalar,
+        pub radius: Scalar,
         pub is_fixed: bool,
     }
 
     impl Particle {
         /// 新しい `Particle` を作成します。
-        pub fn new(x: f64, y: f64) -> Self {
+        pub fn new(x: Scalar, y: Scalar) -> Self {
             Self {
                 pos: Vec2::new(x, y),
                 prev_pos: Vec2::new(x, y),
@@ -234,6 +504,98 @@ pub mod core {
                 is_fixed: false,
             }
         }
+
+        /// 質点を `pos` へ瞬間移動させます。`prev_pos` も `pos` に揃えて `vel` を `0` にするため、
+        /// 移動量が速度として扱われて質点が飛び出すことはありません（マウスでのドラッグなどに使います）。
+        pub fn teleport(&mut self, pos: Vec2) {
+            self.pos = pos;
+            self.prev_pos = pos;
+            self.vel = Vec2::new(0.0, 0.0);
+        }
+
+        /// 質点の速度を `v` に設定します。`prev_pos` を `pos - v * dt` にするため、時間刻み `dt` の
+        /// Verlet 積分で `pos` と `prev_pos` の差から求まる速度も `v` になります。
+        pub fn set_velocity(&mut self, v: Vec2, dt: Scalar) {
+            self.vel = v;
+            self.prev_pos = self.pos - v * dt;
+        }
+
+        /// 質点の運動エネルギー `0.5 * m * |v|^2` を返します。
+        /// 固定質点（無限大の質量）は `0.0` を返します。
+        pub fn kinetic_energy(&self) -> Scalar {
+            if self.is_fixed || self.inv_mass < Scalar::EPSILON {
+                return 0.0;
+            }
+            0.5 * self.vel.length_squared() / self.inv_mass
+        }
+
+        /// 質点の運動量 `m * v` を返します。
+        /// 固定質点（無限大の質量）はゼロベクトルを返します。
+        pub fn momentum(&self) -> Vec2 {
+            if self.is_fixed || self.inv_mass < Scalar::EPSILON {
+                return Vec2::new(0.0, 0.0);
+            }
+            self.vel * (1.0 / self.inv_mass)
+        }
+
+        /// 有限の質量を返します。固定質点（無限大の質量）は `None` を返します。
+        fn finite_mass(&self) -> Option<Scalar> {
+            if self.is_fixed || self.inv_mass < Scalar::EPSILON { None } else { Some(1.0 / self.inv_mass) }
+        }
+    }
+
+    /// 境界や固定質点による補正後の丸め誤差を接触とみなす幅
+    const CONTACT_TOLERANCE: Scalar = 1e-9;
+
+    /// 質点 `p` が境界 `bounds` の壁に接していて、`dir` の向きに動かすとその壁に押し込まれるかを判定します。
+    fn supported_by_bounds(bounds: Option<(Vec2, Vec2)>, p: &Particle, dir: Vec2) -> bool {
+        let Some((min, max)) = bounds else { return false };
+        let touching = |gap: Scalar| gap <= p.radius + CONTACT_TOLERANCE;
+        (dir.x < 0.0 && touching(p.pos.x - min.x))
+            || (dir.x > 0.0 && touching(max.x - p.pos.x))
+            || (dir.y < 0.0 && touching(p.pos.y - min.y))
+            || (dir.y > 0.0 && touching(max.y - p.pos.y))
+    }
+
+    /// 衝突の補正で質点をそれ以上押し込めない支え（境界の壁・閉じ込め領域の辺・固定質点）。
+    struct Supports<'a> {
+        bounds: Option<(Vec2, Vec2)>,
+        containment: Option<&'a ContainmentRegion>,
+        /// 固定質点の位置と半径
+        fixed: Vec<(Vec2, Scalar)>,
+    }
+
+    impl<'a> Supports<'a> {
+        /// `config` の境界・閉じ込め領域（`enable_boundaries` が有効な場合のみ）と、`particles` の固定質点を支えとします。
+        fn new(config: &'a SimulationConfig, particles: &[Particle]) -> Self {
+            let (bounds, containment) = if config.enable_boundaries { (config.bounds, config.containment.as_ref()) } else { (None, None) };
+            let fixed = particles.iter().filter(|p| p.is_fixed).map(|p| (p.pos, p.radius)).collect();
+            Self { bounds, containment, fixed }
+        }
+
+        /// 質点 `p` を `dir` の向きに動かすと、接している支えに押し込まれるかを判定します。固定質点自身は常に真です。
+        fn supports(&self, p: &Particle, dir: Vec2) -> bool {
+            p.is_fixed
+                || supported_by_bounds(self.bounds, p, dir)
+                || self.containment.is_some_and(|region| region.supports(p.pos, p.radius, dir))
+                || self.fixed.iter().any(|&(pos, radius)| {
+                    let offset = pos - p.pos;
+                    Vec2::dot(dir, offset) > 0.0 && offset.length() <= radius + p.radius + CONTACT_TOLERANCE
+                })
+        }
+    }
+
+    /// 異なる2つのインデックスの質点を同時にミュータブルに借用します。
+    /// 戻り値の順序は引数 `(i, j)` の順序に従います。
+    fn pair_mut(particles: &mut [Particle], i: usize, j: usize) -> (&mut Particle, &mut Particle) {
+        // インデックスのペアを安全にミュータブルに借用するためのテクニック
+        if i < j {
+            let (s1, s2) = particles.split_at_mut(j);
+            (&mut s1[i], &mut s2[0])
+        } else {
+            let (s1, s2) = particles.split_at_mut(i);
+            (&mut s2[0], &mut s1[j])
+        }
     }
 
     /// 2つの質点を結ぶバネを表す構造体。距離拘束として機能します。
@@ -244,16 +606,19 @@ pub mod core {
     pub struct Spring {
         pub p1_index: usize,
         pub p2_index: usize,
-        pub rest_length: f64,
-        pub stiffness: f64,
+        pub rest_length: Scalar,
+        pub stiffness: Scalar,
+        /// 熱源による剛性の倍率（`1.0` で影響なし）。`Simulation` の各ステップの初めに、バネの中点での
+        /// 熱源の熱から計算し直され、解決時に `stiffness` に掛けられます。
+        pub thermal_stiffness_scale: Scalar,
     }
 
     impl Spring {
         /// 新しい `Spring` を作成します。
         /// `particles` スライスから初期位置を取得し、静止長を計算します。
-        pub fn new(p1_index: usize, p2_index: usize, stiffness: f64, particles: &[Particle]) -> Self {
+        pub fn new(p1_index: usize, p2_index: usize, stiffness: Scalar, particles: &[Particle]) -> Self {
             let rest_length = (particles[p1_index].pos - particles[p2_index].pos).length();
-            Self { p1_index, p2_index, rest_length, stiffness }
+            Self { p1_index, p2_index, rest_length, stiffness, thermal_stiffness_scale: 1.0 }
         }
 
         /// バネ拘束を解決し、質点の位置を修正します。
@@ -262,32 +627,167 @@ pub mod core {
         ///
         /// * `particles` - シミュレーション内の全質点を含む可変スライス。
         pub fn solve(&self, particles: &mut [Particle]) {
-            // インデックスのペアを安全にミュータブルに借用するためのテクニック
-            let (p1_slice, p2_slice) = if self.p1_index < self.p2_index {
-                let (s1, s2) = particles.split_at_mut(self.p2_index);
-                (&mut s1[self.p1_index], &mut s2[0])
-            } else {
-                let (s1, s2) = particles.split_at_mut(self.p1_index);
-                (&mut s2[0], &mut s1[self.p2_index])
-            };
+            self.solve_with_stiffness(particles, self.heated_stiffness(), Vec2::length);
+        }
+
+        /// `stiffness` に `thermal_stiffness_scale` を掛けた、熱を考慮した剛性を返します。
+        pub fn heated_stiffness(&self) -> Scalar {
+            self.stiffness * self.thermal_stiffness_scale
+        }
+
+        /// 剛性 `stiffness` (`0..=1`) を、時間刻み `dt` と反復回数 `iterations` での XPBD のコンプライアンスに変換します。
+        ///
+        /// 剛性 `k` のバネを `n` 回解くと、1ステップで拘束の誤差 `C` のうち `1 - (1 - k)^n` の割合が補正されます。
+        /// 一方 XPBD では、逆質量の和 `w` の距離拘束は `α~ = compliance / dt²` として
+        /// `w / (w + α~)` の割合が補正されます。両者を等しいとおいて
+        ///
+        /// `compliance = dt² * w * (1 - k_eff) / k_eff`（`k_eff = 1 - (1 - k)^n`）
+        ///
+        /// を得ます。剛性は質量に依存しない割合ですがコンプライアンスは質量に依存するため、
+        /// ここでは `w = 1`（例えば質量 `2` の質点の組）として換算します。逆質量の和が `w` の拘束には、
+        /// 戻り値を `w` 倍したものを使ってください。`stiffness` が `1` 以上なら `0.0`、`0` 以下なら
+        /// `Scalar::INFINITY` を返します。`iterations` の `0` は `1` として扱います。
+        pub fn compliance_from_stiffness(stiffness: Scalar, dt: Scalar, iterations: usize) -> Scalar {
+            if stiffness >= 1.0 {
+                return 0.0;
+            }
+            if stiffness <= 0.0 {
+                return Scalar::INFINITY;
+            }
+            let effective = 1.0 - float::powf(1.0 - stiffness, iterations.max(1) as Scalar);
+            dt * dt * (1.0 - effective) / effective
+        }
+
+        /// `compliance_from_stiffness` の逆変換です。XPBD のコンプライアンスを、時間刻み `dt` と
+        /// 反復回数 `iterations` で同じ補正量になる剛性 (`0..=1`) に変換します。
+        ///
+        /// `k_eff = 1 / (1 + compliance / dt²)` から `k = 1 - (1 - k_eff)^(1 / n)` を求めます。
+        /// `(1 - k)^n` が浮動小数点の精度を下回るほど `1` に近い剛性は、往復の変換で元の値に戻りません。
+        pub fn stiffness_from_compliance(compliance: Scalar, dt: Scalar, iterations: usize) -> Scalar {
+            if compliance <= 0.0 {
+                return 1.0;
+            }
+            let effective = 1.0 / (1.0 + compliance / (dt * dt));
+            1.0 - float::powf(1.0 - effective, 1.0 / iterations.max(1) as Scalar)
+        }
+
+        /// `self.stiffness` の代わりに `stiffness` を使い、長さを `length` で測ってバネ拘束を解決します。
+        fn solve_with_stiffness(&self, particles: &mut [Particle], stiffness: Scalar, length: fn(&Vec2) -> Scalar) {
+            let (p1_slice, p2_slice) = pair_mut(particles, self.p1_index, self.p2_index);
 
             let total_inv_mass = p1_slice.inv_mass + p2_slice.inv_mass;
-            if total_inv_mass < f64::EPSILON {
+            if total_inv_mass < Scalar::EPSILON {
                 return;
             }
 
             let diff = p1_slice.pos - p2_slice.pos;
-            let dist = diff.length();
-            if dist < f64::EPSILON {
+            let dist = length(&diff);
+            if dist < Scalar::EPSILON {
                 return;
             }
 
             let correction = diff * ((dist - self.rest_length) / dist);
-            let correction_vec = correction * (self.stiffness / total_inv_mass);
+            let correction_vec = correction * (stiffness / total_inv_mass);
 
             p1_slice.pos -= correction_vec * p1_slice.inv_mass;
             p2_slice.pos += correction_vec * p2_slice.inv_mass;
         }
+
+        /// 現在のひずみ `|現在の長さ - 静止長| / 静止長` を返します。静止長が0の場合は `0.0` を返します。
+        pub fn strain(&self, particles: &[Particle]) -> Scalar {
+            if self.rest_length < Scalar::EPSILON {
+                return 0.0;
+            }
+            let len = (particles[self.p1_index].pos - particles[self.p2_index].pos).length();
+            (len - self.rest_length).abs() / self.rest_length
+        }
+
+        /// `stretch_ratio` がひずみに掛ける倍率。ひずみ `1 / 3`（約33%の伸縮）で `1.0` になります。
+        pub const DEFAULT_STRETCH_SCALE: Scalar = 3.0;
+
+        /// ひずみを `DEFAULT_STRETCH_SCALE` 倍して `[0, 1]` に収めた値を返します。
+        /// 静止長で `0.0`、大きく伸縮するほど `1.0` に近づくため、描画色の線形補間の係数としてそのまま使えます。
+        pub fn stretch_ratio(&self, particles: &[Particle]) -> Scalar {
+            self.stretch_ratio_scaled(particles, Self::DEFAULT_STRETCH_SCALE)
+        }
+
+        /// `stretch_ratio` と同様ですが、ひずみに掛ける倍率を `scale` で指定します。
+        pub fn stretch_ratio_scaled(&self, particles: &[Particle], scale: Scalar) -> Scalar {
+            (self.strain(particles) * scale).clamp(0.0, 1.0)
+        }
+    }
+
+    /// 質点とワールド座標の固定点を結ぶバネ。
+    /// 固定点側は動かないため、見えない固定質点を置いてバネで結ぶ代わりに使えます。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct FixedSpring {
+        pub particle_index: usize,
+        pub anchor: Vec2,
+        pub rest_length: Scalar,
+        pub stiffness: Scalar,
+    }
+
+    impl FixedSpring {
+        /// 新しい `FixedSpring` を作成します。
+        /// `particles` スライスから質点の初期位置を取得し、固定点との距離を静止長とします。
+        pub fn new(particle_index: usize, anchor: Vec2, stiffness: Scalar, particles: &[Particle]) -> Self {
+            let rest_length = (particles[particle_index].pos - anchor).length();
+            Self { particle_index, anchor, rest_length, stiffness }
+        }
+
+        /// バネ拘束を解決し、質点の位置を修正します。
+        pub fn solve(&self, particles: &mut [Particle]) {
+            self.solve_with_stiffness(particles, self.stiffness, Vec2::length);
+        }
+
+        /// `self.stiffness` の代わりに `stiffness` を使い、長さを `length` で測ってバネ拘束を解決します。
+        fn solve_with_stiffness(&self, particles: &mut [Particle], stiffness: Scalar, length: fn(&Vec2) -> Scalar) {
+            let p = &mut particles[self.particle_index];
+            if p.is_fixed || p.inv_mass < Scalar::EPSILON {
+                return;
+            }
+            let diff = p.pos - self.anchor;
+            let dist = length(&diff);
+            if dist < Scalar::EPSILON {
+                return;
+            }
+            p.pos -= diff * ((dist - self.rest_length) / dist * stiffness);
+        }
+    }
+
+    /// マウスなどで質点を目標位置へ引き寄せるドラッグ拘束。`Simulation::set_drag` で設定します。
+    ///
+    /// 質点の位置を直接書き換える代わりに拘束として解決するため、他の拘束や衝突と釣り合いながら動き、
+    /// 離したときには引いていた速度が残ります。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DragConstraint {
+        pub particle_index: usize,
+        /// 質点を引き寄せる目標位置
+        pub target: Vec2,
+        pub stiffness: Scalar,
+        /// 1回の反復で質点を動かす距離（座標の単位）の上限。力ではなく位置の補正量の上限です。`None` の場合は制限しません。
+        /// 小さくすると目標を速く動かしたときに質点が遅れて追従し、ボディが伸びすぎなくなります。
+        pub max_correction: Option<Scalar>,
+    }
+
+    impl DragConstraint {
+        /// 質点 `particle_index` を `target` へ引き寄せるドラッグ拘束を作成します。補正量は制限しません。
+        pub fn new(particle_index: usize, target: Vec2, stiffness: Scalar) -> Self {
+            Self { particle_index, target, stiffness, max_correction: None }
+        }
+
+        /// `stiffness` を使ってドラッグ拘束を解決します。補正量は `max_correction` で制限します。
+        fn solve_with_stiffness(&self, particles: &mut [Particle], stiffness: Scalar) {
+            let p = &mut particles[self.particle_index];
+            if p.is_fixed || p.inv_mass < Scalar::EPSILON {
+                return;
+            }
+            let correction = (self.target - p.pos) * stiffness;
+            p.pos += match self.max_correction {
+                Some(max) if correction.length() > max => correction.normalize() * max.max(0.0),
+                _ => correction,
+            };
+        }
     }
 
     /// 形状維持拘束（Shape Matching Constraint）を表す構造体。
@@ -295,29 +795,54 @@ pub mod core {
     #[derive(Debug, Clone, PartialEq)]
     pub struct ShapeMatchingConstraint {
         pub particle_indices: Vec<usize>,
-        pub stiffness: f64,
+        pub stiffness: Scalar,
         /// 初期形状における、重心からの相対位置ベクトル群。
         initial_shape: Vec<Vec2>,
         /// 現在のフレームでの重心。
         center_of_mass: Vec2,
+        /// 直前の解決で抽出した回転。退化に近い形状で回転が反転しないよう、次の解決で参照します。
+        prev_rotation: Mat2,
+        /// 質点ごとの重み（`particle_indices` と同じ順序）。`None` の場合は質量で重み付けします。
+        weights: Option<Vec<Scalar>>,
+        /// `prev_rotation` を極分解で求めたときの `A_pq`。変化が小さい間は分解を省いて回転を使い回します。
+        prev_a_pq: Option<Mat2>,
+        /// これまでに極分解を行った回数
+        decompositions: usize,
     }
 
     impl ShapeMatchingConstraint {
         /// 新しい形状維持拘束を作成します。
-        pub fn new(particle_indices: Vec<usize>, stiffness: f64, particles: &[Particle]) -> Self {
+        pub fn new(particle_indices: Vec<usize>, stiffness: Scalar, particles: &[Particle]) -> Self {
+            Self::build(particle_indices, None, stiffness, particles)
+        }
+
+        /// 質点ごとの重み `weights`（`particle_indices` と同じ順序）を指定して形状維持拘束を作成します。
+        ///
+        /// 重心と、回転を求める行列 `A_pq` の計算で、質量の代わりに重みを使います。重みの大きい質点ほど
+        /// 拘束の重心と回転がその質点の動きに追従するため、ボディの芯を外側の柔らかい部分より強く効かせられます。
+        ///
+        /// # Panics
+        ///
+        /// `weights` と `particle_indices` の長さが異なる場合にパニックします。
+        pub fn with_weights(particle_indices: Vec<usize>, weights: Vec<Scalar>, stiffness: Scalar, particles: &[Particle]) -> Self {
+            assert_eq!(weights.len(), particle_indices.len(), "weights must match particle_indices");
+            Self::build(particle_indices, Some(weights), stiffness, particles)
+        }
+
+        fn build(particle_indices: Vec<usize>, weights: Option<Vec<Scalar>>, stiffness: Scalar, particles: &[Particle]) -> Self {
             let mut initial_shape = Vec::with_capacity(particle_indices.len());
             
             // 初期形状の重心を計算
             let mut center = Vec2::new(0.0, 0.0);
             let mut total_mass = 0.0;
-            for &i in &particle_indices {
+            for (k, &i) in particle_indices.iter().enumerate() {
                 let p = &particles[i];
-                let mass = if p.inv_mass > f64::EPSILON { 1.0 / p.inv_mass } else { 0.0 };
+                let mass = Self::center_weight(weights.as_deref(), k, p);
                 center += p.pos * mass;
                 total_mass += mass;
             }
 
-            let initial_center = if total_mass > f64::EPSILON {
+            let initial_center = if total_mass > Scalar::EPSILON {
                 center * (1.0 / total_mass)
             } else {
                 Vec2::new(0.0, 0.0)
@@ -333,20 +858,87 @@ pub mod core {
                 stiffness,
                 initial_shape,
                 center_of_mass: initial_center,
+                prev_rotation: Mat2::new(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)),
+                weights,
+                prev_a_pq: None,
+                decompositions: 0,
+            }
+        }
+
+        /// 重心の計算で `k` 番目の質点 `p` に掛ける重み。重みが無い場合は質量（固定された質点は `0`）です。
+        fn center_weight(weights: Option<&[Scalar]>, k: usize, p: &Particle) -> Scalar {
+            match weights {
+                Some(w) => w[k],
+                None if p.inv_mass > Scalar::EPSILON => 1.0 / p.inv_mass,
+                None => 0.0,
+            }
+        }
+
+        /// `with_weights` で指定した質点ごとの重みを返します。質量で重み付けする場合は `None` です。
+        pub fn weights(&self) -> Option<&[Scalar]> {
+            self.weights.as_deref()
+        }
+
+        /// 初期形状と重心を現在の質点の位置から取り直した拘束を返します。重みは引き継ぎます。
+        fn rebuilt(&mut self, particles: &[Particle]) -> Self {
+            Self::build(core::mem::take(&mut self.particle_indices), self.weights.take(), self.stiffness, particles)
+        }
+
+        /// 質点 `index` を拘束から外し、残りの質点の初期形状をその重心からの相対位置に取り直します。
+        /// 残りの質点どうしの初期形状の位置関係は変わりません。
+        fn remove_particle(&mut self, index: usize, particles: &[Particle]) {
+            let Some(k) = self.particle_indices.iter().position(|&i| i == index) else { return };
+            self.particle_indices.remove(k);
+            self.initial_shape.remove(k);
+            if let Some(weights) = &mut self.weights {
+                weights.remove(k);
+            }
+
+            let mut center = Vec2::new(0.0, 0.0);
+            let mut total_mass = 0.0;
+            for (k, (&i, &q)) in self.particle_indices.iter().zip(&self.initial_shape).enumerate() {
+                let p = &particles[i];
+                let mass = Self::center_weight(self.weights.as_deref(), k, p);
+                center += q * mass;
+                total_mass += mass;
+            }
+            if total_mass > Scalar::EPSILON {
+                let offset = center * (1.0 / total_mass);
+                for q in &mut self.initial_shape {
+                    *q -= offset;
+                }
             }
         }
         
+        /// 初期形状の重心からの相対位置を `factor` 倍します。
+        fn scale_rest_shape(&mut self, factor: Scalar) {
+            for q in &mut self.initial_shape {
+                *q = *q * factor;
+            }
+        }
+
+        /// 直前の解決で抽出した回転を返します。まだ解決していない場合は単位行列です。
+        pub fn rotation(&self) -> Mat2 {
+            self.prev_rotation
+        }
+
+        /// これまでの解決で `A_pq` の極分解を行った回数を返します。
+        /// `SimulationConfig::shape_match_tolerance` で分解を省いた解決は数えません。
+        pub fn decomposition_count(&self) -> usize {
+            self.decompositions
+        }
+
         /// 現在の重心を計算して更新します。
         fn calculate_center_of_mass(&mut self, particles: &[Particle]) {
             let mut center = Vec2::new(0.0, 0.0);
             let mut total_mass = 0.0;
-            for &i in &self.particle_indices {
+            for (k, &i) in self.particle_indices.iter().enumerate() {
                 let p = &particles[i];
-                let mass = if p.inv_mass > f64::EPSILON { 1.0 / p.inv_mass } else { 0.0 };
+                let mass = Self::center_weight(self.weights.as_deref(), k, p);
                 center += p.pos * mass;
                 total_mass += mass;
             }
-            self.center_of_mass = if total_mass > f64::EPSILON {
+            self.center_of_mass = if total_mass > Scalar::EPSILON {
                 center * (1.0 / total_mass)
             } else {
                 self.center_of_mass // 質量がない場合は動かさない
@@ -355,12 +947,22 @@ pub mod core {
 
         /// 形状維持拘束を解決し、質点の位置を修正します。
         pub fn solve(&mut self, particles: &mut [Particle]) {
+            self.solve_with_stiffness(particles, self.stiffness, 0.0);
+        }
+
+        /// `self.stiffness` の代わりに `stiffness` を使って形状維持拘束を解決します。
+        ///
+        /// `tolerance` が正の場合、`A_pq` が直前に極分解したときの値からフロベニウスノルムで
+        /// `tolerance` 倍（相対値）以内しか変わっていなければ、分解を省いて前回の回転を使います。
+        fn solve_with_stiffness(&mut self, particles: &mut [Particle], stiffness: Scalar, tolerance: Scalar) {
             self.calculate_center_of_mass(particles);
 
             let mut a_pq = Mat2::default();
             for (i, &p_idx) in self.particle_indices.iter().enumerate() {
                 let q = self.initial_shape[i]; // 初期形状の相対ベクトル
-                let p = particles[p_idx].pos - self.center_of_mass; // 現在の相対ベクトル
+                // 現在の相対ベクトル（重みが無い場合は全ての質点を等しく扱う）
+                let w = self.weights.as_ref().map_or(1.0, |w| w[i]);
+                let p = (particles[p_idx].pos - self.center_of_mass) * w;
                 
                 a_pq.c1.x += p.x * q.x;
                 a_pq.c1.y += p.y * q.x;
@@ -368,7 +970,19 @@ pub mod core {
                 a_pq.c2.y += p.y * q.y;
             }
 
-            let r = a_pq.polar_decomposition();
+            let unchanged = |prev: &Mat2| {
+                let diff = (a_pq.c1 - prev.c1).length_squared() + (a_pq.c2 - prev.c2).length_squared();
+                let norm = prev.c1.length_squared() + prev.c2.length_squared();
+                diff <= tolerance * tolerance * norm
+            };
+            let r = if tolerance > 0.0 && self.prev_a_pq.as_ref().is_some_and(unchanged) {
+                self.prev_rotation
+            } else {
+                self.decompositions += 1;
+                self.prev_a_pq = Some(a_pq);
+                a_pq.polar_decomposition_coherent(&self.prev_rotation)
+            };
+            self.prev_rotation = r;
 
             for (i, &p_idx) in self.particle_indices.iter().enumerate() {
                 let particle = &mut particles[p_idx];
@@ -377,59 +991,853 @@ pub mod core {
                 }
 
                 let goal_pos = self.center_of_mass + r.mul_vec(self.initial_shape[i]);
-                let correction = (goal_pos - particle.pos) * self.stiffness;
+                let correction = (goal_pos - particle.pos) * stiffness;
                 particle.pos += correction;
             }
         }
     }
 
-    /// 形状生成時のエラーを定義
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub enum ShapeError {
-        SelfIntersecting,
-        NotEnoughParticles,
+    /// 連続する3つの質点 `(prev, cur, next)` の折れ曲がり角を保つ曲げ拘束。
+    ///
+    /// `cur` における折れ曲がり角（`cur - prev` から `next - cur` への符号付き回転角）が
+    /// 初期形状の値から変化しないように質点の位置を修正します。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct BendConstraint {
+        pub prev_index: usize,
+        pub cur_index: usize,
+        pub next_index: usize,
+        /// 初期形状における折れ曲がり角（ラジアン）
+        pub rest_angle: Scalar,
+        pub stiffness: Scalar,
     }
 
-    impl fmt::Display for ShapeError {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            match self {
-                ShapeError::SelfIntersecting => write!(f, "The provided shape is self-intersecting."),
-                ShapeError::NotEnoughParticles => write!(f, "Not enough particles for a closed shape (minimum 3)."),
-            }
+    impl BendConstraint {
+        /// 新しい `BendConstraint` を作成します。
+        /// `particles` スライスから初期位置を取得し、静止角を計算します。
+        pub fn new(prev_index: usize, cur_index: usize, next_index: usize, stiffness: Scalar, particles: &[Particle]) -> Self {
+            let rest_angle = Self::turning_angle(
+                particles[prev_index].pos,
+                particles[cur_index].pos,
+                particles[next_index].pos,
+            );
+            Self { prev_index, cur_index, next_index, rest_angle, stiffness }
         }
-    }
 
-    /// ソフトボディを構成する要素の集合。
-    /// 実際の質点データは `Simulation` が所有し、`SoftBody` はインデックスで管理します。
-    #[derive(Debug, Clone)]
-    pub struct SoftBody {
-        pub particle_indices: Vec<usize>,
-        pub springs: Vec<Spring>,
-        pub shape_constraint: Option<ShapeMatchingConstraint>,
-        /// ワイヤーフレーム衝突判定用の外周ワイヤー情報 (グローバルインデックス)
-        pub outline_wires: Option<Vec<(usize, usize)>>,
-    }
+        /// `cur` における符号付きの折れ曲がり角を計算します。
+        fn turning_angle(prev: Vec2, cur: Vec2, next: Vec2) -> Scalar {
+            let d1 = cur - prev;
+            let d2 = next - cur;
+            float::atan2(Vec2::cross(d1, d2), Vec2::dot(d1, d2))
+        }
 
-    /// シミュレーション全体の環境と状態を管理する構造体。
-    #[derive(Debug, Clone)]
-    pub struct Simulation {
-        pub particles: Vec<Particle>,
-        soft_bodies: Vec<SoftBody>,
-        config: SimulationConfig,
+        /// 曲げ拘束を解決し、質点の位置を修正します。
+        ///
+        /// 拘束関数 `C = θ - θ0` の勾配に沿って3つの質点を質量の逆数で重み付けして動かします。
+        pub fn solve(&self, particles: &mut [Particle]) {
+            self.solve_with_stiffness(particles, self.stiffness);
+        }
+
+        /// `self.stiffness` の代わりに `stiffness` を使って曲げ拘束を解決します。
+        fn solve_with_stiffness(&self, particles: &mut [Particle], stiffness: Scalar) {
+            let prev = particles[self.prev_index].pos;
+            let cur = particles[self.cur_index].pos;
+            let next = particles[self.next_index].pos;
+            let d1 = cur - prev;
+            let d2 = next - cur;
+            let len1_sq = d1.length_squared();
+            let len2_sq = d2.length_squared();
+            if len1_sq < Scalar::EPSILON || len2_sq < Scalar::EPSILON {
+                return;
+            }
+
+            // 角度差を [-π, π] に正規化
+            let error = Self::turning_angle(prev, cur, next) - self.rest_angle;
+            let error = float::atan2(float::sin(error), float::cos(error));
+
+            // ベクトル v の角度の勾配は perp(v) / |v|^2
+            let grad_prev = Vec2::new(-d1.y, d1.x) * (1.0 / len1_sq);
+            let grad_next = Vec2::new(-d2.y, d2.x) * (1.0 / len2_sq);
+            let grad_cur = Vec2::new(0.0, 0.0) - grad_prev - grad_next;
+
+            let w_prev = particles[self.prev_index].inv_mass;
+            let w_cur = particles[self.cur_index].inv_mass;
+            let w_next = particles[self.next_index].inv_mass;
+            let denom = w_prev * grad_prev.length_squared()
+                + w_cur * grad_cur.length_squared()
+                + w_next * grad_next.length_squared();
+            if denom < Scalar::EPSILON {
+                return;
+            }
+
+            let lambda = -error / denom * stiffness;
+            particles[self.prev_index].pos += grad_prev * (lambda * w_prev);
+            particles[self.cur_index].pos += grad_cur * (lambda * w_cur);
+            particles[self.next_index].pos += grad_next * (lambda * w_next);
+        }
     }
-    
-    /// `SoftBody` を生成するための設定。ビルダーパターンのように使用します。
+
+    /// 3つの質点 `a`, `b`, `c` が `b` で成す内角を `[min_angle, max_angle]`（ラジアン、`0..=π`）に保つ拘束。
+    ///
+    /// 範囲内では何もせず、範囲を外れた場合のみ近い方の端に向けて補正するため、
+    /// 関節の曲がりすぎを防ぐ可動域の制限として使えます。まっすぐな状態の内角は `π` です。
     #[derive(Debug, Clone, PartialEq)]
-    pub struct SoftBodyConfig {
-        pub center: Vec2,
-        pub size: Vec2,
-        pub rows: usize,
-        pub cols: usize,
-        pub stiffness: f64,
-        pub shape_stiffness: f64,
-        pub is_fixed: bool,
-        pub particle_radius: f64,
-        pub particle_inv_mass: f64,
+    pub struct AngleConstraint {
+        pub a: usize,
+        pub b: usize,
+        pub c: usize,
+        pub min_angle: Scalar,
+        pub max_angle: Scalar,
+        pub stiffness: Scalar,
+    }
+
+    impl AngleConstraint {
+        /// 新しい `AngleConstraint` を作成します。
+        pub fn new(a: usize, b: usize, c: usize, min_angle: Scalar, max_angle: Scalar, stiffness: Scalar) -> Self {
+            Self { a, b, c, min_angle, max_angle, stiffness }
+        }
+
+        /// `b` における内角（`0..=π`）を返します。
+        pub fn angle(&self, particles: &[Particle]) -> Scalar {
+            let u = particles[self.a].pos - particles[self.b].pos;
+            let v = particles[self.c].pos - particles[self.b].pos;
+            float::atan2(Vec2::cross(u, v).abs(), Vec2::dot(u, v))
+        }
+
+        /// 角度拘束を解決し、質点の位置を修正します。
+        ///
+        /// 内角が範囲外の場合、拘束関数 `C = θ - (最も近い範囲の端)` の勾配に沿って
+        /// 3つの質点を質量の逆数で重み付けして動かします。
+        pub fn solve(&self, particles: &mut [Particle]) {
+            self.solve_with_stiffness(particles, self.stiffness);
+        }
+
+        /// `self.stiffness` の代わりに `stiffness` を使って角度拘束を解決します。
+        fn solve_with_stiffness(&self, particles: &mut [Particle], stiffness: Scalar) {
+            let u = particles[self.a].pos - particles[self.b].pos;
+            let v = particles[self.c].pos - particles[self.b].pos;
+            let len_u_sq = u.length_squared();
+            let len_v_sq = v.length_squared();
+            if len_u_sq < Scalar::EPSILON || len_v_sq < Scalar::EPSILON {
+                return;
+            }
+
+            // 符号付きの角度 φ (u から v) と、内角 θ = |φ|
+            let signed = float::atan2(Vec2::cross(u, v), Vec2::dot(u, v));
+            let angle = signed.abs();
+            let error = if angle < self.min_angle {
+                angle - self.min_angle
+            } else if angle > self.max_angle {
+                angle - self.max_angle
+            } else {
+                return;
+            };
+
+            // ベクトル w の角度の勾配は perp(w) / |w|^2。θ = |φ| なので φ の符号を掛ける
+            let sign = if signed < 0.0 { -1.0 } else { 1.0 };
+            let grad_a = u.perpendicular() * (-sign / len_u_sq);
+            let grad_c = v.perpendicular() * (sign / len_v_sq);
+            let grad_b = Vec2::new(0.0, 0.0) - grad_a - grad_c;
+
+            let w_a = particles[self.a].inv_mass;
+            let w_b = particles[self.b].inv_mass;
+            let w_c = particles[self.c].inv_mass;
+            let denom = w_a * grad_a.length_squared() + w_b * grad_b.length_squared() + w_c * grad_c.length_squared();
+            if denom < Scalar::EPSILON {
+                return;
+            }
+
+            let lambda = -error / denom * stiffness;
+            particles[self.a].pos += grad_a * (lambda * w_a);
+            particles[self.b].pos += grad_b * (lambda * w_b);
+            particles[self.c].pos += grad_c * (lambda * w_c);
+        }
+    }
+
+    /// 閉じた外周の中の気体の圧力を模した力。
+    ///
+    /// 外周の面積が `target_area` より小さいと外向きに、大きいと内向きに、各辺の法線方向の力を
+    /// 辺の両端の質点に半分ずつ与えます。圧力は `pressure * (target_area - 面積) / target_area` で、
+    /// 各辺の力は圧力に辺の長さを掛けた大きさです。位置を直接補正する体積拘束と異なり、力として
+    /// 速度に加わるため、膨らむ際には慣性で目標の面積をわずかに行き過ぎてから落ち着きます。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PressureConstraint {
+        /// 外周の質点（リングの順）
+        pub particle_indices: Vec<usize>,
+        /// 目標の面積
+        pub target_area: Scalar,
+        /// 圧力の強さ
+        pub pressure: Scalar,
+    }
+
+    impl PressureConstraint {
+        /// 新しい `PressureConstraint` を作成します。目標の面積は現在の外周の面積です。
+        pub fn new(particle_indices: Vec<usize>, pressure: Scalar, particles: &[Particle]) -> Self {
+            let mut constraint = Self { particle_indices, target_area: 0.0, pressure };
+            constraint.target_area = constraint.area(particles);
+            constraint
+        }
+
+        /// 外周の現在の面積を返します。
+        pub fn area(&self, particles: &[Particle]) -> Scalar {
+            let points: Vec<Vec2> = self.particle_indices.iter().map(|&i| particles[i].pos).collect();
+            geometry::polygon_area(&points)
+        }
+
+        /// 圧力による力を `dt` 秒分の速度の変化として質点に加えます。固定質点は動かしません。
+        fn apply(&self, particles: &mut [Particle], dt: Scalar) {
+            if self.target_area < Scalar::EPSILON { return; }
+            let twice_signed_area: Scalar = geometry::circular_windows(&self.particle_indices)
+                .map(|(a, b)| Vec2::cross(particles[a].pos, particles[b].pos))
+                .sum();
+            let pressure = self.pressure * (self.target_area - twice_signed_area.abs() * 0.5) / self.target_area;
+            // 反時計回り（符号付き面積が正）のとき、辺の右側が外側
+            let outward = if twice_signed_area >= 0.0 { -1.0 } else { 1.0 };
+            for (a, b) in geometry::circular_windows(&self.particle_indices) {
+                // 辺の長さを掛けた外向きの法線
+                let force = (particles[b].pos - particles[a].pos).perpendicular() * (outward * pressure * 0.5);
+                for i in [a, b] {
+                    let p = &mut particles[i];
+                    if p.is_fixed { continue; }
+                    p.vel += force * (p.inv_mass * dt);
+                }
+            }
+        }
+    }
+
+    /// 形状生成時のエラーを定義
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ShapeError {
+        SelfIntersecting,
+        NotEnoughParticles,
+        /// 隣接する質点の間隔が質点の直径より小さく、生成直後から重なってしまう
+        OverlappingParticles,
+        /// 2つの点の距離が `geometry::COINCIDENT_EPSILON` 以下で、長さ0のバネや退化した形状維持拘束ができてしまう
+        CoincidentPoints,
+    }
+
+    impl fmt::Display for ShapeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ShapeError::SelfIntersecting => write!(f, "The provided shape is self-intersecting."),
+                ShapeError::NotEnoughParticles => write!(f, "Not enough particles for a closed shape (minimum 3)."),
+                ShapeError::OverlappingParticles => write!(f, "Particle spacing is smaller than the particle diameter."),
+                ShapeError::CoincidentPoints => write!(f, "Two or more points of the shape coincide."),
+            }
+        }
+    }
+
+    /// 質点の操作時のエラーを定義
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParticleError {
+        /// 質点のインデックスが範囲外
+        IndexOutOfBounds,
+        /// 質点が外周ワイヤーまたは曲げ拘束の一部で、取り除くとリングの構造が壊れる
+        OnOutline,
+    }
+
+    impl fmt::Display for ParticleError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ParticleError::IndexOutOfBounds => write!(f, "Particle index is out of bounds."),
+                ParticleError::OnOutline => write!(f, "Particle is part of an outline ring and cannot be removed."),
+            }
+        }
+    }
+
+    /// `Simulation::validate` で見つかった不整合。
+    ///
+    /// バネの `body` は所属するボディで、`None` は `connect` で追加したバネを表します。
+    /// `spring` はその中での番号です。
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ValidationError {
+        /// 質点の位置または速度が有限でない
+        NonFiniteParticle { index: usize },
+        /// バネの端点のインデックスが範囲外
+        SpringIndexOutOfBounds { body: Option<SoftBodyHandle>, spring: usize },
+        /// バネの静止長が正の有限値でない
+        InvalidRestLength { body: Option<SoftBodyHandle>, spring: usize, rest_length: Scalar },
+        /// 形状維持拘束の質点のインデックスが範囲外
+        ShapeIndexOutOfBounds { body: SoftBodyHandle, index: usize },
+    }
+
+    impl fmt::Display for ValidationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ValidationError::NonFiniteParticle { index } => write!(f, "Particle {index} has a non-finite position or velocity."),
+                ValidationError::SpringIndexOutOfBounds { body, spring } => write!(f, "Spring {spring} of {body:?} references a particle out of bounds."),
+                ValidationError::InvalidRestLength { body, spring, rest_length } => write!(f, "Spring {spring} of {body:?} has an invalid rest length {rest_length}."),
+                ValidationError::ShapeIndexOutOfBounds { body, index } => write!(f, "Shape constraint of {body:?} references particle {index} out of bounds."),
+            }
+        }
+    }
+
+    /// `Simulation` 内のソフトボディを指すハンドル。
+    ///
+    /// ボディの追加時に割り当てられる一意な ID で、他のボディの追加・削除によって
+    /// `soft_bodies()` 内の位置が変わっても同じボディを指し続けます。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct SoftBodyHandle(usize);
+
+    /// `Simulation::connect` で追加されたバネを指すハンドル。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct SpringHandle(usize);
+
+    /// `Simulation::add_spawner` で追加されたスポナーを指すハンドル。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct SpawnerHandle(usize);
+
+    /// `Simulation::add_heat_source` で追加された熱源を指すハンドル。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct HeatSourceHandle(usize);
+
+    /// バネを柔らかくする熱源。`Simulation::add_heat_source` で登録します。
+    ///
+    /// 点 `p` での熱は `intensity * (1 - |p - center| / radius)`（半径の外では `0`）で、複数の熱源の熱は
+    /// 足し合わされます。熱 `h` の位置にあるバネの剛性は `1 / (1 + h)` 倍になります。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct HeatSource {
+        pub center: Vec2,
+        pub radius: Scalar,
+        pub intensity: Scalar,
+    }
+
+    impl HeatSource {
+        /// 点 `pos` での熱を返します。`radius` が `0` 以下の場合や `intensity` が負の場合は `0.0` です。
+        pub fn heat_at(&self, pos: Vec2) -> Scalar {
+            if self.radius <= 0.0 {
+                return 0.0;
+            }
+            let falloff = 1.0 - (pos - self.center).length() / self.radius;
+            self.intensity.max(0.0) * falloff.max(0.0)
+        }
+    }
+
+    /// ソフトボディを構成する要素の集合。
+    /// 実際の質点データは `Simulation` が所有し、`SoftBody` はインデックスで管理します。
+    #[derive(Debug, Clone)]
+    pub struct SoftBody {
+        pub particle_indices: Vec<usize>,
+        pub springs: Vec<Spring>,
+        pub shape_constraint: Option<ShapeMatchingConstraint>,
+        /// ワイヤーフレーム衝突判定用の外周ワイヤー情報 (グローバルインデックス)
+        pub outline_wires: Option<Vec<(usize, usize)>>,
+        /// 外周リングの曲げ拘束
+        pub bend_constraints: Vec<BendConstraint>,
+        /// 関節の可動域を制限する角度拘束
+        pub angle_constraints: Vec<AngleConstraint>,
+        /// 外周を内側から押す圧力
+        pub pressure_constraints: Vec<PressureConstraint>,
+        handle: SoftBodyHandle,
+        tag: Option<u64>,
+        /// `add_soft_body` で作られた格子状のボディの `(rows, cols)`。`particle_indices` は行優先で並びます。
+        grid: Option<(usize, usize)>,
+        /// 衝突の応答を行わず、重なりの検出だけに使うセンサーかどうか
+        is_sensor: bool,
+    }
+
+    impl SoftBody {
+        /// ハンドル未割り当ての `SoftBody` を作成します。
+        /// ハンドルは `Simulation::push_body` で登録時に割り当てられます。
+        fn new(
+            particle_indices: Vec<usize>,
+            springs: Vec<Spring>,
+            shape_constraint: Option<ShapeMatchingConstraint>,
+            outline_wires: Option<Vec<(usize, usize)>>,
+        ) -> Self {
+            Self {
+                particle_indices,
+                springs,
+                shape_constraint,
+                outline_wires,
+                bend_constraints: Vec::new(),
+                angle_constraints: Vec::new(),
+                pressure_constraints: Vec::new(),
+                handle: SoftBodyHandle(usize::MAX),
+                tag: None,
+                grid: None,
+                is_sensor: false,
+            }
+        }
+
+        /// このボディのハンドルを返します。
+        pub fn handle(&self) -> SoftBodyHandle {
+            self.handle
+        }
+
+        /// 生成時に `SoftBodyConfig::tag` で指定されたタグを返します。
+        pub fn tag(&self) -> Option<u64> {
+            self.tag
+        }
+
+        /// 生成時に `SoftBodyConfig::is_sensor` で指定された、センサーのボディかどうかを返します。
+        pub fn is_sensor(&self) -> bool {
+            self.is_sensor
+        }
+
+        /// `add_soft_body` で作られた格子状のボディの `(rows, cols)` を返します。
+        /// 凸形状やチェーンなど格子でないボディ、および質点を取り除かれたボディでは `None` です。
+        pub fn grid_dimensions(&self) -> Option<(usize, usize)> {
+            self.grid
+        }
+
+        /// 格子状のボディの `row` 行 `col` 列目の質点の、シミュレーション全体でのインデックスを返します。
+        /// 格子でないボディや、範囲外の行・列の場合は `None` を返します。
+        pub fn grid_index(&self, row: usize, col: usize) -> Option<usize> {
+            let (rows, cols) = self.grid?;
+            if row >= rows || col >= cols {
+                return None;
+            }
+            self.particle_indices.get(row * cols + col).copied()
+        }
+
+        /// 形状維持拘束の静止形状（初期形状と重心）を現在の質点の位置から取り直します。
+        /// 変形させた形をそのまま新しい静止形状として受け入れる場合に使います。
+        /// バネと曲げ拘束の静止状態は変更しません。形状維持拘束が無い場合は何もしません。
+        pub fn set_rest_shape_to_current(&mut self, particles: &[Particle]) {
+            if let Some(sc) = &mut self.shape_constraint {
+                *sc = sc.rebuilt(particles);
+            }
+        }
+
+        /// ボディの全質点の位置の平均（質量で重み付けしない重心）を返します。質点が無い場合は原点を返します。
+        pub fn centroid(&self, particles: &[Particle]) -> Vec2 {
+            if self.particle_indices.is_empty() {
+                return Vec2::new(0.0, 0.0);
+            }
+            let sum = self.particle_indices.iter().fold(Vec2::new(0.0, 0.0), |acc, &i| acc + particles[i].pos);
+            sum * (1.0 / self.particle_indices.len() as Scalar)
+        }
+
+        /// 外周の頂点の現在位置を `outline_wires` の順に返します。
+        /// `geometry` モジュールの多角形を受け取る関数にそのまま渡せます。
+        /// 外周ワイヤーを持たないボディでは空の `Vec` を返します。
+        pub fn outline_points(&self, particles: &[Particle]) -> Vec<Vec2> {
+            match &self.outline_wires {
+                Some(wires) => wires.iter().map(|&(a, _)| particles[a].pos).collect(),
+                None => Vec::new(),
+            }
+        }
+
+        /// 外周の各辺の外向き単位法線を、`outline_wires` と同じ順序で返します。
+        ///
+        /// 外周の巡回方向は符号付き面積から判定するため、時計回り・反時計回りのどちらでも
+        /// 外側を向きます。外周ワイヤーを持たないボディでは空の `Vec` を返します。
+        pub fn edge_normals(&self, particles: &[Particle]) -> Vec<Vec2> {
+            let Some(wires) = &self.outline_wires else { return Vec::new() };
+            let twice_signed_area: Scalar = wires.iter().map(|&(a, b)| Vec2::cross(particles[a].pos, particles[b].pos)).sum();
+            // 反時計回り（符号付き面積が正）のとき、辺の右側が外側
+            let outward = if twice_signed_area >= 0.0 { -1.0 } else { 1.0 };
+            wires
+                .iter()
+                .map(|&(a, b)| (particles[b].pos - particles[a].pos).perpendicular().normalize() * outward)
+                .collect()
+        }
+
+        /// 外周の各頂点の外向き単位法線を返します。
+        ///
+        /// `i` 番目の要素は `outline_wires[i]` の始点の法線で、その頂点に接する2辺
+        /// （`outline_wires[i - 1]` と `outline_wires[i]`）の法線の平均です。
+        /// 外周ワイヤーを持たないボディでは空の `Vec` を返します。
+        pub fn vertex_normals(&self, particles: &[Particle]) -> Vec<Vec2> {
+            let edges = self.edge_normals(particles);
+            let n = edges.len();
+            (0..n).map(|i| (edges[(i + n - 1) % n] + edges[i]).normalize()).collect()
+        }
+
+        /// 点 `point` が外周の内側にあるかを偶奇規則で判定します。
+        /// 外周ワイヤーを持たないボディでは常に `false` を返します。
+        pub fn contains_point(&self, particles: &[Particle], point: Vec2) -> bool {
+            let Some(wires) = &self.outline_wires else { return false };
+            let crossings = wires
+                .iter()
+                .filter(|&&(a, b)| geometry::ray_crosses_edge(point, particles[a].pos, particles[b].pos))
+                .count();
+            crossings % 2 == 1
+        }
+
+        /// ボディの内側の点 `point` における速度を、最も近い数個の質点の速度の逆距離加重平均で返します。
+        ///
+        /// 内外の判定は、外周を持つボディでは外周、持たないボディでは質点の凸包で行い、外側の点では
+        /// `None` を返します。`point` が質点の位置と一致する場合は、その質点の速度を返します。
+        pub fn velocity_at(&self, point: Vec2, particles: &[Particle]) -> Option<Vec2> {
+            // 平均に使う質点の数
+            const NEIGHBORS: usize = 4;
+            let inside = if self.outline_wires.is_some() {
+                self.contains_point(particles, point)
+            } else {
+                let positions: Vec<Vec2> = self.particle_indices.iter().map(|&i| particles[i].pos).collect();
+                geometry::point_in_polygon(point, &geometry::convex_hull(&positions))
+            };
+            if !inside { return None; }
+
+            let mut nearest: Vec<(Scalar, Vec2)> = self.particle_indices.iter()
+                .map(|&i| ((particles[i].pos - point).length(), particles[i].vel))
+                .collect();
+            nearest.sort_by(|a, b| a.0.total_cmp(&b.0));
+            nearest.truncate(NEIGHBORS);
+            if nearest.first().is_some_and(|&(dist, _)| dist < Scalar::EPSILON) {
+                return Some(nearest[0].1);
+            }
+            let (sum, total_weight) = nearest.iter().fold((Vec2::new(0.0, 0.0), 0.0), |(sum, total), &(dist, vel)| {
+                (sum + vel * (1.0 / dist), total + 1.0 / dist)
+            });
+            Some(sum * (1.0 / total_weight))
+        }
+
+        /// 点 `point` からボディの表面（質点の円、および外周ワイヤー、外周が無い場合はバネ）までの
+        /// 符号付きの隙間を返します。負の値は `point` が質点や辺の太さの内側にあることを表します。
+        fn surface_gap(&self, particles: &[Particle], point: Vec2) -> Scalar {
+            let edges: Vec<(usize, usize)> = match &self.outline_wires {
+                Some(wires) => wires.clone(),
+                None => self.springs.iter().map(|s| (s.p1_index, s.p2_index)).collect(),
+            };
+            let edge_gaps = edges.into_iter().map(|(a, b)| {
+                let (pa, pb) = (&particles[a], &particles[b]);
+                let (dist_sq, _) = geometry::dist_sq_to_segment(point, pa.pos, pb.pos);
+                dist_sq.sqrt() - (pa.radius + pb.radius) * 0.5
+            });
+            let particle_gaps = self.particle_indices.iter().map(|&i| (point - particles[i].pos).length() - particles[i].radius);
+            edge_gaps.chain(particle_gaps).fold(Scalar::INFINITY, Scalar::min)
+        }
+
+        /// 参照している質点のインデックスを `remap[旧インデックス] = 新インデックス` に従って付け替えます。
+        fn remap_indices(&mut self, remap: &[usize]) {
+            for i in &mut self.particle_indices {
+                *i = remap[*i];
+            }
+            for spring in &mut self.springs {
+                spring.p1_index = remap[spring.p1_index];
+                spring.p2_index = remap[spring.p2_index];
+            }
+            if let Some(sc) = &mut self.shape_constraint {
+                for i in &mut sc.particle_indices {
+                    *i = remap[*i];
+                }
+            }
+            if let Some(wires) = &mut self.outline_wires {
+                for (a, b) in wires {
+                    *a = remap[*a];
+                    *b = remap[*b];
+                }
+            }
+            for bend in &mut self.bend_constraints {
+                bend.prev_index = remap[bend.prev_index];
+                bend.cur_index = remap[bend.cur_index];
+                bend.next_index = remap[bend.next_index];
+            }
+            for angle in &mut self.angle_constraints {
+                angle.a = remap[angle.a];
+                angle.b = remap[angle.b];
+                angle.c = remap[angle.c];
+            }
+            for pressure in &mut self.pressure_constraints {
+                for i in &mut pressure.particle_indices {
+                    *i = remap[*i];
+                }
+            }
+        }
+    }
+
+    /// 1ステップの間に発生した2つの質点の衝突。`Simulation::collision_events` で取得します。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct CollisionEvent {
+        /// 衝突した質点のインデックス (`a < b`)
+        pub a: usize,
+        pub b: usize,
+        /// 接触点（重なり合った2つの円の表面の中点）
+        pub point: Vec2,
+        /// `b` から `a` へ向かう単位法線
+        pub normal: Vec2,
+        /// ステップ全体で `a` に与えられた力積の大きさ（`b` には逆向きに同じ大きさ）
+        pub impulse: Scalar,
+    }
+
+    /// 境界（`SimulationConfig::bounds`）の辺。y 軸は下向き（画面座標）で、`Top` が `min.y`、`Bottom` が `max.y` の辺です。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub enum Side {
+        Left,
+        Right,
+        Top,
+        Bottom,
+    }
+
+    /// 1ステップの間に発生した質点と境界の衝突。`Simulation::boundary_events` で取得します。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct BoundaryEvent {
+        /// 衝突した質点のインデックス
+        pub particle: usize,
+        /// 衝突した境界の辺
+        pub side: Side,
+        /// 接触した瞬間の、辺に向かう法線方向の速さ
+        pub impact_speed: Scalar,
+    }
+
+    /// 半直線と質点の円の交差。`Simulation::raycast` / `Simulation::raycast_all` で取得します。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct RaycastHit {
+        /// 交わった質点のインデックス
+        pub particle: usize,
+        /// 始点から交点までの距離
+        pub distance: Scalar,
+        /// 半直線が円に入る点
+        pub point: Vec2,
+        /// 交点での円の外向き単位法線（始点が円の内側にある場合は半直線の逆向き）
+        pub normal: Vec2,
+    }
+
+    /// シーンの規模の集計。`Simulation::stats` で取得します。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+    pub struct SimStats {
+        /// 質点の数
+        pub particle_count: usize,
+        /// ソフトボディの数
+        pub body_count: usize,
+        /// バネの数。ボディのバネに加え、`connect` で追加したバネと固定点へのバネ（`FixedSpring`）を含みます。
+        pub spring_count: usize,
+        /// 曲げ拘束の数
+        pub bend_constraint_count: usize,
+        /// 角度拘束の数
+        pub angle_constraint_count: usize,
+        /// 形状維持拘束の数
+        pub shape_constraint_count: usize,
+        /// ワイヤー衝突の対象となる外周ワイヤー（辺）の数
+        pub collider_count: usize,
+    }
+
+    /// 描画用の円（質点）の種類。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum CircleKind {
+        Free,
+        Fixed,
+    }
+
+    /// 描画用の線分の種類。描画側で色を選ぶ手掛かりに使います。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub enum LineHint {
+        /// バネ。`stretch` は `Spring::stretch_ratio` の値（静止長で `0.0`、大きく伸縮するほど `1.0`）です。
+        Spring { stretch: Scalar },
+        /// 外周ワイヤーの辺
+        Outline,
+    }
+
+    /// 描画用の円。質点1つに対応します。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct Circle {
+        pub center: Vec2,
+        pub radius: Scalar,
+        pub kind: CircleKind,
+    }
+
+    /// 描画用の線分。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct LineSeg {
+        pub a: Vec2,
+        pub b: Vec2,
+        pub color_hint: LineHint,
+    }
+
+    /// 描画用の軸平行な矩形。
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub struct Aabb {
+        pub min: Vec2,
+        pub max: Vec2,
+        /// 矩形が囲むボディ。`None` はシミュレーションの境界（`SimulationConfig::bounds`）です。
+        pub body: Option<SoftBodyHandle>,
+    }
+
+    /// シミュレーションの状態を描画するための図形の一覧。`Simulation::debug_draw` で取得します。
+    /// 描画ライブラリに依存しないため、どの描画側からもシミュレーションの内部を知らずに使えます。
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct DebugDrawData {
+        /// 質点ごとの円（質点のインデックス順）
+        pub circles: Vec<Circle>,
+        /// バネ（`Simulation::springs` の順）に続けて、外周ワイヤーの辺（`Simulation::iter_outline_edges` の順）
+        pub lines: Vec<LineSeg>,
+        /// シミュレーションの境界（設定されている場合は先頭）と、質点を持つボディごとのバウンディングボックス
+        pub aabbs: Vec<Aabb>,
+    }
+
+    /// 衝突解決の各反復で見つかった接触を、質点の組ごとにまとめます。
+    #[derive(Debug, Default)]
+    struct ContactAccumulator {
+        /// `impulse` には位置補正量の合計を保持し、ステップの最後に `dt` で割って力積に換算します。
+        events: Vec<CollisionEvent>,
+        index: BTreeMap<(usize, usize), usize>,
+    }
+
+    impl ContactAccumulator {
+        /// 質点 `i` を `j` から `normal` 方向に `correction` だけ押し離す接触を記録します。
+        fn record(&mut self, i: usize, j: usize, point: Vec2, normal: Vec2, correction: Scalar) {
+            let (a, b, normal) = if i < j { (i, j, normal) } else { (j, i, normal * -1.0) };
+            if let Some(&k) = self.index.get(&(a, b)) {
+                self.events[k].impulse += correction;
+            } else {
+                self.index.insert((a, b), self.events.len());
+                self.events.push(CollisionEvent { a, b, point, normal, impulse: correction });
+            }
+        }
+    }
+
+    /// `Simulation::solve_constraints` で解決する拘束の参照（ボディの番号と拘束の番号）。
+    #[derive(Debug, Copy, Clone)]
+    enum ConstraintRef {
+        Spring(usize, usize),
+        Bend(usize, usize),
+        Angle(usize, usize),
+        Shape(usize),
+        Connection(usize),
+        FixedSpring(usize),
+        Drag,
+    }
+
+    /// シミュレーション内部で使う、シード指定可能な擬似乱数生成器 (xorshift64*)。
+    #[derive(Debug, Clone)]
+    struct Rng(u64);
+
+    impl Rng {
+        fn new(seed: u64) -> Self {
+            // splitmix64 で初期状態を攪拌し、シード 0 でも状態が 0 にならないようにする
+            let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            Self(if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z })
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut x = self.0;
+            x ^= x >> 12;
+            x ^= x << 25;
+            x ^= x >> 27;
+            self.0 = x;
+            x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        /// `[0, 1)` の一様乱数を返します。
+        fn next_unit(&mut self) -> Scalar {
+            let bits = Scalar::MANTISSA_DIGITS;
+            (self.next_u64() >> (64 - bits)) as Scalar * (1.0 / (1u64 << bits) as Scalar)
+        }
+
+        /// 半径 `radius` の円内の一様な点を返します。
+        fn in_disc(&mut self, radius: Scalar) -> Vec2 {
+            let r = radius * self.next_unit().sqrt();
+            let angle = self.next_unit() * consts::TAU;
+            Vec2::new(r * float::cos(angle), r * float::sin(angle))
+        }
+    }
+
+    /// `Simulation::snapshot` で保存したシミュレーションの状態。`Simulation::restore` で復元できます。
+    ///
+    /// 質点、ボディ（拘束を含む）、`connect` のバネ、固定点へのバネ、ドラッグ拘束、スポナー、乱数生成器、
+    /// ステップ数を保持します。設定（`SimulationConfig`）と熱源は含みません。
+    #[derive(Debug, Clone)]
+    pub struct Snapshot {
+        particles: Vec<Particle>,
+        soft_bodies: Vec<SoftBody>,
+        connections: Vec<(SpringHandle, Spring)>,
+        fixed_springs: Vec<(SpringHandle, FixedSpring)>,
+        drag: Option<DragConstraint>,
+        spawners: Vec<(SpawnerHandle, Spawner)>,
+        particle_owners: Vec<Option<SoftBodyHandle>>,
+        rng: Rng,
+        step_count: usize,
+    }
+
+    impl Snapshot {
+        /// 保存した時点の質点を返します。
+        pub fn particles(&self) -> &[Particle] {
+            &self.particles
+        }
+
+        /// 保存した時点の `Simulation::step_count` を返します。
+        pub fn step_count(&self) -> usize {
+            self.step_count
+        }
+    }
+
+    /// シミュレーション全体の環境と状態を管理する構造体。
+    #[derive(Debug, Clone)]
+    pub struct Simulation {
+        pub particles: Vec<Particle>,
+        soft_bodies: Vec<SoftBody>,
+        config: SimulationConfig,
+        /// 次に割り当てるソフトボディの ID
+        next_body_id: usize,
+        /// ボディに属さない、`connect` で追加されたバネ
+        connections: Vec<(SpringHandle, Spring)>,
+        /// 質点と固定点を結ぶバネ
+        fixed_springs: Vec<(SpringHandle, FixedSpring)>,
+        /// 設定中のドラッグ拘束
+        drag: Option<DragConstraint>,
+        /// 次に割り当てるバネの ID
+        next_spring_id: usize,
+        /// `SimulationConfig::seed` で初期化される乱数生成器
+        rng: Rng,
+        /// 直前のステップで発生した衝突
+        collision_events: Vec<CollisionEvent>,
+        boundary_events: Vec<BoundaryEvent>,
+        /// 直前のステップで接触していたボディの組
+        body_contacts: Vec<(SoftBodyHandle, SoftBodyHandle)>,
+        spawners: Vec<(SpawnerHandle, Spawner)>,
+        next_spawner_id: usize,
+        heat_sources: Vec<(HeatSourceHandle, HeatSource)>,
+        next_heat_source_id: usize,
+        /// 質点のインデックスから、その質点を含むボディへの逆引き表
+        particle_owners: Vec<Option<SoftBodyHandle>>,
+        /// これまでに進めたステップ数（`warm_start` のステップを含む）
+        step_count: usize,
+        /// `enable_history` で有効にした、直近のステップの状態（古い順）
+        history: VecDeque<Snapshot>,
+        /// 履歴に保持するステップ数。`0` の場合は履歴を記録しません。
+        history_capacity: usize,
+    }
+    
+    /// `SoftBody` を生成するための設定。ビルダーパターンのように使用します。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SoftBodyConfig {
+        pub center: Vec2,
+        pub size: Vec2,
+        pub rows: usize,
+        pub cols: usize,
+        pub stiffness: Scalar,
+        pub shape_stiffness: Scalar,
+        pub is_fixed: bool,
+        pub particle_radius: Scalar,
+        pub particle_inv_mass: Scalar,
+        /// 曲げ剛性。`0.0` の場合は曲げ拘束を生成しません。
+        /// チェーンでは1つ飛ばしの質点間のバネとして生成されます。
+        pub bend_stiffness: Scalar,
+        /// チェーンの最初の質点を固定するかどうか
+        pub pin_first: bool,
+        /// チェーンの最後の質点を固定するかどうか
+        pub pin_last: bool,
+        /// ボディに付ける任意のタグ（描画色や材質の識別など）。`SoftBody::tag` で取得できます。
+        pub tag: Option<u64>,
+        /// 生成時に各質点の初期位置をずらす最大距離。完全に対称な配置の不自然な釣り合いを崩すために使います。
+        /// ずれはシミュレーションの乱数生成器から決まり、拘束の静止状態はずらした後の位置になります。
+        pub jitter: Scalar,
+        /// `add_convex_body` で生成する殻の層の数。`1`（デフォルト）は外周のみで、
+        /// 2以上では内側に同心のリングを重ねて厚みを持たせ、衝撃が内部まで突き抜けにくくなります。
+        pub shell_layers: usize,
+        /// `add_chain` で、各関節（隣り合う3つの質点の中央）の内角を `(min_angle, max_angle)`
+        /// （ラジアン）に制限する角度拘束を生成します。剛性は `stiffness` を使います。`None` の場合は制限しません。
+        pub angle_limits: Option<(Scalar, Scalar)>,
+        /// `add_soft_body` で格子の内部にもバネを張るかどうか（デフォルト `true`）。`false` の場合は
+        /// 格子の外周に沿ったバネのみを生成し、形状の維持は形状維持拘束に任せることで拘束の数を減らします。
+        pub interior_springs: bool,
+        /// `add_convex_body` で、外周を生成時の面積に保とうとする圧力（`PressureConstraint`）の強さ。
+        /// 面積が縮むと外周を外向きに押して膨らませます。`None` の場合は圧力を与えません。
+        /// 外周を持たない `add_soft_body` や `add_chain` のボディでは使われません。
+        pub target_pressure: Option<Scalar>,
+        /// 衝突の応答を行わないセンサーのボディにするかどうか（デフォルト `false`）。センサーは他の質点を
+        /// 押し戻さず、自身も押されません。外周の内側に入った他のボディは `Simulation::sensor_overlaps` で取得できます。
+        /// ゴール領域や落下判定の領域に使い、動かない領域にするには `is_fixed` と併用します。
+        pub is_sensor: bool,
+        /// `add_soft_body` で格子の外周に沿った外周ワイヤー（`SoftBody::outline_wires`）を生成するかどうか
+        /// （デフォルト `false`）。形状維持拘束の有無とは独立に、描画やワイヤーの衝突に使える外周を持たせます。
+        /// 行または列が1つしかない格子では外周を作れないため生成しません。
+        pub outline: bool,
     }
 
     impl Default for SoftBodyConfig {
@@ -444,72 +1852,545 @@ pub mod core {
                 is_fixed: false,
                 particle_radius: 8.0,
                 particle_inv_mass: 1.0,
+                bend_stiffness: 0.0,
+                pin_first: false,
+                pin_last: false,
+                tag: None,
+                jitter: 0.0,
+                shell_layers: 1,
+                angle_limits: None,
+                interior_springs: true,
+                target_pressure: None,
+                is_sensor: false,
+                outline: false,
             }
         }
     }
 
-    /// シミュレーションのグローバル設定。
+    /// `Simulation::add_body` とスポナーが生成するボディの形状。
+    ///
+    /// `Simulation::add_body` では、`Polygon` と `Chain` の点は `add_convex_body`, `add_chain` と同じく絶対座標です。
+    /// スポナーでは、これらの点は出現位置からの相対座標として扱われます。
     #[derive(Debug, Clone, PartialEq)]
-    pub struct SimulationConfig {
-        pub gravity: Vec2,
-        pub damping: f64,
-        pub solver_iterations: usize,
-        /// 境界。`Some(min, max)` で設定。`None` の場合は境界なし。
-        pub bounds: Option<(Vec2, Vec2)>,
-        pub use_volumetric_collisions: bool,
-        /// ワイヤーフレーム衝突を有効にするオプション
-        pub use_wire_collisions: bool,
+    pub enum ShapeKind {
+        /// `add_soft_body` による `rows` 行 `cols` 列の格子。中心と大きさは設定の `center`, `size` を使います。
+        Grid { rows: usize, cols: usize },
+        /// `add_convex_body` による外周を持つ多角形
+        Polygon(Vec<Vec2>),
+        /// `add_chain` によるチェーン
+        Chain(Vec<Vec2>),
+        /// 設定の `center` を中心とし、半径 `radius` の円に内接する `segments` 角形を、`add_convex_body` で生成します。
+        Circle { radius: Scalar, segments: usize },
     }
 
-    impl Default for SimulationConfig {
-        fn default() -> Self {
-            Self {
-                gravity: Vec2::new(0.0, 270.0),
-                damping: 0.99,
-                solver_iterations: 8,
-                bounds: None,
-                use_volumetric_collisions: false,
-                use_wire_collisions: false, // デフォルトでは無効
+    impl ShapeKind {
+        /// `Polygon` と `Chain` の点を `offset` だけ平行移動した形状を返します。その他の形状はそのまま返します。
+        fn translated(self, offset: Vec2) -> Self {
+            let translate = |points: Vec<Vec2>| points.into_iter().map(|p| p + offset).collect();
+            match self {
+                Self::Polygon(points) => Self::Polygon(translate(points)),
+                Self::Chain(points) => Self::Chain(translate(points)),
+                shape => shape,
             }
         }
     }
 
-    impl Simulation {
-        /// 新しいシミュレーション環境を作成します。
-        pub fn new(config: SimulationConfig) -> Self {
-            Self {
-                particles: Vec::new(),
-                soft_bodies: Vec::new(),
-                config,
+    /// 一定の時間間隔でボディを生成し続けるスポナー。`Simulation::add_spawner` で登録します。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Spawner {
+        /// ボディの出現位置（格子の中心、または相対座標の原点）
+        pub position: Vec2,
+        /// 生成の間隔（秒）。正でない場合は何も生成しません。
+        pub interval: Scalar,
+        /// 生成するボディの設定。`center` は `position` で置き換えられます。
+        pub template: SoftBodyConfig,
+        /// 生成するボディの形状。`Polygon`, `Chain` の点は `position` からの相対座標です。
+        pub shape: ShapeKind,
+        /// 生成するボディの数の上限。`None` の場合は無制限です。
+        pub max_bodies: Option<usize>,
+        /// 前回の生成からの経過時間
+        elapsed: Scalar,
+        spawned: usize,
+    }
+
+    impl Spawner {
+        /// 新しい `Spawner` を作成します。上限は無く、最初のボディは `interval` 経過後に生成されます。
+        pub fn new(position: Vec2, interval: Scalar, template: SoftBodyConfig, shape: ShapeKind) -> Self {
+            Self { position, interval, template, shape, max_bodies: None, elapsed: 0.0, spawned: 0 }
+        }
+
+        /// これまでに生成したボディの数を返します。
+        pub fn spawned(&self) -> usize {
+            self.spawned
+        }
+    }
+
+    /// `SceneBuilder` に登録されたボディの記述。点は絶対座標です。
+    #[derive(Debug, Clone, PartialEq)]
+    enum SceneBody {
+        Grid(SoftBodyConfig),
+        Convex(Vec<Vec2>, SoftBodyConfig),
+        Chain(Vec<Vec2>, SoftBodyConfig),
+    }
+
+    /// `SceneBuilder::build` で生成できなかったボディ。`index` は登録順の番号です。
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SceneError {
+        pub index: usize,
+        pub error: ShapeError,
+    }
+
+    impl fmt::Display for SceneError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Body {}: {}", self.index, self.error)
+        }
+    }
+
+    /// ボディの記述を集めて、まとめて `Simulation` を構築するビルダー。
+    /// `add_*` を1つずつ呼んで `unwrap` する代わりに、全てのボディのエラーを一度に受け取れます。
+    #[derive(Debug, Clone, Default)]
+    pub struct SceneBuilder {
+        config: SimulationConfig,
+        bodies: Vec<SceneBody>,
+    }
+
+    impl SceneBuilder {
+        /// 設定 `config` のシミュレーションを構築する、空のビルダーを作成します。
+        pub fn new(config: SimulationConfig) -> Self {
+            Self { config, bodies: Vec::new() }
+        }
+
+        /// `Simulation::add_soft_body` による格子状のボディを追加します。
+        pub fn soft_body(mut self, config: SoftBodyConfig) -> Self {
+            self.bodies.push(SceneBody::Grid(config));
+            self
+        }
+
+        /// `Simulation::add_convex_body` による凸形状のボディを追加します。
+        pub fn convex_body(mut self, points: Vec<Vec2>, config: SoftBodyConfig) -> Self {
+            self.bodies.push(SceneBody::Convex(points, config));
+            self
+        }
+
+        /// `Simulation::add_chain` によるチェーンを追加します。
+        pub fn chain(mut self, points: Vec<Vec2>, config: SoftBodyConfig) -> Self {
+            self.bodies.push(SceneBody::Chain(points, config));
+            self
+        }
+
+        /// 登録した全てのボディを順に追加した `Simulation` と、登録順のハンドルを返します。
+        ///
+        /// 生成できないボディがある場合は、最初の1つで止めずに全てのボディを検査し、
+        /// 失敗した全てのボディの `SceneError` を登録順に返します。
+        pub fn build(self) -> Result<(Simulation, Vec<SoftBodyHandle>), Vec<SceneError>> {
+            let mut sim = Simulation::new(self.config);
+            let mut handles = Vec::with_capacity(self.bodies.len());
+            let mut errors = Vec::new();
+            for (index, body) in self.bodies.iter().enumerate() {
+                let result = match body {
+                    SceneBody::Grid(config) => sim.add_soft_body(config),
+                    SceneBody::Convex(points, config) => sim.add_convex_body(points, config),
+                    SceneBody::Chain(points, config) => Ok(sim.add_chain(points, config)),
+                };
+                match result {
+                    Ok(handle) => handles.push(handle),
+                    Err(error) => errors.push(SceneError { index, error }),
+                }
             }
+            if errors.is_empty() { Ok((sim, handles)) } else { Err(errors) }
+        }
+    }
+
+    /// 時間積分の方式。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+    pub enum Integrator {
+        /// 位置ベースの Verlet 積分（デフォルト）。
+        /// 拘束解決後の速度を変位 `(pos - prev_pos) / dt` から復元するため、
+        /// 拘束による位置の補正が速度にも反映されます。
+        #[default]
+        Verlet,
+        /// 半陰的（シンプレクティック）オイラー法。
+        /// 速度を積分してから位置を積分し、積分した速度をそのまま次のステップに引き継ぎます。
+        /// 拘束・衝突・境界による位置の補正は速度の射影として反映し、補正の向きに逆らう速度の成分だけを
+        /// 取り除きます。`Verlet` と異なり、重なりの押し戻しなどの補正によって速度が加わることはありません。
+        SemiImplicitEuler,
+    }
+
+    /// 位置に依存する重力場。`SimulationConfig::gravity_field` に設定すると、
+    /// 一定の `gravity` の代わりに各質点の位置での加速度が使われます。
+    #[derive(Debug, Copy, Clone)]
+    pub enum GravityField {
+        /// `center` に向かう逆2乗の引力。加速度の大きさは `strength / r^2` です。
+        Attractor { center: Vec2, strength: Scalar },
+        /// `center` の周りを反時計回り（y 軸下向きの画面座標では時計回り）に回す渦。
+        /// 加速度の大きさは `strength / r` で、向きは中心からの方向に垂直です。
+        Vortex { center: Vec2, strength: Scalar },
+        /// 位置から加速度を返す任意の関数
+        Custom(fn(Vec2) -> Vec2),
+    }
+
+    /// `Custom` 同士は関数ポインタのアドレスで比較します。
+    impl PartialEq for GravityField {
+        fn eq(&self, other: &Self) -> bool {
+            match (self, other) {
+                (
+                    GravityField::Attractor { center: c1, strength: s1 },
+                    GravityField::Attractor { center: c2, strength: s2 },
+                )
+                | (
+                    GravityField::Vortex { center: c1, strength: s1 },
+                    GravityField::Vortex { center: c2, strength: s2 },
+                ) => c1 == c2 && s1 == s2,
+                (GravityField::Custom(f1), GravityField::Custom(f2)) => core::ptr::fn_addr_eq(*f1, *f2),
+                _ => false,
+            }
+        }
+    }
+
+    impl GravityField {
+        /// 位置 `pos` における加速度を返します。中心と一致する位置では `0` を返します。
+        pub fn acceleration_at(&self, pos: Vec2) -> Vec2 {
+            match *self {
+                GravityField::Attractor { center, strength } => {
+                    let d = center - pos;
+                    let r_sq = d.length_squared();
+                    if r_sq < Scalar::EPSILON { return Vec2::new(0.0, 0.0); }
+                    d * (strength / (r_sq * r_sq.sqrt()))
+                }
+                GravityField::Vortex { center, strength } => {
+                    let d = pos - center;
+                    let r_sq = d.length_squared();
+                    if r_sq < Scalar::EPSILON { return Vec2::new(0.0, 0.0); }
+                    Vec2::new(-d.y, d.x) * (strength / r_sq)
+                }
+                GravityField::Custom(f) => f(pos),
+            }
+        }
+    }
+
+    /// 各反復で拘束を解決する順序。
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+    pub enum SolveOrder {
+        /// ボディの順に、バネ・曲げ拘束・形状維持拘束を逐次解決します（ガウス・ザイデル法、デフォルト）。
+        /// 収束は速いものの、先に解決された拘束に結果が偏ります。
+        #[default]
+        Sequential,
+        /// 逐次解決の順序を反復ごとにシミュレーションの乱数生成器でシャッフルします。
+        RandomizedPerIteration,
+        /// 全ての拘束を反復開始時の位置から解き、質点ごとに補正量の平均を適用します（ヤコビ法）。
+        /// 解決順序に依存しないため対称な配置は対称なまま保たれますが、収束は遅くなります。
+        Jacobi,
+    }
+
+    /// 質点を閉じ込める多角形の領域。`Simulation::set_containment` で設定します。
+    ///
+    /// 各反復で、領域の外に出た質点や、辺まで半径より近づいた質点を、最も近い辺の内向き法線の方向に
+    /// 押し戻します。内向き法線は頂点の巡回方向（符号付き面積の符号）から求めるため、時計回り・
+    /// 反時計回りのどちらで頂点を与えても構いません。凹多角形も扱えます。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ContainmentRegion {
+        polygon: Vec<Vec2>,
+        /// `polygon[i]` から `polygon[i + 1]` への辺の内向き単位法線
+        inward_normals: Vec<Vec2>,
+    }
+
+    impl ContainmentRegion {
+        /// 多角形 `polygon` の内側を領域とする `ContainmentRegion` を作成します。
+        /// 頂点が3つ未満の場合は `None` を返します。
+        pub fn new(polygon: Vec<Vec2>) -> Option<Self> {
+            if polygon.len() < 3 {
+                return None;
+            }
+            let twice_signed_area: Scalar = geometry::circular_windows(&polygon).map(|(a, b)| Vec2::cross(a, b)).sum();
+            // 反時計回り（符号付き面積が正）のとき、辺の左側が内側
+            let inward = if twice_signed_area >= 0.0 { 1.0 } else { -1.0 };
+            let inward_normals = geometry::circular_windows(&polygon)
+                .map(|(a, b)| (b - a).perpendicular().normalize() * inward)
+                .collect();
+            Some(Self { polygon, inward_normals })
+        }
+
+        /// 領域の多角形の頂点を返します。
+        pub fn polygon(&self) -> &[Vec2] {
+            &self.polygon
+        }
+
+        /// 半径 `radius` の質点が位置 `pos` にあるとき、領域に収めるための補正量を返します。
+        fn correction(&self, pos: Vec2, radius: Scalar) -> Vec2 {
+            let Some((k, closest)) = geometry::find_nearest_segment(&self.polygon, pos) else {
+                return Vec2::new(0.0, 0.0);
+            };
+            let normal = self.inward_normals[k];
+            if geometry::point_in_polygon(pos, &self.polygon) {
+                let dist = (pos - closest).length();
+                if dist < radius { normal * (radius - dist) } else { Vec2::new(0.0, 0.0) }
+            } else {
+                closest + normal * radius - pos
+            }
+        }
+
+        /// 半径 `radius` の質点が位置 `pos` で最も近い辺に接していて、`dir` の向きに動かすと
+        /// その辺の外へ押し込まれるかを判定します。
+        fn supports(&self, pos: Vec2, radius: Scalar, dir: Vec2) -> bool {
+            let Some((k, closest)) = geometry::find_nearest_segment(&self.polygon, pos) else { return false };
+            Vec2::dot(dir, self.inward_normals[k]) < 0.0 && (pos - closest).length() <= radius + CONTACT_TOLERANCE
+        }
+    }
+
+    /// シミュレーションのグローバル設定。
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SimulationConfig {
+        pub gravity: Vec2,
+        pub damping: Scalar,
+        pub solver_iterations: usize,
+        /// 境界。`Some(min, max)` で設定。`None` の場合は境界なし。
+        pub bounds: Option<(Vec2, Vec2)>,
+        /// 多角形の閉じ込め領域。`bounds` と併用でき、`None` の場合は領域なし。
+        pub containment: Option<ContainmentRegion>,
+        pub use_volumetric_collisions: bool,
+        /// ワイヤーフレーム衝突を有効にするオプション
+        pub use_wire_collisions: bool,
+        /// 時間積分の方式
+        pub integrator: Integrator,
+        /// 位置に依存する重力場。`Some` の場合は `gravity` の代わりに使われます。
+        pub gravity_field: Option<GravityField>,
+        /// 乱数生成器のシード。`Simulation::new` の時点の値が使われ、同じシードなら同じ結果になります。
+        pub seed: u64,
+        /// 質点同士の衝突イベントとして報告する力積の閾値。`None` の場合は衝突イベントを記録しません。
+        pub collision_event_threshold: Option<Scalar>,
+        /// 境界との衝突イベントとして報告する衝突の速さ（`BoundaryEvent::impact_speed`）の閾値。
+        /// `None` の場合は境界との衝突イベントを記録しません。
+        pub boundary_event_threshold: Option<Scalar>,
+        /// 拘束を解決する順序
+        pub solve_order: SolveOrder,
+        /// 全てのバネ・曲げ拘束・形状維持拘束の剛性に、解決時に掛ける倍率（デフォルト `1.0`）。
+        /// `0` に近づけると全体が柔らかく、`1` より大きくすると硬くなります。各拘束に保存された
+        /// 剛性は変更しないため、値を戻せば元の硬さに戻ります。掛けた結果は、不安定にならないよう
+        /// `1.0`（元の剛性が `1.0` より大きい場合はその値）で打ち切られます。
+        pub stiffness_multiplier: Scalar,
+        /// `true` の場合、`solver_iterations` の代わりに毎ステップ `Simulation::recommended_iterations` の
+        /// 反復回数を使います（デフォルト `false`）。
+        pub auto_iterations: bool,
+        /// 質点同士の衝突の反発係数。`Some(e)` の場合、各ステップの速度更新の後に、接触した質点の組の
+        /// 法線方向の相対速度を、接触前の速度の `-e` 倍にする力積を与えます（`1.0` で完全弾性、
+        /// `0.0` で完全非弾性）。`None`（デフォルト）の場合は位置の補正のみで衝突を解決します。
+        pub restitution: Option<Scalar>,
+        /// 連続的衝突判定を有効にするオプション（デフォルト `false`）。1ステップで半径より長く動く質点について、
+        /// `prev_pos` から `pos` までの経路を他のボディの外周ワイヤーや質点と照合し、最初の接触位置で止めます。
+        /// 薄い壁を高速な質点がすり抜けるのを防げますが、計算量が増えます。
+        pub continuous_collision: bool,
+        /// ソルバーの1回の反復で衝突解決（質点同士と、有効な場合はワイヤー衝突）を繰り返す回数（デフォルト `1`）。
+        /// 積み重なったボディの深いめり込みを解消したい場合に、バネの反復回数（硬さに影響します）を
+        /// 増やさずに衝突だけを多く解決できます。`0` は `1` として扱います。
+        pub collision_iterations: usize,
+        /// シミュレーション開始から `gravity_ramp_steps` ステップかけて、重力を `0` から線形に強めます
+        /// （デフォルト `0` で無効）。`n` ステップ目（`0` から数えます）の重力は `n / gravity_ramp_steps` 倍で、
+        /// `gravity_ramp_steps` ステップ目以降は元の強さです。ボディが接触して落ち着いてから重さが掛かるため、
+        /// 読み込み直後の落下を和らげられます。`warm_start` のステップも数えます。
+        pub gravity_ramp_steps: usize,
+        /// 空気抵抗の係数。`Some(c)` の場合、各質点に速度の2乗に比例する抵抗 `-c * (2 * radius) * |v| * v` を与えます
+        /// （2次元での断面の大きさとして直径を使います）。同じ質量なら大きい質点ほど強く減速し、
+        /// 終端速度 `sqrt(g / (c * 2 * radius * inv_mass))` が小さくなります。`None`（デフォルト）の場合は抵抗なしです。
+        pub air_drag: Option<Scalar>,
+        /// 質点同士および質点と境界（`bounds`）の衝突で、補正せずに許す重なりの深さ（デフォルト `0.0`）。
+        /// 接触し続ける質点が毎ステップ押し戻されて細かく震えるのを抑えられます。負の値は `0.0` として扱います。
+        /// 許した重なりの内側の質点同士の接触も、衝突イベント・反発・`contacts` では接触として扱います。
+        pub penetration_slop: Scalar,
+        /// 1回の反復で補正する重なりの割合（デフォルト `1.0`）。`1.0` より小さくすると、深い重なりを
+        /// 複数の反復・ステップに分けて解消するため、ボディが勢いよく弾け飛ぶのを抑えられます。
+        /// 補正量は `(重なり - penetration_slop).max(0.0) * penetration_bias` です。値は `[0, 1]` に収めて使い、
+        /// NaN はデフォルトの `1.0` として扱います。
+        pub penetration_bias: Scalar,
+        /// 質点同士の衝突（ワイヤー衝突と連続的衝突判定を含む）を解決するかどうか（デフォルト `true`）。
+        /// `false` の場合は衝突の判定自体を行わないため、衝突を独自に処理する場合や、拘束の解決だけを
+        /// 計測する場合に O(n²) の計算を省けます。
+        pub enable_collisions: bool,
+        /// 境界（`bounds`）と閉じ込め領域（`containment`）を適用するかどうか（デフォルト `true`）。
+        pub enable_boundaries: bool,
+        /// 形状維持拘束の極分解を省く閾値（デフォルト `0.0` で常に分解します）。正の場合、各拘束の `A_pq` が
+        /// 直前に分解したときから相対的にこの割合以内しか変わっていなければ、前回の回転を使い回します。
+        /// 静止に近いシーンでは反復ごとの分解の大半を省けますが、回転の誤差は最大でこの割合程度になります。
+        pub shape_match_tolerance: Scalar,
+        /// 外周同士の衝突を有効にするオプション（デフォルト `false`）。異なるボディの外周ワイヤーを
+        /// 厚みの無い多角形の面として扱い、相手の外周の内側に入った頂点を最も近い辺の上まで押し戻します。
+        /// 異なるボディの外周の頂点どうしでは質点の半径による衝突を行わないため、平らな面どうしが隙間なく接します。
+        /// 内部の質点は通常どおり半径で衝突します。
+        pub outline_collisions: bool,
+        /// `true` の場合、バネ（ボディのバネ、`connect` のバネ、`FixedSpring`）の長さを `Vec2::length` の代わりに
+        /// `Vec2::length_fast` で測ります（デフォルト `false`）。通常の大きさの座標では結果はほぼ変わらず、
+        /// `deterministic` feature を使わなくてもバネの解決がプラットフォームに依存しなくなります。
+        pub fast_length: bool,
+    }
+
+    impl SimulationConfig {
+        /// 拘束の剛性 `stiffness` に `stiffness_multiplier` を掛けた、解決時に使う剛性を返します。
+        fn effective_stiffness(&self, stiffness: Scalar) -> Scalar {
+            (stiffness * self.stiffness_multiplier).clamp(0.0, stiffness.max(1.0))
+        }
+
+        /// バネの長さを測る関数を `fast_length` に従って返します。
+        fn length_fn(&self) -> fn(&Vec2) -> Scalar {
+            if self.fast_length { Vec2::length_fast } else { Vec2::length }
+        }
+
+        /// 重なりの深さ `penetration` のうち、1回の反復で補正する量を返します。
+        fn penetration_correction(&self, penetration: Scalar) -> Scalar {
+            let bias = if self.penetration_bias.is_nan() { 1.0 } else { self.penetration_bias.clamp(0.0, 1.0) };
+            (penetration - self.penetration_slop.max(0.0)).max(0.0) * bias
+        }
+
+        /// 位置 `pos` にある質点に働く重力加速度を返します。
+        pub fn gravity_at(&self, pos: Vec2) -> Vec2 {
+            match &self.gravity_field {
+                Some(field) => field.acceleration_at(pos),
+                None => self.gravity,
+            }
+        }
+    }
+
+    impl Default for SimulationConfig {
+        fn default() -> Self {
+            Self {
+                gravity: Vec2::new(0.0, 270.0),
+                damping: 0.99,
+                solver_iterations: 8,
+                bounds: None,
+                containment: None,
+                use_volumetric_collisions: false,
+                use_wire_collisions: false, // デフォルトでは無効
+                integrator: Integrator::Verlet,
+                gravity_field: None,
+                seed: 0,
+                collision_event_threshold: None,
+                boundary_event_threshold: None,
+                solve_order: SolveOrder::Sequential,
+                stiffness_multiplier: 1.0,
+                auto_iterations: false,
+                restitution: None,
+                continuous_collision: false,
+                collision_iterations: 1,
+                gravity_ramp_steps: 0,
+                air_drag: None,
+                penetration_slop: 0.0,
+                penetration_bias: 1.0,
+                enable_collisions: true,
+                enable_boundaries: true,
+                shape_match_tolerance: 0.0,
+                outline_collisions: false,
+                fast_length: false,
+            }
+        }
+    }
+
+    impl Simulation {
+        /// 新しいシミュレーション環境を作成します。
+        pub fn new(config: SimulationConfig) -> Self {
+            let rng = Rng::new(config.seed);
+            Self {
+                particles: Vec::new(),
+                soft_bodies: Vec::new(),
+                config,
+                next_body_id: 0,
+                connections: Vec::new(),
+                fixed_springs: Vec::new(),
+                drag: None,
+                next_spring_id: 0,
+                rng,
+                collision_events: Vec::new(),
+                boundary_events: Vec::new(),
+                body_contacts: Vec::new(),
+                spawners: Vec::new(),
+                next_spawner_id: 0,
+                heat_sources: Vec::new(),
+                next_heat_source_id: 0,
+                particle_owners: Vec::new(),
+                step_count: 0,
+                history: VecDeque::new(),
+                history_capacity: 0,
+            }
+        }
+
+        /// 質点とソフトボディの格納領域を予約した上でシミュレーション環境を作成します。
+        /// 大量の質点を生成することが分かっている場合の再確保を避けられます。
+        pub fn with_capacity(config: SimulationConfig, particles: usize, bodies: usize) -> Self {
+            let mut sim = Self::new(config);
+            sim.particles.reserve(particles);
+            sim.soft_bodies.reserve(bodies);
+            sim
+        }
+
+        /// 設定に従って質点を1つ生成し、そのグローバルインデックスを返します。
+        /// `config.jitter` が正の場合は、位置をその距離以内でランダムにずらします。
+        fn spawn_particle(&mut self, pos: Vec2, config: &SoftBodyConfig) -> usize {
+            let pos = if config.jitter > 0.0 { pos + self.rng.in_disc(config.jitter) } else { pos };
+            let mut p = Particle::new(pos.x, pos.y);
+            p.radius = config.particle_radius;
+            if config.is_fixed {
+                p.is_fixed = true;
+                p.inv_mass = 0.0;
+            } else {
+                p.inv_mass = config.particle_inv_mass;
+            }
+            self.particles.push(p);
+            self.particles.len() - 1
+        }
+
+        /// ボディに新しいハンドルを割り当てて登録します。
+        fn push_body(&mut self, mut body: SoftBody) -> SoftBodyHandle {
+            let handle = SoftBodyHandle(self.next_body_id);
+            self.next_body_id += 1;
+            body.handle = handle;
+            self.soft_bodies.push(body);
+            self.assign_particle_owners(self.soft_bodies.len() - 1);
+            handle
+        }
+
+        /// `soft_bodies[body_idx]` の全ての質点の逆引き先を、そのボディに設定します。
+        fn assign_particle_owners(&mut self, body_idx: usize) {
+            let body = &self.soft_bodies[body_idx];
+            if self.particle_owners.len() < self.particles.len() {
+                self.particle_owners.resize(self.particles.len(), None);
+            }
+            for &i in &body.particle_indices {
+                self.particle_owners[i] = Some(body.handle);
+            }
+        }
+
+        /// 質点 `index` を含むボディのハンドルを返します。
+        /// どのボディにも含まれない質点や、範囲外のインデックスの場合は `None` を返します。
+        pub fn body_of_particle(&self, index: usize) -> Option<SoftBodyHandle> {
+            let handle = (*self.particle_owners.get(index)?)?;
+            // 切断や結合で取り除かれたボディを指したままの質点を除く
+            self.body_index(handle).map(|_| handle)
         }
 
         /// シミュレーションにソフトボディを追加します。
         /// 質点と拘束を生成し、シミュレーションの状態に統合します。
-        pub fn add_soft_body(&mut self, config: &SoftBodyConfig) {
+        ///
+        /// グリッドの質点間隔が質点の直径 (`2 * particle_radius`) より小さい場合、
+        /// 生成直後の衝突解決でボディが弾け飛ぶため `ShapeError::OverlappingParticles` を返します。
+        /// 質点間隔が `geometry::COINCIDENT_EPSILON` 以下で質点が重なる場合は `ShapeError::CoincidentPoints` を返します。
+        pub fn add_soft_body(&mut self, config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            let spacing_x = if config.cols > 1 { config.size.x / (config.cols - 1) as Scalar } else { 0.0 };
+            let spacing_y = if config.rows > 1 { config.size.y / (config.rows - 1) as Scalar } else { 0.0 };
+            let coincident = |spacing: Scalar| spacing.abs() <= geometry::COINCIDENT_EPSILON;
+            if (config.cols > 1 && coincident(spacing_x)) || (config.rows > 1 && coincident(spacing_y)) {
+                return Err(ShapeError::CoincidentPoints);
+            }
+            let diameter = 2.0 * config.particle_radius;
+            if (config.cols > 1 && diameter > spacing_x) || (config.rows > 1 && diameter > spacing_y) {
+                return Err(ShapeError::OverlappingParticles);
+            }
+
             let _start_index = self.particles.len();
             let mut particle_indices = Vec::new();
-
-            let spacing_x = if config.cols > 1 { config.size.x / (config.cols - 1) as f64 } else { 0.0 };
-            let spacing_y = if config.rows > 1 { config.size.y / (config.rows - 1) as f64 } else { 0.0 };
             let top_left = config.center - Vec2::new(config.size.x * 0.5, config.size.y * 0.5);
 
             for i in 0..config.rows {
                 for j in 0..config.cols {
-                    let x = top_left.x + j as f64 * spacing_x;
-                    let y = top_left.y + i as f64 * spacing_y;
-                    let mut p = Particle::new(x, y);
-                    p.radius = config.particle_radius;
-
-                    if config.is_fixed {
-                        p.is_fixed = true;
-                        p.inv_mass = 0.0;
-                    } else {
-                         p.inv_mass = config.particle_inv_mass;
-                    }
-                    
-                    particle_indices.push(self.particles.len());
-                    self.particles.push(p);
+                    let x = top_left.x + j as Scalar * spacing_x;
+                    let y = top_left.y + i as Scalar * spacing_y;
+                    particle_indices.push(self.spawn_particle(Vec2::new(x, y), config));
                 }
             }
             
@@ -518,138 +2399,1609 @@ pub mod core {
                 for i in 0..config.rows {
                     for j in 0..config.cols {
                         let p_idx = _start_index + i * config.cols + j;
+                        let on_border_row = i == 0 || i == config.rows - 1;
+                        let on_border_col = j == 0 || j == config.cols - 1;
                         // 右の質点とのバネ
-                        if j < config.cols - 1 {
+                        if j < config.cols - 1 && (config.interior_springs || on_border_row) {
                             let p2_idx = _start_index + i * config.cols + (j + 1);
                             springs.push(Spring::new(p_idx, p2_idx, config.stiffness, &self.particles));
                         }
                         // 下の質点とのバネ
-                        if i < config.rows - 1 {
+                        if i < config.rows - 1 && (config.interior_springs || on_border_col) {
                             let p2_idx = _start_index + (i + 1) * config.cols + j;
                             springs.push(Spring::new(p_idx, p2_idx, config.stiffness, &self.particles));
                         }
                     }
                 }
             }
-
-            let shape_constraint = if config.shape_stiffness > 0.0 {
-                Some(ShapeMatchingConstraint::new(particle_indices.clone(), config.shape_stiffness, &self.particles))
-            } else {
-                None
-            };
-            
-            self.soft_bodies.push(SoftBody {
-                particle_indices,
-                springs,
-                shape_constraint,
-                outline_wires: None,
-            });
+
+            let shape_constraint = if config.shape_stiffness > 0.0 {
+                Some(ShapeMatchingConstraint::new(particle_indices.clone(), config.shape_stiffness, &self.particles))
+            } else {
+                None
+            };
+            
+            let outline_wires = if config.outline && config.rows > 1 && config.cols > 1 {
+                // 上辺を左から右、右辺を上から下、下辺を右から左、左辺を下から上へと一周する
+                let (rows, cols) = (config.rows, config.cols);
+                let border: Vec<usize> = (0..cols).map(|j| (0, j))
+                    .chain((1..rows).map(|i| (i, cols - 1)))
+                    .chain((0..cols - 1).rev().map(|j| (rows - 1, j)))
+                    .chain((1..rows - 1).rev().map(|i| (i, 0)))
+                    .map(|(i, j)| particle_indices[i * cols + j])
+                    .collect();
+                Some(geometry::circular_windows(&border).collect())
+            } else {
+                None
+            };
+
+            let mut body = SoftBody::new(particle_indices, springs, shape_constraint, outline_wires);
+            body.tag = config.tag;
+            body.is_sensor = config.is_sensor;
+            body.grid = Some((config.rows, config.cols));
+            Ok(self.push_body(body))
+        }
+
+        /// `add_soft_body` と同様にソフトボディを追加し、ハンドルと生成した質点のインデックスの範囲を返します。
+        ///
+        /// 質点は行ごとに上から順に並ぶため、範囲の先頭 `config.cols` 個が最上段の質点です。
+        pub fn add_soft_body_at(&mut self, config: &SoftBodyConfig) -> Result<(SoftBodyHandle, Range<usize>), ShapeError> {
+            let start = self.particles.len();
+            let handle = self.add_soft_body(config)?;
+            Ok((handle, start..self.particles.len()))
+        }
+
+        /// `add_soft_body` と同様にソフトボディを追加し、既存の質点と重なっている場合は、重ならなくなるまで
+        /// 新しいボディ全体を平行移動させます。
+        ///
+        /// 重なったまま最初のステップを迎えると、衝突解決で両方のボディが勢いよく弾き飛ばされます。
+        /// 移動の向きは、重なっている既存の質点の重心から新しいボディの重心への向き（一致する場合は上向き）です。
+        /// `pos` と `prev_pos` を同じだけ動かすため、新しいボディにも既存のボディにも速度は生じません。
+        pub fn add_soft_body_resolved(&mut self, config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            // 押し出しで別の質点と新たに重なった場合に、繰り返し押し出す回数の上限
+            const MAX_PASSES: usize = 16;
+            let (handle, range) = self.add_soft_body_at(config)?;
+            let overlaps = |sim: &Self| -> Vec<(usize, usize)> {
+                range.clone().flat_map(|i| (0..range.start).map(move |j| (i, j)))
+                    .filter(|&(i, j)| {
+                        let (a, b) = (&sim.particles[i], &sim.particles[j]);
+                        (a.pos - b.pos).length() < a.radius + b.radius
+                    })
+                    .collect()
+            };
+            let initial = overlaps(self);
+            if initial.is_empty() {
+                return Ok(handle);
+            }
+            let mean = |indices: &[usize]| {
+                let sum = indices.iter().fold(Vec2::new(0.0, 0.0), |sum, &i| sum + self.particles[i].pos);
+                sum * (1.0 / indices.len() as Scalar)
+            };
+            let mut others: Vec<usize> = initial.iter().map(|&(_, j)| j).collect();
+            others.sort_unstable();
+            others.dedup();
+            let spawned: Vec<usize> = range.clone().collect();
+            let dir = (mean(&spawned) - mean(&others)).normalize();
+            let dir = if dir == Vec2::new(0.0, 0.0) { Vec2::new(0.0, -1.0) } else { dir };
+
+            let mut pairs = initial;
+            for _ in 0..MAX_PASSES {
+                if pairs.is_empty() { break; }
+                // 各組について、dir 方向に動かして距離が半径の和になる移動量（2次方程式の大きい方の解）
+                let shift = pairs.iter().map(|&(i, j)| {
+                    let offset = self.particles[i].pos - self.particles[j].pos;
+                    let min_dist = self.particles[i].radius + self.particles[j].radius;
+                    let b = Vec2::dot(offset, dir);
+                    let c = offset.length_squared() - min_dist * min_dist;
+                    -b + (b * b - c).max(0.0).sqrt()
+                }).fold(0.0, Scalar::max);
+                for i in range.clone() {
+                    let p = &mut self.particles[i];
+                    p.pos += dir * shift;
+                    p.prev_pos += dir * shift;
+                }
+                pairs = overlaps(self);
+            }
+            Ok(handle)
+        }
+
+        /// 凸形状のソフトボディを追加する新しいファクトリ関数
+        ///
+        /// `config.shell_layers` が2以上の場合は、外周の内側に重心へ向かって縮めたリングを重ね、
+        /// 厚みのある殻にします（`add_shell_layers` を参照）。
+        ///
+        /// 距離が `geometry::COINCIDENT_EPSILON` 以下の点の組を含む場合は、点を統合せずに
+        /// `ShapeError::CoincidentPoints` を返します。
+        pub fn add_convex_body(&mut self, particle_positions: &[Vec2], config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            if particle_positions.len() < 3 { return Err(ShapeError::NotEnoughParticles); }
+            if geometry::has_coincident_points(particle_positions) { return Err(ShapeError::CoincidentPoints); }
+            if geometry::check_self_intersection(particle_positions) { return Err(ShapeError::SelfIntersecting); }
+            let mut particle_indices = Vec::new();
+            for &pos in particle_positions {
+                particle_indices.push(self.spawn_particle(pos, config));
+            }
+            let handle = self.push_ring_body(particle_indices, config.stiffness, config.shape_stiffness, config.bend_stiffness, config.tag);
+            self.set_sensor(handle, config.is_sensor);
+            if let Some(pressure) = config.target_pressure {
+                self.add_pressure(handle, pressure, None);
+            }
+            if config.shell_layers > 1 {
+                self.add_shell_layers(handle, particle_positions, config);
+            }
+            Ok(handle)
+        }
+
+        /// 外周リングの内側に `config.shell_layers - 1` 層のリングを追加します。
+        ///
+        /// `k` 層目の各頂点は、外周の頂点を重心に向かって `k * 3 * particle_radius` だけ寄せた位置です
+        /// （重心を越えないよう制限します）。各リングの辺と、隣り合うリング間の対応する頂点および
+        /// 対角の頂点をバネで結びます。外周ワイヤーと曲げ拘束は外周のみで、形状維持拘束は全質点に
+        /// 対して作り直します。
+        fn add_shell_layers(&mut self, handle: SoftBodyHandle, outline: &[Vec2], config: &SoftBodyConfig) {
+            let Some(body_idx) = self.body_index(handle) else { return };
+            let centroid = outline.iter().fold(Vec2::new(0.0, 0.0), |acc, &p| acc + p) * (1.0 / outline.len() as Scalar);
+            let inset = 3.0 * config.particle_radius;
+            let n = outline.len();
+
+            let mut rings = vec![self.soft_bodies[body_idx].particle_indices.clone()];
+            let mut springs = Vec::new();
+            for k in 1..config.shell_layers {
+                let ring: Vec<usize> = outline
+                    .iter()
+                    .map(|&p| {
+                        let offset = p - centroid;
+                        let dist = offset.length();
+                        let inset_dist = (dist - inset * k as Scalar).max(config.particle_radius.min(dist));
+                        self.spawn_particle(centroid + offset.normalize() * inset_dist, config)
+                    })
+                    .collect();
+                let outer = &rings[k - 1];
+                for (i, (a, b)) in geometry::circular_windows(&ring).enumerate() {
+                    let (oa, ob) = (outer[i], outer[(i + 1) % n]);
+                    springs.push(Spring::new(a, b, config.stiffness, &self.particles));
+                    springs.push(Spring::new(oa, a, config.stiffness, &self.particles));
+                    springs.push(Spring::new(oa, b, config.stiffness, &self.particles));
+                    springs.push(Spring::new(ob, a, config.stiffness, &self.particles));
+                }
+                rings.push(ring);
+            }
+
+            let body = &mut self.soft_bodies[body_idx];
+            body.springs.extend(springs);
+            body.particle_indices = rings.concat();
+            if config.shape_stiffness > 0.0 {
+                body.shape_constraint = Some(ShapeMatchingConstraint::new(body.particle_indices.clone(), config.shape_stiffness, &self.particles));
+            }
+            self.assign_particle_owners(body_idx);
+        }
+
+        /// ボディをセンサーにするかどうかを設定します。
+        fn set_sensor(&mut self, handle: SoftBodyHandle, is_sensor: bool) {
+            if let Some(body_idx) = self.body_index(handle) {
+                self.soft_bodies[body_idx].is_sensor = is_sensor;
+            }
+        }
+
+        /// 質点ごとに、その質点を含むボディがセンサーかどうかを返します。センサーが無い場合は空の `Vec` です。
+        fn sensor_particles(&self) -> Vec<bool> {
+            if !self.soft_bodies.iter().any(|sb| sb.is_sensor) {
+                return Vec::new();
+            }
+            let mut sensors = vec![false; self.particles.len()];
+            for sb in self.soft_bodies.iter().filter(|sb| sb.is_sensor) {
+                for &i in &sb.particle_indices {
+                    sensors[i] = true;
+                }
+            }
+            sensors
+        }
+
+        /// センサーのボディ `handle` の外周の内側に質点が1つ以上あるボディを、ボディの順に返します。
+        ///
+        /// センサー自身は含みません。ボディが存在しない場合、センサーでない場合、外周を持たない場合は空の `Vec` を返します。
+        pub fn sensor_overlaps(&self, handle: SoftBodyHandle) -> Vec<SoftBodyHandle> {
+            let Some(sensor) = self.soft_body(handle).filter(|sb| sb.is_sensor) else { return Vec::new() };
+            self.soft_bodies.iter()
+                .filter(|sb| sb.handle != handle)
+                .filter(|sb| sb.particle_indices.iter().any(|&i| sensor.contains_point(&self.particles, self.particles[i].pos)))
+                .map(|sb| sb.handle)
+                .collect()
+        }
+
+        /// 閉じたリング状に並んだ既存の質点から、外周ワイヤー付きのボディを構築して登録します。
+        /// 外周の各辺にバネを張り、剛性が正の場合は形状維持拘束と曲げ拘束も生成します。
+        fn push_ring_body(&mut self, particle_indices: Vec<usize>, stiffness: Scalar, shape_stiffness: Scalar, bend_stiffness: Scalar, tag: Option<u64>) -> SoftBodyHandle {
+            let outline_wires: Vec<(usize, usize)> = geometry::circular_windows(&particle_indices).collect();
+            let mut springs = Vec::new();
+            for &(p1_idx, p2_idx) in &outline_wires {
+                springs.push(Spring::new(p1_idx, p2_idx, stiffness, &self.particles));
+            }
+            let shape_constraint = if shape_stiffness > 0.0 { Some(ShapeMatchingConstraint::new(particle_indices.clone(), shape_stiffness, &self.particles)) } else { None };
+            let mut body = SoftBody::new(particle_indices, springs, shape_constraint, Some(outline_wires));
+            body.tag = tag;
+            if bend_stiffness > 0.0 {
+                for w in geometry::circular_windows_n(&body.particle_indices, 3) {
+                    body.bend_constraints.push(BendConstraint::new(w[0], w[1], w[2], bend_stiffness, &self.particles));
+                }
+            }
+            self.push_body(body)
+        }
+
+        /// 外周を持つボディを線分 `line` に沿って2つのボディに切断します。
+        ///
+        /// `line` が外周のちょうど2辺の内部と交差する場合、各交点に両側のボディ用の質点を
+        /// 新たに生成し、元のボディを2つの閉じたボディに置き換えます。新しいボディのバネと
+        /// 形状維持拘束は現在の位置を静止状態として再構築され、バネと曲げ拘束の剛性には元のボディの
+        /// それぞれの平均を使います。元の質点はそのまま再利用され、タグは両方のボディに引き継がれます。
+        /// 圧力を持つボディでは、元の目標の面積を切断時の2つのボディの面積の比で分けたものが、
+        /// それぞれの目標の面積になります。
+        ///
+        /// 切断できるのは、`add_convex_body` などで作った外周の質点だけからなるボディです。
+        /// ボディが存在しない、外周を持たない、外周以外の質点・バネや角度拘束を持つ（外周付きのグリッドや
+        /// `shell_layers` のボディなど）、または交差する辺が2つでない場合は `None` を返し、
+        /// シミュレーションは変更されません。
+        pub fn slice_body(&mut self, handle: SoftBodyHandle, line: Line) -> Option<(SoftBodyHandle, SoftBodyHandle)> {
+            let body_idx = self.body_index(handle)?;
+            let body = &self.soft_bodies[body_idx];
+            let wires = body.outline_wires.as_ref()?;
+            // 外周から再構築できないもの（内部の質点・バネ、角度拘束）を持つボディは切断しない
+            let ring_only = body.particle_indices.len() == wires.len()
+                && wires.iter().all(|&(a, _)| body.particle_indices.contains(&a))
+                && body.springs.iter().all(|s| wires.iter().any(|&edge| Self::spans(s, edge)))
+                && body.angle_constraints.is_empty();
+            if !ring_only {
+                return None;
+            }
+
+            // (辺の番号, 辺上のパラメータ)
+            let mut cuts = Vec::new();
+            for (k, &(a, b)) in wires.iter().enumerate() {
+                let edge = Line::new(self.particles[a].pos, self.particles[b].pos);
+                if let Some((t, _)) = geometry::segment_intersection_params(&edge, &line) {
+                    // 頂点を通る場合は辺の特定が曖昧になるため、辺の内部での交差のみ扱う
+                    if t > 0.0 && t < 1.0 {
+                        cuts.push((k, t));
+                    }
+                }
+            }
+            if cuts.len() != 2 {
+                return None;
+            }
+
+            let mean = |values: &mut dyn Iterator<Item = Scalar>| {
+                let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+                if count == 0 { 0.0 } else { sum / count as Scalar }
+            };
+            let stiffness = mean(&mut body.springs.iter().map(|s| s.stiffness));
+            let shape_stiffness = body.shape_constraint.as_ref().map_or(0.0, |sc| sc.stiffness);
+            let bend_stiffness = mean(&mut body.bend_constraints.iter().map(|b| b.stiffness));
+            let tag = body.tag;
+            let is_sensor = body.is_sensor;
+            let pressure = body.pressure_constraints.first().map(|pc| (pc.pressure, pc.target_area));
+            let wires = wires.clone();
+            let ring: Vec<usize> = wires.iter().map(|&(a, _)| a).collect();
+            let n = ring.len();
+            let ((e1, t1), (e2, t2)) = (cuts[0], cuts[1]);
+            self.soft_bodies.remove(body_idx);
+
+            // 切断点 x1 (辺 e1 上) から x2 (辺 e2 上) までの片側と、その反対側
+            let mut ring_a = vec![self.spawn_particle_on_edge(wires[e1], t1)];
+            ring_a.extend((e1 + 1..=e2).map(|i| ring[i % n]));
+            ring_a.push(self.spawn_particle_on_edge(wires[e2], t2));
+
+            let mut ring_b = vec![self.spawn_particle_on_edge(wires[e2], t2)];
+            ring_b.extend((e2 + 1..=e1 + n).map(|i| ring[i % n]));
+            ring_b.push(self.spawn_particle_on_edge(wires[e1], t1));
+
+            let ring_area = |ring: &[usize]| geometry::polygon_area(&ring.iter().map(|&i| self.particles[i].pos).collect::<Vec<_>>());
+            let (area_a, area_b) = (ring_area(&ring_a), ring_area(&ring_b));
+            let a = self.push_ring_body(ring_a, stiffness, shape_stiffness, bend_stiffness, tag);
+            let b = self.push_ring_body(ring_b, stiffness, shape_stiffness, bend_stiffness, tag);
+            self.set_sensor(a, is_sensor);
+            self.set_sensor(b, is_sensor);
+            if let Some((pressure, target_area)) = pressure {
+                // 元の目標の面積を、切断時の各ボディの面積の比で分け合う
+                let total = area_a + area_b;
+                let share = |area: Scalar| (total > Scalar::EPSILON).then(|| target_area * area / total);
+                self.add_pressure(a, pressure, share(area_a));
+                self.add_pressure(b, pressure, share(area_b));
+            }
+            Some((a, b))
+        }
+
+        /// 線分 `line` と交差する全てのバネを取り除き、取り除いた数を返します。
+        ///
+        /// バネの線分は両端の質点の現在位置で判定します。質点はそのまま残るため、
+        /// ボディは切られた箇所で緩むだけで、ボディの構成は変わりません。
+        /// `connect` で追加したバネも対象になります。
+        pub fn cut_springs(&mut self, line: Line) -> usize {
+            let particles = &self.particles;
+            let is_cut = |spring: &Spring| {
+                let segment = Line::new(particles[spring.p1_index].pos, particles[spring.p2_index].pos);
+                geometry::segment_intersection(&segment, &line).is_some()
+            };
+            let mut removed = 0;
+            for sb in &mut self.soft_bodies {
+                let before = sb.springs.len();
+                sb.springs.retain(|spring| !is_cut(spring));
+                removed += before - sb.springs.len();
+            }
+            let before = self.connections.len();
+            self.connections.retain(|(_, spring)| !is_cut(spring));
+            removed += before - self.connections.len();
+            removed
+        }
+
+        /// 2つのボディを1つのボディに融合します。
+        ///
+        /// 両方の質点・バネ・曲げ拘束を1つの `SoftBody` にまとめ、距離が `weld_distance` 以下の
+        /// 質点の組（各ボディから1つずつ）の間に接合バネを追加します。接合バネの剛性は
+        /// 両ボディのバネ剛性の平均です。形状維持拘束は現在の位置を静止形状として全質点に対して
+        /// 再構築され、剛性は元のボディのもの（両方にある場合は平均）を使います。
+        /// 2つの外周を1つの閉じた外周にまとめることはできないため、融合したボディは外周ワイヤーを持ちません
+        /// （外周同士の衝突・法線・`contains_point` の対象外になります）。圧力は元の外周ごとに働き続けます。
+        /// タグは `a` のもの（なければ `b` のもの）を引き継ぎます。
+        ///
+        /// どちらかのハンドルが存在しない場合、または `a == b` の場合は `None` を返します。
+        pub fn merge_bodies(&mut self, a: SoftBodyHandle, b: SoftBodyHandle, weld_distance: Scalar) -> Option<SoftBodyHandle> {
+            if a == b { return None; }
+            let idx_a = self.body_index(a)?;
+            let idx_b = self.body_index(b)?;
+            // 大きい方から取り除くことで、もう一方の位置がずれないようにする
+            let (first, second) = if idx_a > idx_b { (idx_a, idx_b) } else { (idx_b, idx_a) };
+            let body_1 = self.soft_bodies.remove(first);
+            let body_2 = self.soft_bodies.remove(second);
+            let (body_a, body_b) = if idx_a > idx_b { (body_1, body_2) } else { (body_2, body_1) };
+
+            let mean_stiffness = |sb: &SoftBody| {
+                if sb.springs.is_empty() { None } else { Some(sb.springs.iter().map(|s| s.stiffness).sum::<Scalar>() / sb.springs.len() as Scalar) }
+            };
+            let mean = |x: Option<Scalar>, y: Option<Scalar>| match (x, y) {
+                (Some(x), Some(y)) => Some((x + y) * 0.5),
+                (x, y) => x.or(y),
+            };
+            let weld_stiffness = mean(mean_stiffness(&body_a), mean_stiffness(&body_b)).unwrap_or(0.0);
+            let shape_stiffness = mean(
+                body_a.shape_constraint.as_ref().map(|sc| sc.stiffness),
+                body_b.shape_constraint.as_ref().map(|sc| sc.stiffness),
+            );
+
+            let mut springs = body_a.springs;
+            springs.extend(body_b.springs);
+            let weld_distance_sq = weld_distance * weld_distance;
+            for &i in &body_a.particle_indices {
+                for &j in &body_b.particle_indices {
+                    if (self.particles[i].pos - self.particles[j].pos).length_squared() <= weld_distance_sq {
+                        springs.push(Spring::new(i, j, weld_stiffness, &self.particles));
+                    }
+                }
+            }
+
+            let mut particle_indices = body_a.particle_indices;
+            particle_indices.extend(body_b.particle_indices);
+            let shape_constraint = shape_stiffness.map(|k| ShapeMatchingConstraint::new(particle_indices.clone(), k, &self.particles));
+            let mut body = SoftBody::new(particle_indices, springs, shape_constraint, None);
+            body.bend_constraints = body_a.bend_constraints;
+            body.bend_constraints.extend(body_b.bend_constraints);
+            body.angle_constraints = body_a.angle_constraints;
+            body.angle_constraints.extend(body_b.angle_constraints);
+            body.pressure_constraints = body_a.pressure_constraints;
+            body.pressure_constraints.extend(body_b.pressure_constraints);
+            body.tag = body_a.tag.or(body_b.tag);
+            // センサーでないボディと合わせた場合は、衝突の応答を失わないよう通常のボディにする
+            body.is_sensor = body_a.is_sensor && body_b.is_sensor;
+            Some(self.push_body(body))
+        }
+
+        /// 辺 `(a, b)` 上のパラメータ `t` の位置に、両端の質点の状態を補間した質点を生成します。
+        fn spawn_particle_on_edge(&mut self, (a, b): (usize, usize), t: Scalar) -> usize {
+            let (pa, pb) = (&self.particles[a], &self.particles[b]);
+            let lerp = |u: Vec2, v: Vec2| u + (v - u) * t;
+            let mut p = Particle::new(0.0, 0.0);
+            p.pos = lerp(pa.pos, pb.pos);
+            p.prev_pos = lerp(pa.prev_pos, pb.prev_pos);
+            p.vel = lerp(pa.vel, pb.vel);
+            p.radius = pa.radius + (pb.radius - pa.radius) * t;
+            p.inv_mass = pa.inv_mass + (pb.inv_mass - pa.inv_mass) * t;
+            p.is_fixed = pa.is_fixed && pb.is_fixed;
+            self.particles.push(p);
+            self.particles.len() - 1
+        }
+
+        /// ボディとその質点をシミュレーションから取り除きます。
+        ///
+        /// 残りの質点のインデックスは詰め直され、他のボディの拘束もそれに合わせて更新されます。
+        /// 他のボディのハンドルは変わりません。ボディが存在しない場合は `false` を返します。
+        pub fn remove_soft_body(&mut self, handle: SoftBodyHandle) -> bool {
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            let body = self.soft_bodies.remove(body_idx);
+            let mut keep = vec![true; self.particles.len()];
+            for &i in &body.particle_indices {
+                keep[i] = false;
+            }
+            self.retain_particles(&keep);
+            true
+        }
+
+        /// 質点を1つ取り除き、それより後ろの質点のインデックスを全て1つずつ詰めます。
+        ///
+        /// 質点につながるバネ（`connect` で追加したものを含む）と角度拘束は削除され、形状維持拘束からは
+        /// 外されます（残りの質点の初期形状は保たれます）。質点が無くなった形状維持拘束は削除されます。
+        /// 外周ワイヤーや曲げ拘束に含まれる質点は取り除けず、`ParticleError::OnOutline` を返します。
+        pub fn remove_particle(&mut self, index: usize) -> Result<(), ParticleError> {
+            if index >= self.particles.len() {
+                return Err(ParticleError::IndexOutOfBounds);
+            }
+            let on_outline = self.soft_bodies.iter().any(|sb| {
+                sb.outline_wires.iter().flatten().any(|&(a, b)| a == index || b == index)
+                    || sb.bend_constraints.iter().any(|c| [c.prev_index, c.cur_index, c.next_index].contains(&index))
+            });
+            if on_outline {
+                return Err(ParticleError::OnOutline);
+            }
+
+            for sb in &mut self.soft_bodies {
+                let len = sb.particle_indices.len();
+                sb.particle_indices.retain(|&i| i != index);
+                if sb.particle_indices.len() != len {
+                    // 格子に穴が開くため、行と列で質点を指定できなくなる
+                    sb.grid = None;
+                }
+                sb.springs.retain(|s| s.p1_index != index && s.p2_index != index);
+                sb.angle_constraints.retain(|c| ![c.a, c.b, c.c].contains(&index));
+                if let Some(sc) = &mut sb.shape_constraint {
+                    sc.remove_particle(index, &self.particles);
+                    if sc.particle_indices.is_empty() {
+                        sb.shape_constraint = None;
+                    }
+                }
+            }
+            let mut keep = vec![true; self.particles.len()];
+            keep[index] = false;
+            self.retain_particles(&keep);
+            Ok(())
+        }
+
+        /// 質点やボディの取り除きで余った格納領域を解放し、質点の数を返します。
+        ///
+        /// `remove_soft_body` と `remove_particle` はその場で質点を詰めてインデックスを振り直すため、
+        /// 質点の配列に空きが残ることはありません。ここでは取り除きを繰り返した後に残る、
+        /// 質点・ボディ・拘束の `Vec` の余分な容量を切り詰めます。インデックスとハンドルは変わりません。
+        pub fn compact(&mut self) -> usize {
+            self.particles.shrink_to_fit();
+            self.particle_owners.shrink_to_fit();
+            self.soft_bodies.shrink_to_fit();
+            for sb in &mut self.soft_bodies {
+                sb.particle_indices.shrink_to_fit();
+                sb.springs.shrink_to_fit();
+                sb.bend_constraints.shrink_to_fit();
+                sb.angle_constraints.shrink_to_fit();
+                sb.pressure_constraints.shrink_to_fit();
+            }
+            self.connections.shrink_to_fit();
+            self.fixed_springs.shrink_to_fit();
+            self.particles.len()
+        }
+
+        /// `keep[i]` が `false` の質点を取り除き、残りのボディが参照するインデックスを詰め直します。
+        /// 取り除く質点は、残りのボディから参照されていてはいけません。
+        fn retain_particles(&mut self, keep: &[bool]) {
+            let mut remap = vec![usize::MAX; keep.len()];
+            let mut next = 0;
+            for (i, &k) in keep.iter().enumerate() {
+                if k {
+                    remap[i] = next;
+                    next += 1;
+                }
+            }
+            let mut keep_iter = keep.iter();
+            self.particles.retain(|_| *keep_iter.next().unwrap());
+            let mut keep_iter = keep.iter();
+            self.particle_owners.retain(|_| *keep_iter.next().unwrap_or(&true));
+            for sb in &mut self.soft_bodies {
+                sb.remap_indices(&remap);
+            }
+            self.connections.retain(|(_, spring)| keep[spring.p1_index] && keep[spring.p2_index]);
+            for (_, spring) in &mut self.connections {
+                spring.p1_index = remap[spring.p1_index];
+                spring.p2_index = remap[spring.p2_index];
+            }
+            self.fixed_springs.retain(|(_, spring)| keep[spring.particle_index]);
+            for (_, spring) in &mut self.fixed_springs {
+                spring.particle_index = remap[spring.particle_index];
+            }
+            if let Some(drag) = &mut self.drag {
+                if keep[drag.particle_index] {
+                    drag.particle_index = remap[drag.particle_index];
+                } else {
+                    self.drag = None;
+                }
+            }
+        }
+
+        /// 既存の2つの質点 `p1`, `p2` をバネで結び、そのハンドルを返します。
+        ///
+        /// 異なるボディの質点同士も結ぶことができます。自然長は現在の質点間の距離です。
+        /// バネはシミュレーションが保持し、各ステップでボディの拘束の後に解決されます。
+        /// 質点を含むボディが取り除かれると、そのバネも取り除かれます。
+        ///
+        /// # Panics
+        ///
+        /// `p1` または `p2` が質点の範囲外の場合と、`p1 == p2` の場合にパニックします。
+        pub fn connect(&mut self, p1: usize, p2: usize, stiffness: Scalar) -> SpringHandle {
+            assert_ne!(p1, p2, "cannot connect a particle to itself");
+            let handle = SpringHandle(self.next_spring_id);
+            self.next_spring_id += 1;
+            self.connections.push((handle, Spring::new(p1, p2, stiffness, &self.particles)));
+            handle
+        }
+
+        /// `connect` または `add_fixed_spring` で追加したバネを取り除きます。存在しない場合は `false` を返します。
+        pub fn disconnect(&mut self, handle: SpringHandle) -> bool {
+            let before = self.connections.len() + self.fixed_springs.len();
+            self.connections.retain(|(h, _)| *h != handle);
+            self.fixed_springs.retain(|(h, _)| *h != handle);
+            self.connections.len() + self.fixed_springs.len() != before
+        }
+
+        /// 質点と固定点を結ぶバネを追加し、そのハンドルを返します。
+        ///
+        /// バネはシミュレーションが保持し、`connect` のバネと同様に各ステップで解決されます。
+        /// 固定点は質点として追加されないため、質点数や衝突判定には影響しません。
+        /// 質点が取り除かれると、そのバネも取り除かれます。
+        ///
+        /// # Panics
+        ///
+        /// `spring.particle_index` が質点の範囲外の場合にパニックします。
+        pub fn add_fixed_spring(&mut self, spring: FixedSpring) -> SpringHandle {
+            assert!(spring.particle_index < self.particles.len(), "particle index out of bounds");
+            let handle = SpringHandle(self.next_spring_id);
+            self.next_spring_id += 1;
+            self.fixed_springs.push((handle, spring));
+            handle
+        }
+
+        /// `add_fixed_spring` で追加した全てのバネを列挙します。
+        pub fn fixed_springs(&self) -> impl Iterator<Item = &FixedSpring> {
+            self.fixed_springs.iter().map(|(_, spring)| spring)
+        }
+
+        /// ドラッグ拘束を設定します。既に設定されている場合は置き換えます。
+        ///
+        /// 拘束は各反復で他の全ての拘束の後に解決されます。目標位置を動かすには `drag_mut` を使います。
+        /// 質点が取り除かれると、ドラッグ拘束も解除されます。
+        ///
+        /// # Panics
+        ///
+        /// `drag.particle_index` が質点の範囲外の場合にパニックします。
+        pub fn set_drag(&mut self, drag: DragConstraint) {
+            assert!(drag.particle_index < self.particles.len(), "particle index out of bounds");
+            self.drag = Some(drag);
+        }
+
+        /// ドラッグ拘束を解除し、設定されていた拘束を返します。
+        pub fn clear_drag(&mut self) -> Option<DragConstraint> {
+            self.drag.take()
+        }
+
+        /// 設定中のドラッグ拘束を返します。
+        pub fn drag(&self) -> Option<&DragConstraint> {
+            self.drag.as_ref()
+        }
+
+        /// 設定中のドラッグ拘束への可変参照を返します。
+        pub fn drag_mut(&mut self) -> Option<&mut DragConstraint> {
+            self.drag.as_mut()
+        }
+
+        /// 点 `point` で選択されるボディのハンドルを返します（エディタでのクリック選択用）。
+        ///
+        /// `point` を外周（外周が無いボディでは全質点の凸包）の内側に含むボディがあればそれを返します。
+        /// 無い場合は、質点や辺（外周ワイヤー、外周が無いボディではバネ）の太さの内側に `point` があるボディのうち、
+        /// 表面が最も近いものを返します。どのボディにも当たらない場合は `None` を返します。
+        pub fn nearest_body(&self, point: Vec2) -> Option<SoftBodyHandle> {
+            let inside = |sb: &&SoftBody| match sb.outline_wires {
+                Some(_) => sb.contains_point(&self.particles, point),
+                None => {
+                    let points: Vec<Vec2> = sb.particle_indices.iter().map(|&i| self.particles[i].pos).collect();
+                    geometry::point_in_polygon(point, &geometry::convex_hull(&points))
+                }
+            };
+            if let Some(sb) = self.soft_bodies.iter().find(inside) {
+                return Some(sb.handle);
+            }
+            self.soft_bodies
+                .iter()
+                .map(|sb| (sb.surface_gap(&self.particles, point), sb.handle))
+                .filter(|&(gap, _)| gap <= 0.0)
+                .min_by(|x, y| x.0.total_cmp(&y.0))
+                .map(|(_, handle)| handle)
+        }
+
+        /// 2つのボディが重なっているかを調べ、重なっている場合は貫通深さを返します。
+        ///
+        /// 各ボディの外周（外周が無いボディでは全質点）の凸包に分離軸定理を適用します。
+        /// 凹んだ外周は凸包で近似され、質点の半径は考慮しません。衝突の解決は行わず、
+        /// シミュレーションは変更されません。重なっていない場合、ボディが存在しない場合、
+        /// `a == b` の場合は `None` を返します。
+        pub fn bodies_overlap(&self, a: SoftBodyHandle, b: SoftBodyHandle) -> Option<Scalar> {
+            if a == b { return None; }
+            let hull = |handle| {
+                let sb = self.soft_body(handle)?;
+                let points = match sb.outline_wires {
+                    Some(_) => sb.outline_points(&self.particles),
+                    None => sb.particle_indices.iter().map(|&i| self.particles[i].pos).collect(),
+                };
+                Some(geometry::convex_hull(&points))
+            };
+            geometry::convex_penetration_depth(&hull(a)?, &hull(b)?)
+        }
+
+        /// ボディの形状維持拘束の静止形状を現在の位置から取り直します（`SoftBody::set_rest_shape_to_current`）。
+        /// ボディが存在しない場合は `false` を返します。
+        pub fn reset_body_rest_shape(&mut self, handle: SoftBodyHandle) -> bool {
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            self.soft_bodies[body_idx].set_rest_shape_to_current(&self.particles);
+            true
+        }
+
+        /// ボディの静止状態を一様に `factor` 倍します（風船を膨らませる・しぼませる）。
+        ///
+        /// 全てのバネの静止長と、形状維持拘束の初期形状を `factor` 倍し、圧力の目標の面積を `factor * factor` 倍します。
+        /// 質点は動かさないため、ボディは以降のステップで新しい大きさへ徐々に変形します。`1.0` より大きい値で膨らみ、
+        /// 小さい値でしぼみます。ボディが存在しない場合や、`factor` が正の有限値でない場合は何もせずに `false` を返します。
+        pub fn scale_body_rest(&mut self, handle: SoftBodyHandle, factor: Scalar) -> bool {
+            if !(factor.is_finite() && factor > 0.0) { return false; }
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            let body = &mut self.soft_bodies[body_idx];
+            for spring in &mut body.springs {
+                spring.rest_length *= factor;
+            }
+            if let Some(sc) = &mut body.shape_constraint {
+                sc.scale_rest_shape(factor);
+            }
+            for pressure in &mut body.pressure_constraints {
+                pressure.target_area *= factor * factor;
+            }
+            true
+        }
+
+        /// 外周の各辺に沿ったバネの静止長を、外周ワイヤーの順に返します。
+        /// ボディが存在しない場合や外周を持たない場合は `None` を返し、辺に沿ったバネが無い辺は含みません。
+        pub fn outline_rest_lengths(&self, handle: SoftBodyHandle) -> Option<Vec<Scalar>> {
+            let body = self.soft_body(handle)?;
+            let wires = body.outline_wires.as_ref()?;
+            Some(wires.iter().filter_map(|&edge| body.springs.iter().find(|s| Self::spans(s, edge))).map(|s| s.rest_length).collect())
+        }
+
+        /// 外周の各辺に沿ったバネの静止長を `scale` 倍します（現在の値に掛けるため、繰り返すと累積します）。
+        ///
+        /// `1.0` より小さい値では外周の皮が張り詰めて内側への押し込みに強く抵抗し、大きい値では皮が余って
+        /// しわが寄ります。内部のバネと形状維持拘束は変更しません。ボディが存在しない場合や外周を持たない
+        /// 場合は `false` を返します。
+        pub fn set_outline_rest_scale(&mut self, handle: SoftBodyHandle, scale: Scalar) -> bool {
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            let body = &mut self.soft_bodies[body_idx];
+            let Some(wires) = &body.outline_wires else { return false };
+            for spring in &mut body.springs {
+                if wires.iter().any(|&edge| Self::spans(spring, edge)) {
+                    spring.rest_length *= scale;
+                }
+            }
+            true
+        }
+
+        /// バネ `spring` が辺 `(a, b)` の両端を（向きを問わず）結んでいるかを判定します。
+        fn spans(spring: &Spring, (a, b): (usize, usize)) -> bool {
+            (spring.p1_index, spring.p2_index) == (a, b) || (spring.p1_index, spring.p2_index) == (b, a)
+        }
+
+        /// ボディの形状を変えずに、固定されていない全ての質点の質量を `scale` 倍します（`inv_mass` を `scale` で割ります）。
+        ///
+        /// `1.0` より大きい値で重くなり、衝突では軽いボディを押しのけるようになります。固定された質点は
+        /// 固定のまま変更しません。ボディが存在しない場合や、`scale` が正の有限値でない場合は `false` を返します。
+        pub fn set_body_mass_scale(&mut self, handle: SoftBodyHandle, scale: Scalar) -> bool {
+            if !(scale.is_finite() && scale > 0.0) { return false; }
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            for &idx in &self.soft_bodies[body_idx].particle_indices {
+                let p = &mut self.particles[idx];
+                if !p.is_fixed {
+                    p.inv_mass /= scale;
+                }
+            }
+            true
+        }
+
+        /// 外周を持つボディに、目標の面積 `target_area`（`None` の場合は現在の面積）の強さ `pressure` の圧力を追加します。
+        fn add_pressure(&mut self, handle: SoftBodyHandle, pressure: Scalar, target_area: Option<Scalar>) {
+            let Some(body_idx) = self.body_index(handle) else { return };
+            let body = &self.soft_bodies[body_idx];
+            let Some(wires) = &body.outline_wires else { return };
+            let ring = wires.iter().map(|&(a, _)| a).collect();
+            let mut constraint = PressureConstraint::new(ring, pressure, &self.particles);
+            if let Some(target_area) = target_area {
+                constraint.target_area = target_area;
+            }
+            self.soft_bodies[body_idx].pressure_constraints.push(constraint);
+        }
+
+        /// ハンドルに対応するボディの `soft_bodies` 内での位置を返します。
+        fn body_index(&self, handle: SoftBodyHandle) -> Option<usize> {
+            self.soft_bodies.iter().position(|sb| sb.handle == handle)
+        }
+
+        /// ロープや鎖のような、開いた1次元の質点列をソフトボディとして追加します。
+        ///
+        /// `points` の各点に質点を生成し、隣り合う質点同士をバネで結びます（両端は閉じません）。
+        /// `config.bend_stiffness > 0.0` の場合は1つ飛ばしの質点間に曲げバネを追加し、
+        /// `config.pin_first` / `config.pin_last` で両端の質点を固定でき、`config.angle_limits` で
+        /// 各関節の曲がる角度を制限できます。
+        /// 形状維持拘束は他のボディと同様に `config.shape_stiffness` に従うため、
+        /// 自由に垂れ下がるロープにするには `0.0` を指定してください。
+        pub fn add_chain(&mut self, points: &[Vec2], config: &SoftBodyConfig) -> SoftBodyHandle {
+            let mut particle_indices = Vec::with_capacity(points.len());
+            for &pos in points {
+                particle_indices.push(self.spawn_particle(pos, config));
+            }
+
+            let pins = [(config.pin_first, particle_indices.first()), (config.pin_last, particle_indices.last())];
+            for (pin, idx) in pins {
+                if let (true, Some(&idx)) = (pin, idx) {
+                    self.particles[idx].is_fixed = true;
+                    self.particles[idx].inv_mass = 0.0;
+                }
+            }
+
+            let mut springs = Vec::new();
+            for pair in particle_indices.windows(2) {
+                springs.push(Spring::new(pair[0], pair[1], config.stiffness, &self.particles));
+            }
+            if config.bend_stiffness > 0.0 {
+                for triple in particle_indices.windows(3) {
+                    springs.push(Spring::new(triple[0], triple[2], config.bend_stiffness, &self.particles));
+                }
+            }
+
+            let shape_constraint = if config.shape_stiffness > 0.0 {
+                Some(ShapeMatchingConstraint::new(particle_indices.clone(), config.shape_stiffness, &self.particles))
+            } else {
+                None
+            };
+
+            let mut body = SoftBody::new(particle_indices, springs, shape_constraint, None);
+            if let Some((min_angle, max_angle)) = config.angle_limits {
+                body.angle_constraints = body.particle_indices.windows(3)
+                    .map(|w| AngleConstraint::new(w[0], w[1], w[2], min_angle, max_angle, config.stiffness))
+                    .collect();
+            }
+            body.tag = config.tag;
+            body.is_sensor = config.is_sensor;
+            self.push_body(body)
+        }
+
+        /// `shape` の形状のボディを、対応する生成関数（`add_soft_body`, `add_convex_body`, `add_chain`）で追加します。
+        ///
+        /// `ShapeKind::Polygon`, `ShapeKind::Chain` の点は絶対座標で、`config.center` は使いません。
+        /// `ShapeKind::Grid` の `rows`, `cols` は `config` のものより優先されます。`ShapeKind::Circle` は
+        /// `config.center` を中心とする正多角形を追加します。生成関数が返すエラーをそのまま返します。
+        pub fn add_body(&mut self, shape: ShapeKind, config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            match shape {
+                ShapeKind::Grid { rows, cols } => self.add_soft_body(&SoftBodyConfig { rows, cols, ..config.clone() }),
+                ShapeKind::Polygon(points) => self.add_convex_body(&points, config),
+                ShapeKind::Chain(points) => Ok(self.add_chain(&points, config)),
+                ShapeKind::Circle { radius, segments } => self.add_regular_polygon(config.center, radius, segments, config),
+            }
+        }
+
+        /// `center` を中心とし、半径 `radius` の円に内接する正 `sides` 角形のボディを `add_convex_body` で追加します。
+        ///
+        /// 最初の頂点は中心の右（角度 `0`）にあり、角度の増える向きに並びます。`sides` が3未満の場合は
+        /// `ShapeError::NotEnoughParticles` を返します。
+        pub fn add_regular_polygon(&mut self, center: Vec2, radius: Scalar, sides: usize, config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            let points: Vec<Vec2> = (0..sides)
+                .map(|k| center + Self::polar(consts::TAU * k as Scalar / sides as Scalar, radius))
+                .collect();
+            self.add_convex_body(&points, config)
+        }
+
+        /// `center` を中心とする `points` 個の角を持つ星形のボディを `add_convex_body` で追加します。
+        ///
+        /// 外側の頂点（半径 `r_outer`）と内側の頂点（半径 `r_inner`）を交互に `2 * points` 個並べた外周になり、
+        /// 最初の外側の頂点は中心の右（角度 `0`）にあります。星形は凸ではありませんが、自己交差しないため
+        /// 外周と形状維持拘束を持つ通常のボディになります。`points` が2未満の場合は `ShapeError::NotEnoughParticles` を返します。
+        pub fn add_star(&mut self, center: Vec2, r_outer: Scalar, r_inner: Scalar, points: usize, config: &SoftBodyConfig) -> Result<SoftBodyHandle, ShapeError> {
+            if points < 2 {
+                return Err(ShapeError::NotEnoughParticles);
+            }
+            let vertices: Vec<Vec2> = (0..points * 2)
+                .map(|k| {
+                    let r = if k % 2 == 0 { r_outer } else { r_inner };
+                    center + Self::polar(consts::TAU * k as Scalar / (points * 2) as Scalar, r)
+                })
+                .collect();
+            self.add_convex_body(&vertices, config)
+        }
+
+        /// 角度 `angle`、長さ `r` のベクトルを返します。
+        fn polar(angle: Scalar, r: Scalar) -> Vec2 {
+            Vec2::new(float::cos(angle), float::sin(angle)) * r
+        }
+
+        /// シミュレーションを 1 ステップ進めます。
+        ///
+        /// # Arguments
+        ///
+        /// * `dt` - タイムステップ（例: `1.0 / 60.0`）。
+        ///   `0.0` 以下（負の値を含む）や有限でない値は受け付けず、何もせずに戻ります。
+        ///   最初のフレームなどでフレーム時間が `0.0` になる場合でも安全に呼び出せます。
+        pub fn step(&mut self, dt: Scalar) {
+            self.run_spawners(dt);
+            self.step_with_gravity_scale(dt, 1.0, &mut |_, _| {});
+        }
+
+        /// `step` と同様に 1 ステップ進め、ソルバーの各反復の後に `f(反復の番号, 全質点)` を呼び出します。
+        ///
+        /// 反復の番号は `0` から始まり、各反復での拘束・衝突・境界の解決が終わった時点の質点が渡されます。
+        /// 反復ごとに位置が収束していく様子の可視化などに使えます。`dt` が無効な場合は一度も呼び出しません。
+        pub fn step_with_callback(&mut self, dt: Scalar, mut f: impl FnMut(usize, &[Particle])) {
+            self.run_spawners(dt);
+            self.step_with_gravity_scale(dt, 1.0, &mut f);
+        }
+
+        /// 出現位置 `position` に、`interval` 秒ごとにボディを生成するスポナーを追加し、そのハンドルを返します。
+        ///
+        /// ボディは `step` の中で、経過時間の合計が `interval` を超えるたびに `template` と `shape` から
+        /// 生成されます。生成数の上限は `spawner_mut` で `Spawner::max_bodies` に設定できます。
+        /// 生成に失敗する設定（`ShapeError` になるもの）の場合、そのボディは生成されません。
+        pub fn add_spawner(&mut self, position: Vec2, interval: Scalar, template: SoftBodyConfig, shape: ShapeKind) -> SpawnerHandle {
+            let handle = SpawnerHandle(self.next_spawner_id);
+            self.next_spawner_id += 1;
+            self.spawners.push((handle, Spawner::new(position, interval, template, shape)));
+            handle
+        }
+
+        /// スポナーを取り除きます。既に生成されたボディはそのまま残ります。
+        /// スポナーが存在しない場合は `false` を返します。
+        pub fn remove_spawner(&mut self, handle: SpawnerHandle) -> bool {
+            let len = self.spawners.len();
+            self.spawners.retain(|(h, _)| *h != handle);
+            self.spawners.len() != len
+        }
+
+        /// ハンドルに対応するスポナーを返します。存在しない場合は `None` を返します。
+        pub fn spawner(&self, handle: SpawnerHandle) -> Option<&Spawner> {
+            self.spawners.iter().find(|(h, _)| *h == handle).map(|(_, s)| s)
+        }
+
+        /// ハンドルに対応するスポナーを可変で返します。存在しない場合は `None` を返します。
+        pub fn spawner_mut(&mut self, handle: SpawnerHandle) -> Option<&mut Spawner> {
+            self.spawners.iter_mut().find(|(h, _)| *h == handle).map(|(_, s)| s)
+        }
+
+        /// 中心 `center`、半径 `radius`、強さ `intensity` の熱源を追加し、そのハンドルを返します。
+        ///
+        /// 熱源の近くのバネは剛性が下がって柔らかくなり（`HeatSource` を参照）、熱源から離れるか
+        /// 熱源を取り除くと元の剛性に戻ります。溶けるろうそくや溶岩のような表現に使えます。
+        pub fn add_heat_source(&mut self, center: Vec2, radius: Scalar, intensity: Scalar) -> HeatSourceHandle {
+            let handle = HeatSourceHandle(self.next_heat_source_id);
+            self.next_heat_source_id += 1;
+            self.heat_sources.push((handle, HeatSource { center, radius, intensity }));
+            handle
+        }
+
+        /// 熱源を取り除きます。熱源が存在しない場合は `false` を返します。
+        pub fn remove_heat_source(&mut self, handle: HeatSourceHandle) -> bool {
+            let len = self.heat_sources.len();
+            self.heat_sources.retain(|(h, _)| *h != handle);
+            self.heat_sources.len() != len
+        }
+
+        /// ハンドルに対応する熱源を可変で返します。存在しない場合は `None` を返します。
+        /// 熱源を動かした結果は次のステップから反映されます。
+        pub fn heat_source_mut(&mut self, handle: HeatSourceHandle) -> Option<&mut HeatSource> {
+            self.heat_sources.iter_mut().find(|(h, _)| *h == handle).map(|(_, s)| s)
+        }
+
+        /// 全ての熱源による点 `pos` での熱の合計を返します。
+        pub fn heat_at(&self, pos: Vec2) -> Scalar {
+            self.heat_sources.iter().map(|(_, source)| source.heat_at(pos)).sum()
+        }
+
+        /// 全てのバネの `thermal_stiffness_scale` を、バネの中点での熱から計算し直します。
+        fn update_thermal_stiffness(&mut self) {
+            let particles = &self.particles;
+            let sources = &self.heat_sources;
+            let scale = |spring: &Spring| {
+                let mid = Vec2::mid(particles[spring.p1_index].pos, particles[spring.p2_index].pos);
+                let heat: Scalar = sources.iter().map(|(_, source)| source.heat_at(mid)).sum();
+                1.0 / (1.0 + heat)
+            };
+            for sb in &mut self.soft_bodies {
+                for spring in &mut sb.springs {
+                    spring.thermal_stiffness_scale = scale(spring);
+                }
+            }
+            for (_, spring) in &mut self.connections {
+                spring.thermal_stiffness_scale = scale(spring);
+            }
+        }
+
+        /// 全てのスポナーの経過時間を `dt` 進め、`interval` を超えた分だけボディを生成します。
+        fn run_spawners(&mut self, dt: Scalar) {
+            if !dt.is_finite() || dt <= 0.0 {
+                return;
+            }
+            for k in 0..self.spawners.len() {
+                let spawner = &mut self.spawners[k].1;
+                if spawner.interval <= 0.0 {
+                    continue;
+                }
+                spawner.elapsed += dt;
+                let mut due = 0;
+                while spawner.elapsed >= spawner.interval && spawner.max_bodies.is_none_or(|max| spawner.spawned + due < max) {
+                    spawner.elapsed -= spawner.interval;
+                    due += 1;
+                }
+                spawner.spawned += due;
+                let spawner = spawner.clone();
+                for _ in 0..due {
+                    self.spawn_from(&spawner);
+                }
+            }
+        }
+
+        /// スポナーの設定に従ってボディを1つ生成します。
+        fn spawn_from(&mut self, spawner: &Spawner) {
+            let config = SoftBodyConfig { center: spawner.position, ..spawner.template.clone() };
+            // 生成できない設定の場合は何もしない
+            let _ = self.add_body(spawner.shape.clone().translated(spawner.position), &config);
+        }
+
+        /// 重力を `gravity_scale` 倍にして 1 ステップ進めます。各反復の後に `on_iteration` を呼び出します。
+        fn step_with_gravity_scale(&mut self, dt: Scalar, gravity_scale: Scalar, on_iteration: &mut dyn FnMut(usize, &[Particle])) {
+            if !dt.is_finite() || dt <= 0.0 {
+                return;
+            }
+            let ramp = self.config.gravity_ramp_steps;
+            let gravity_scale = if self.step_count < ramp {
+                gravity_scale * self.step_count as Scalar / ramp as Scalar
+            } else {
+                gravity_scale
+            };
+            self.step_count += 1;
+            self.update_thermal_stiffness();
+
+            // 1. 力を適用 (速度、位置の順に積分)
+            for sb in &self.soft_bodies {
+                for pressure in &sb.pressure_constraints {
+                    pressure.apply(&mut self.particles, dt);
+                }
+            }
+            for p in &mut self.particles {
+                if p.is_fixed { continue; }
+                // 空気抵抗は半陰的に解き、大きな dt でも速度の向きが反転しないようにする
+                let drag = self.config.air_drag.map_or(0.0, |c| c * 2.0 * p.radius * p.inv_mass * p.vel.length() * dt);
+                p.vel += self.config.gravity_at(p.pos) * (dt * gravity_scale);
+                if drag > 0.0 {
+                    p.vel = p.vel * (1.0 / (1.0 + drag));
+                }
+                p.prev_pos = p.pos;
+                p.pos += p.vel * dt;
+            }
+            // 半陰的オイラー法では、拘束による位置の補正の向きで速度を射影するため予測位置を覚えておく
+            let predicted: Vec<Vec2> = match self.config.integrator {
+                Integrator::Verlet => Vec::new(),
+                Integrator::SemiImplicitEuler => self.particles.iter().map(|p| p.pos).collect(),
+            };
+            if self.config.continuous_collision && self.config.enable_collisions {
+                self.clamp_swept_motion();
+            }
+
+            // 2. 拘束を解決 (反復法)
+            let record_contacts = self.config.collision_event_threshold.is_some() || self.config.restitution.is_some();
+            let mut contacts = record_contacts.then(ContactAccumulator::default);
+            let mut body_contacts = core::mem::take(&mut self.body_contacts);
+            body_contacts.clear();
+            let mut boundary_hits = self.config.boundary_event_threshold.map(|_| BTreeMap::new());
+            let iterations = if self.config.auto_iterations {
+                self.recommended_iterations()
+            } else {
+                self.config.solver_iterations
+            };
+            for iteration in 0..iterations {
+                self.solve_constraints();
+                if self.config.enable_collisions {
+                    for _ in 0..self.config.collision_iterations.max(1) {
+                        self.solve_collisions(contacts.as_mut(), &mut body_contacts);
+                        if self.config.use_wire_collisions { self.solve_wire_collisions(); }
+                        if self.config.outline_collisions { self.solve_outline_collisions(); }
+                    }
+                }
+                if self.config.enable_boundaries {
+                    self.apply_boundary_conditions(boundary_hits.as_mut());
+                    self.apply_containment();
+                }
+                on_iteration(iteration, &self.particles);
+            }
+
+            // 3. 速度を更新
+            let pre_solve_velocities: Option<Vec<Vec2>> =
+                self.config.restitution.map(|_| self.particles.iter().map(|p| p.vel).collect());
+            for (i, p) in self.particles.iter_mut().enumerate() {
+                if p.is_fixed {
+                    p.vel = Vec2::new(0.0, 0.0);
+                    continue;
+                }
+                let new_vel = match self.config.integrator {
+                    Integrator::Verlet => (p.pos - p.prev_pos) * (1.0 / dt),
+                    Integrator::SemiImplicitEuler => {
+                        let correction = p.pos - predicted[i];
+                        let normal = correction.normalize();
+                        let against = Vec2::dot(p.vel, normal);
+                        if against < 0.0 { p.vel - normal * against } else { p.vel }
+                    }
+                };
+                p.vel = new_vel * self.config.damping;
+            }
+            if let (Some(restitution), Some(contacts), Some(pre)) = (self.config.restitution, &contacts, &pre_solve_velocities) {
+                self.apply_restitution(contacts, pre, restitution);
+            }
+
+            // 4. 衝突イベントとボディ間の接触を記録
+            body_contacts.sort_by_key(|(a, b)| (a.0, b.0));
+            self.body_contacts = body_contacts;
+            self.collision_events.clear();
+            if let (Some(threshold), Some(contacts)) = (self.config.collision_event_threshold, contacts) {
+                self.collision_events.extend(
+                    contacts.events.into_iter()
+                        .map(|event| CollisionEvent { impulse: event.impulse / dt, ..event })
+                        .filter(|event| event.impulse >= threshold),
+                );
+            }
+            self.boundary_events.clear();
+            if let (Some(threshold), Some(hits)) = (self.config.boundary_event_threshold, boundary_hits) {
+                self.boundary_events.extend(
+                    hits.into_iter()
+                        .map(|((particle, side), impact_speed)| BoundaryEvent { particle, side, impact_speed })
+                        .filter(|event| event.impact_speed >= threshold),
+                );
+            }
+
+            // 5. 履歴を記録
+            if self.history_capacity > 0 {
+                if self.history.len() == self.history_capacity {
+                    self.history.pop_front();
+                }
+                self.history.push_back(self.snapshot());
+            }
+        }
+
+        /// 現在の状態を保存したスナップショットを返します。
+        pub fn snapshot(&self) -> Snapshot {
+            Snapshot {
+                particles: self.particles.clone(),
+                soft_bodies: self.soft_bodies.clone(),
+                connections: self.connections.clone(),
+                fixed_springs: self.fixed_springs.clone(),
+                drag: self.drag.clone(),
+                spawners: self.spawners.clone(),
+                particle_owners: self.particle_owners.clone(),
+                rng: self.rng.clone(),
+                step_count: self.step_count,
+            }
+        }
+
+        /// `snapshot` で保存した状態を復元します。設定と熱源、履歴はそのまま残ります。
+        ///
+        /// 新しく割り当てるハンドルの ID は巻き戻さないため、保存後に追加したボディのハンドルが
+        /// 復元後に別のボディを指すことはありません。
+        pub fn restore(&mut self, snapshot: &Snapshot) {
+            self.restore_owned(snapshot.clone());
+        }
+
+        fn restore_owned(&mut self, snapshot: Snapshot) {
+            self.particles = snapshot.particles;
+            self.soft_bodies = snapshot.soft_bodies;
+            self.connections = snapshot.connections;
+            self.fixed_springs = snapshot.fixed_springs;
+            self.drag = snapshot.drag;
+            self.spawners = snapshot.spawners;
+            self.particle_owners = snapshot.particle_owners;
+            self.rng = snapshot.rng;
+            self.step_count = snapshot.step_count;
+            self.collision_events.clear();
+            self.boundary_events.clear();
+            self.body_contacts.clear();
+        }
+
+        /// 直近 `capacity` ステップの状態を、各ステップの終わりに自動で記録するようにします。
+        ///
+        /// 記録はリングバッファで、容量を超えると最も古い状態から捨てます。`capacity` が `0` の場合は
+        /// 記録をやめて履歴を消去します。容量を小さくした場合は古い状態から捨てて収めます。
+        pub fn enable_history(&mut self, capacity: usize) {
+            self.history_capacity = capacity;
+            while self.history.len() > capacity {
+                self.history.pop_front();
+            }
+        }
+
+        /// 記録されている状態の数を返します。
+        pub fn history_len(&self) -> usize {
+            self.history.len()
+        }
+
+        /// `n` ステップ前に記録した状態を返します。`0` は直前のステップの終わり（最新の記録）です。
+        /// 記録が無い場合は `None` を返します。
+        pub fn history_at(&self, n: usize) -> Option<&Snapshot> {
+            self.history.len().checked_sub(n + 1).and_then(|i| self.history.get(i))
+        }
+
+        /// 最新の記録を捨てて、その1つ前に記録した状態を復元します。
+        ///
+        /// 記録が2つ未満で戻る先が無い場合は何もせずに `false` を返します。
+        pub fn step_back(&mut self) -> bool {
+            if self.history.len() < 2 {
+                return false;
+            }
+            self.history.pop_back();
+            if let Some(previous) = self.history.back().cloned() {
+                self.restore_owned(previous);
+            }
+            true
+        }
+
+        /// 描画を始める前にシーンを静止状態に近づけます。
+        ///
+        /// 前半の `steps / 2` 回で重力を `0` から徐々に強めながら、全体で `steps` 回ステップを進めます。
+        /// 各ステップの後に速度を強く減衰させるため、空中に置かれたボディは勢いをつけずに沈み込み、
+        /// 最初のフレームでの大きな落下や跳ね返りを避けられます。終了時には全質点の速度を `0` にします。
+        pub fn warm_start(&mut self, steps: usize, dt: Scalar) {
+            const WARM_START_DAMPING: Scalar = 0.9;
+            let ramp_steps = (steps / 2).max(1);
+            for k in 0..steps {
+                let gravity_scale = ((k + 1) as Scalar / ramp_steps as Scalar).min(1.0);
+                self.step_with_gravity_scale(dt, gravity_scale, &mut |_, _| {});
+                for p in &mut self.particles {
+                    p.vel = p.vel * WARM_START_DAMPING;
+                }
+            }
+            self.freeze_velocities();
+            self.collision_events.clear();
+            self.boundary_events.clear();
+            self.body_contacts.clear();
+        }
+
+        /// 直前の `step` で発生した質点間の衝突のうち、力積が
+        /// `SimulationConfig::collision_event_threshold` 以上のものを返します。
+        /// 閾値が `None` の場合は常に空です。ワイヤーとの衝突は含まず、壁（`bounds`）との衝突は
+        /// `boundary_events` で取得します。
+        pub fn collision_events(&self) -> &[CollisionEvent] {
+            &self.collision_events
+        }
+
+        /// 直前の `step` で接触していたボディの組を、重複なく返します。
+        ///
+        /// 異なるボディに属する2つの質点の衝突が解決された組を、力積の大きさに関係なく報告します。
+        /// 各組は追加順の早いボディが先で、組の並びも追加順です。ワイヤー・外周同士の衝突や、
+        /// ボディに属さない質点との衝突は含みません。積み重ねたボディの支持関係の把握などに使えます。
+        pub fn contacts(&self) -> &[(SoftBodyHandle, SoftBodyHandle)] {
+            &self.body_contacts
+        }
+
+        /// 直前の `step` で発生した質点と境界（`bounds`）の衝突のうち、衝突の速さが
+        /// `SimulationConfig::boundary_event_threshold` 以上のものを、質点と辺の組ごとに1つずつ返します。
+        /// 閾値が `None` の場合は常に空です。境界上で静止している質点も毎ステップ重力の分だけ辺に
+        /// 押し付けられるため、小さな閾値ではそれらも報告されます。
+        pub fn boundary_events(&self) -> &[BoundaryEvent] {
+            &self.boundary_events
+        }
+
+        /// `SimulationConfig::solve_order` に従って、全てのボディの拘束と、シミュレーションが保持するバネを1回ずつ解決します。
+        fn solve_constraints(&mut self) {
+            match self.config.solve_order {
+                SolveOrder::Sequential => {
+                    let k = |stiffness: Scalar| self.config.effective_stiffness(stiffness);
+                    let length = self.config.length_fn();
+                    let tolerance = self.config.shape_match_tolerance;
+                    for sb in &mut self.soft_bodies {
+                        for spring in &sb.springs {
+                            spring.solve_with_stiffness(&mut self.particles, k(spring.heated_stiffness()), length);
+                        }
+                        for bend in &sb.bend_constraints {
+                            bend.solve_with_stiffness(&mut self.particles, k(bend.stiffness));
+                        }
+                        for angle in &sb.angle_constraints {
+                            angle.solve_with_stiffness(&mut self.particles, k(angle.stiffness));
+                        }
+                        if let Some(sc) = &mut sb.shape_constraint {
+                            sc.solve_with_stiffness(&mut self.particles, k(sc.stiffness), tolerance);
+                        }
+                    }
+                    for (_, spring) in &self.connections {
+                        spring.solve_with_stiffness(&mut self.particles, k(spring.heated_stiffness()), length);
+                    }
+                    for (_, spring) in &self.fixed_springs {
+                        spring.solve_with_stiffness(&mut self.particles, k(spring.stiffness), length);
+                    }
+                    if let Some(drag) = &self.drag {
+                        drag.solve_with_stiffness(&mut self.particles, k(drag.stiffness));
+                    }
+                }
+                SolveOrder::RandomizedPerIteration => {
+                    let mut refs = self.constraint_refs();
+                    // Fisher-Yates シャッフル
+                    for i in (1..refs.len()).rev() {
+                        let j = (self.rng.next_u64() % (i as u64 + 1)) as usize;
+                        refs.swap(i, j);
+                    }
+                    for c in refs {
+                        self.solve_constraint(c, None);
+                    }
+                }
+                SolveOrder::Jacobi => {
+                    // 全ての拘束を同じ位置から解き、質点ごとに補正量の平均を適用する
+                    let base: Vec<Vec2> = self.particles.iter().map(|p| p.pos).collect();
+                    let mut delta = vec![Vec2::new(0.0, 0.0); base.len()];
+                    let mut count = vec![0usize; base.len()];
+                    let mut touched = Vec::new();
+                    for c in self.constraint_refs() {
+                        touched.clear();
+                        self.solve_constraint(c, Some(&mut touched));
+                        for &i in &touched {
+                            delta[i] += self.particles[i].pos - base[i];
+                            count[i] += 1;
+                            self.particles[i].pos = base[i];
+                        }
+                    }
+                    for (i, p) in self.particles.iter_mut().enumerate() {
+                        if count[i] > 0 {
+                            p.pos += delta[i] * (1.0 / count[i] as Scalar);
+                        }
+                    }
+                }
+            }
+        }
+
+        /// 解決すべき全ての拘束を、`Sequential` と同じ順序で列挙します。
+        fn constraint_refs(&self) -> Vec<ConstraintRef> {
+            let mut refs = Vec::new();
+            for (b, sb) in self.soft_bodies.iter().enumerate() {
+                refs.extend((0..sb.springs.len()).map(|k| ConstraintRef::Spring(b, k)));
+                refs.extend((0..sb.bend_constraints.len()).map(|k| ConstraintRef::Bend(b, k)));
+                refs.extend((0..sb.angle_constraints.len()).map(|k| ConstraintRef::Angle(b, k)));
+                if sb.shape_constraint.is_some() {
+                    refs.push(ConstraintRef::Shape(b));
+                }
+            }
+            refs.extend((0..self.connections.len()).map(ConstraintRef::Connection));
+            refs.extend((0..self.fixed_springs.len()).map(ConstraintRef::FixedSpring));
+            if self.drag.is_some() {
+                refs.push(ConstraintRef::Drag);
+            }
+            refs
+        }
+
+        /// 拘束を1つ解決します。`touched` が `Some` の場合は、拘束が参照する質点のインデックスを追加します。
+        fn solve_constraint(&mut self, c: ConstraintRef, touched: Option<&mut Vec<usize>>) {
+            let particles = &mut self.particles;
+            let k = |stiffness: Scalar| self.config.effective_stiffness(stiffness);
+            let length = self.config.length_fn();
+            let mut touched = touched;
+            let mut touch = |indices: &[usize]| {
+                if let Some(t) = touched.as_deref_mut() {
+                    t.extend_from_slice(indices);
+                }
+            };
+            match c {
+                ConstraintRef::Spring(b, i) => {
+                    let spring = &self.soft_bodies[b].springs[i];
+                    spring.solve_with_stiffness(particles, k(spring.heated_stiffness()), length);
+                    touch(&[spring.p1_index, spring.p2_index]);
+                }
+                ConstraintRef::Bend(b, i) => {
+                    let bend = &self.soft_bodies[b].bend_constraints[i];
+                    bend.solve_with_stiffness(particles, k(bend.stiffness));
+                    touch(&[bend.prev_index, bend.cur_index, bend.next_index]);
+                }
+                ConstraintRef::Angle(b, i) => {
+                    let angle = &self.soft_bodies[b].angle_constraints[i];
+                    angle.solve_with_stiffness(particles, k(angle.stiffness));
+                    touch(&[angle.a, angle.b, angle.c]);
+                }
+                ConstraintRef::Shape(b) => {
+                    if let Some(sc) = &mut self.soft_bodies[b].shape_constraint {
+                        sc.solve_with_stiffness(particles, k(sc.stiffness), self.config.shape_match_tolerance);
+                        touch(&sc.particle_indices);
+                    }
+                }
+                ConstraintRef::Connection(i) => {
+                    let spring = &self.connections[i].1;
+                    spring.solve_with_stiffness(particles, k(spring.heated_stiffness()), length);
+                    touch(&[spring.p1_index, spring.p2_index]);
+                }
+                ConstraintRef::FixedSpring(i) => {
+                    let spring = &self.fixed_springs[i].1;
+                    spring.solve_with_stiffness(particles, k(spring.stiffness), length);
+                    touch(&[spring.particle_index]);
+                }
+                ConstraintRef::Drag => {
+                    if let Some(drag) = &self.drag {
+                        drag.solve_with_stiffness(particles, k(drag.stiffness));
+                        touch(&[drag.particle_index]);
+                    }
+                }
+            }
         }
 
-        /// 凸形状のソフトボディを追加する新しいファクトリ関数
-        pub fn add_convex_body(&mut self, particle_positions: &[Vec2], config: &SoftBodyConfig) -> Result<(), ShapeError> {
-            if particle_positions.len() < 3 { return Err(ShapeError::NotEnoughParticles); }
-            if geometry::check_self_intersection(particle_positions) { return Err(ShapeError::SelfIntersecting); }
-            let _start_index = self.particles.len();
-            let mut particle_indices = Vec::new();
-            for pos in particle_positions {
-                let mut p = Particle::new(pos.x, pos.y);
-                p.radius = config.particle_radius;
-                if config.is_fixed { p.is_fixed = true; p.inv_mass = 0.0; } else { p.inv_mass = config.particle_inv_mass; }
-                particle_indices.push(self.particles.len());
-                self.particles.push(p);
-            }
-            let mut outline_wires = Vec::new();
-            for i in 0..particle_indices.len() {
-                outline_wires.push((particle_indices[i], particle_indices[(i + 1) % particle_indices.len()]));
+        /// `center` から半径 `radius` 以内の固定されていない質点に、外向きの力積を与えます。
+        ///
+        /// 力積の大きさは中心で `strength`、半径の縁で `0` となるよう線形に減衰し、
+        /// 速度の変化は力積に質量の逆数を掛けたものになります（重い質点ほど動きにくい）。
+        /// 中心と完全に重なる質点は方向が定まらないため変化しません。
+        pub fn apply_radial_impulse(&mut self, center: Vec2, strength: Scalar, radius: Scalar) {
+            if radius <= 0.0 { return; }
+            for p in &mut self.particles {
+                if p.is_fixed { continue; }
+                let offset = p.pos - center;
+                let dist = offset.length();
+                if dist >= radius { continue; }
+                let impulse = strength * (1.0 - dist / radius);
+                p.vel += offset.normalize() * (impulse * p.inv_mass);
             }
-            let mut springs = Vec::new();
-            for &(p1_idx, p2_idx) in &outline_wires {
-                springs.push(Spring::new(p1_idx, p2_idx, config.stiffness, &self.particles));
+        }
+
+        /// ボディを重心のまわりに角速度 `omega`（rad/s）で回転させます。
+        ///
+        /// 固定されていない各質点の速度に `omega × r`（`r` は固定されていない質点の質量で重み付けした
+        /// 重心からの相対位置）を加えます。重心まわりの回転なので、ボディの運動量は変わりません。
+        /// 正の `omega` は `Vec2::perpendicular` の向き（y 軸が下向きの画面座標では時計回り）です。
+        /// ボディが存在しない場合や、固定されていない質点が無い場合は `false` を返します。
+        pub fn apply_angular_velocity(&mut self, handle: SoftBodyHandle, omega: Scalar) -> bool {
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            let Some((center, _)) = self.body_mass_frame(body_idx) else { return false };
+            for &i in &self.soft_bodies[body_idx].particle_indices {
+                let p = &mut self.particles[i];
+                if p.is_fixed { continue; }
+                p.vel += (p.pos - center).perpendicular() * omega;
             }
-            let shape_constraint = if config.shape_stiffness > 0.0 { Some(ShapeMatchingConstraint::new(particle_indices.clone(), config.shape_stiffness, &self.particles)) } else { None };
-            self.soft_bodies.push(SoftBody { particle_indices, springs, shape_constraint, outline_wires: Some(outline_wires) });
-            Ok(())
+            true
         }
 
-        /// シミュレーションを 1 ステップ進めます。
+        /// ボディの重心まわりの角運動量を `torque` だけ増やします（1回きりの角力積として与えます）。
         ///
-        /// # Arguments
+        /// 固定されていない質点の質量と重心からの距離から慣性モーメント `I = Σ m |r|^2` を求め、
+        /// 角速度 `torque / I` で `apply_angular_velocity` を呼び出します。ボディが存在しない場合や、
+        /// 慣性モーメントが `0` の場合（質点が1つだけなど）は `false` を返します。
+        pub fn apply_torque(&mut self, handle: SoftBodyHandle, torque: Scalar) -> bool {
+            let Some(body_idx) = self.body_index(handle) else { return false };
+            let Some((_, inertia)) = self.body_mass_frame(body_idx) else { return false };
+            if inertia < Scalar::EPSILON { return false; }
+            self.apply_angular_velocity(handle, torque / inertia)
+        }
+
+        /// ボディの固定されていない質点の重心と、そのまわりの慣性モーメントを返します。
+        /// 固定されていない質点が無い場合は `None` を返します。
+        fn body_mass_frame(&self, body_idx: usize) -> Option<(Vec2, Scalar)> {
+            let particles = || self.soft_bodies[body_idx].particle_indices.iter()
+                .filter_map(|&i| Some((self.particles[i].pos, self.particles[i].finite_mass()?)));
+            let total_mass: Scalar = particles().map(|(_, m)| m).sum();
+            if total_mass < Scalar::EPSILON { return None; }
+            let center = particles().fold(Vec2::new(0.0, 0.0), |acc, (pos, m)| acc + pos * m) * (1.0 / total_mass);
+            let inertia = particles().map(|(pos, m)| m * (pos - center).length_squared()).sum();
+            Some((center, inertia))
+        }
+
+        /// 全ての質点の速度を `0` にし、`prev_pos` を現在位置に揃えて、全ての動きを即座に止めます。
         ///
-        /// * `dt` - タイムステップ（例: `1.0 / 60.0`）。
-        pub fn step(&mut self, dt: f64) {
-            // 1. 力を適用 (Verlet積分)
+        /// `prev_pos` も揃えるため、`prev_pos` と `pos` の差から求まる速度（Verlet 積分や
+        /// `interpolated_positions` が使う変位）も `0` になります。
+        pub fn freeze_velocities(&mut self) {
             for p in &mut self.particles {
-                if p.is_fixed { continue; }
-                p.vel += self.config.gravity * dt;
+                p.vel = Vec2::new(0.0, 0.0);
                 p.prev_pos = p.pos;
-                p.pos += p.vel * dt;
             }
+        }
 
-            // 2. 拘束を解決 (反復法)
-            for _ in 0..self.config.solver_iterations {
-                for sb in &mut self.soft_bodies {
-                    for spring in &sb.springs {
-                        spring.solve(&mut self.particles);
+        /// 全ての質点の速度を `factor` 倍します。`prev_pos` からの変位も同じ倍率で縮め、速度と整合させます。
+        /// `0.0` を渡すと `freeze_velocities` と同じ結果になります。
+        pub fn damp_velocities(&mut self, factor: Scalar) {
+            for p in &mut self.particles {
+                p.vel = p.vel * factor;
+                p.prev_pos = p.pos - (p.pos - p.prev_pos) * factor;
+            }
+        }
+
+        /// 全ての質点に対して、インデックスと可変参照を渡して `f` を順に呼び出します。
+        ///
+        /// 独自の力場を与える場合などに、`particles` フィールドに直接触れずに質点をまとめて変更できます。
+        /// `f` はインデックスの昇順に呼ばれ、質点の追加や削除はできないため、拘束が参照するインデックスは変わりません。
+        pub fn apply_to_particles(&mut self, mut f: impl FnMut(usize, &mut Particle)) {
+            for (i, p) in self.particles.iter_mut().enumerate() {
+                f(i, p);
+            }
+        }
+
+        /// このステップで接触した質点の組に、反発係数 `restitution` に応じた法線方向の力積を与えます。
+        ///
+        /// 接触前（拘束の解決前）の速度 `pre_solve_velocities` から求めた法線方向の相対速度 `v_n` が
+        /// 近づく向きの組について、現在の相対速度が `-restitution * v_n` になるよう、
+        /// 質量の逆数に比例して両方の速度を変えます。力積は大きさが等しく逆向きのため、運動量は保存されます。
+        /// 固定質点は無限大の質量として扱います。
+        fn apply_restitution(&mut self, contacts: &ContactAccumulator, pre_solve_velocities: &[Vec2], restitution: Scalar) {
+            for event in &contacts.events {
+                let (a, b) = (event.a, event.b);
+                let (pa, pb) = pair_mut(&mut self.particles, a, b);
+                let inv_mass = |p: &Particle| if p.is_fixed { 0.0 } else { p.inv_mass };
+                let (wa, wb) = (inv_mass(pa), inv_mass(pb));
+                let total_inv_mass = wa + wb;
+                if total_inv_mass < Scalar::EPSILON { continue; }
+
+                let diff = pa.pos - pb.pos;
+                let normal = if diff.length_squared() > Scalar::EPSILON { diff.normalize() } else { event.normal };
+                let approach = Vec2::dot(pre_solve_velocities[a] - pre_solve_velocities[b], normal);
+                if approach >= 0.0 { continue; }
+
+                let target = -restitution * approach;
+                let current = Vec2::dot(pa.vel - pb.vel, normal);
+                let impulse = (target - current) / total_inv_mass;
+                pa.vel += normal * (impulse * wa);
+                pb.vel -= normal * (impulse * wb);
+            }
+        }
+
+        /// 1ステップで半径より長く動いた質点の経路 `prev_pos → pos` を、他のボディの外周ワイヤーと
+        /// 他のボディの質点に対して調べ、最初に接触する位置まで `pos` を戻します（連続的衝突判定）。
+        ///
+        /// ワイヤーと他の質点は現在位置で静止しているものとして扱います。ワイヤーについては経路が辺を
+        /// 横切る場合のみ、辺からの距離が半径になる位置で止めます。開始時点で既に接触しているものは
+        /// 通常の衝突解決に任せます。
+        fn clamp_swept_motion(&mut self) {
+            let sensors = self.sensor_particles();
+            for i in 0..self.particles.len() {
+                let p = &self.particles[i];
+                if p.is_fixed || sensors.get(i).copied().unwrap_or(false) { continue; }
+                let (start, motion, radius) = (p.prev_pos, p.pos - p.prev_pos, p.radius);
+                let motion_len_sq = motion.length_squared();
+                if motion_len_sq <= radius * radius { continue; }
+                let owner = self.body_of_particle(i);
+                let path = Line::new(start, p.pos);
+
+                let mut first_hit: Scalar = 1.0;
+                for sb in &self.soft_bodies {
+                    if Some(sb.handle) == owner || sb.is_sensor { continue; }
+                    for &(a, b) in sb.outline_wires.iter().flatten() {
+                        let (wa, wb) = (self.particles[a].pos, self.particles[b].pos);
+                        let Some((t_cross, _)) = geometry::segment_intersection_params(&path, &Line::new(wa, wb)) else { continue };
+                        let normal = (wb - wa).perpendicular().normalize();
+                        let height = Vec2::dot(start - wa, normal).abs();
+                        let approach = Vec2::dot(motion, normal).abs();
+                        let t = if approach > Scalar::EPSILON { ((height - radius) / approach).max(0.0) } else { 0.0 };
+                        first_hit = first_hit.min(t.min(t_cross));
                     }
-                    if let Some(sc) = &mut sb.shape_constraint {
-                        sc.solve(&mut self.particles);
+                    for &j in &sb.particle_indices {
+                        let q = &self.particles[j];
+                        // |start + motion * t - q| = radius + q.radius の小さい方の解
+                        let offset = start - q.pos;
+                        let reach = radius + q.radius;
+                        let c = offset.length_squared() - reach * reach;
+                        if c <= 0.0 { continue; }
+                        let b = Vec2::dot(offset, motion);
+                        let disc = b * b - motion_len_sq * c;
+                        if b >= 0.0 || disc < 0.0 { continue; }
+                        let t = (-b - disc.sqrt()) / motion_len_sq;
+                        if t <= 1.0 {
+                            first_hit = first_hit.min(t);
+                        }
                     }
                 }
-                self.solve_collisions();
-                if self.config.use_wire_collisions { self.solve_wire_collisions(); }
-                self.apply_boundary_conditions();
-            }
-
-            // 3. 速度を更新
-            for p in &mut self.particles {
-                if p.is_fixed {
-                    p.vel = Vec2::new(0.0, 0.0);
-                    continue;
+                if first_hit < 1.0 {
+                    self.particles[i].pos = start + motion * first_hit;
                 }
-                let new_vel = (p.pos - p.prev_pos) * (1.0 / dt);
-                p.vel = new_vel * self.config.damping;
             }
         }
 
         /// 質点間の衝突を解決します。
-        fn solve_collisions(&mut self) {
-            let n = self.particles.len();
-            for i in 0..n {
-                for j in i + 1..n {
-                    let (p1, p2) = self.particles.split_at_mut(j);
-                    let (p1, p2) = (&mut p1[i], &mut p2[0]);
-                    
+        ///
+        /// 結果が質点の格納順序に依存しないよう、ペアは `collision_order` の順に解決します。
+        ///
+        /// 支え（境界 `bounds` の壁、閉じ込め領域 `containment` の辺、固定質点）に接している質点を
+        /// その支えへ押し込む補正では、その質点を無限大の質量として扱い、相手の質点だけを動かします。
+        /// 質量比の大きい質点が支えの上で重なっている場合に、軽い方が補正のほとんどを受けて支えに
+        /// 押し戻され、重なりが残り続けるのを防ぎます。固定質点は常に無限大の質量として扱います。
+        ///
+        /// `contacts` が `Some` の場合は、解決した接触を記録します。異なるボディの質点同士の接触は、
+        /// そのボディの組を `body_contacts` に重複なく追加します。
+        fn solve_collisions(&mut self, mut contacts: Option<&mut ContactAccumulator>, body_contacts: &mut Vec<(SoftBodyHandle, SoftBodyHandle)>) {
+            let supports = Supports::new(&self.config, &self.particles);
+            let order = self.collision_order();
+            let n = order.len();
+            // 外周同士の衝突で扱う質点（外周の頂点）について、その外周を持つボディのインデックス
+            let mut outlined: Vec<Option<usize>> = Vec::new();
+            if self.config.outline_collisions {
+                outlined.resize(self.particles.len(), None);
+                for (b, sb) in self.soft_bodies.iter().enumerate() {
+                    for &(v, _) in sb.outline_wires.iter().flatten() {
+                        outlined[v] = Some(b);
+                    }
+                }
+            }
+            let sensors = self.sensor_particles();
+            for a in 0..n {
+                for b in a + 1..n {
+                    let (i, j) = (order[a], order[b]);
+                    if sensors.get(i).copied().unwrap_or(false) || sensors.get(j).copied().unwrap_or(false) {
+                        continue;
+                    }
+                    if matches!((outlined.get(i), outlined.get(j)), (Some(Some(bi)), Some(Some(bj))) if bi != bj) {
+                        continue;
+                    }
+                    let (p1, p2) = pair_mut(&mut self.particles, i, j);
+
                     let diff = p1.pos - p2.pos;
                     let dist_sq = diff.length_squared();
                     let min_dist = p1.radius + p2.radius;
 
                     if dist_sq < min_dist * min_dist {
                         let dist = dist_sq.sqrt();
-                        let total_inv_mass = p1.inv_mass + p2.inv_mass;
-                        if total_inv_mass < f64::EPSILON { continue; }
+                        let normal = diff.normalize();
+                        // p1 は normal の向きに、p2 は逆向きに動かす
+                        let w1 = if supports.supports(p1, normal) { 0.0 } else { p1.inv_mass };
+                        let w2 = if supports.supports(p2, normal * -1.0) { 0.0 } else { p2.inv_mass };
+                        let total_inv_mass = w1 + w2;
+                        if total_inv_mass < Scalar::EPSILON { continue; }
 
-                        let correction = diff.normalize() * ((min_dist - dist) / total_inv_mass);
-                        p1.pos += correction * p1.inv_mass;
-                        p2.pos -= correction * p2.inv_mass;
+                        let depth = min_dist - dist;
+                        // `penetration_slop` の内側の接触も、補正量 0 の接触として記録する
+                        let overlap = self.config.penetration_correction(depth);
+                        if let Some(contacts) = contacts.as_deref_mut() {
+                            let point = p2.pos + normal * (p2.radius - depth * 0.5);
+                            contacts.record(i, j, point, normal, overlap / total_inv_mass);
+                        }
+                        if let (Some(&Some(a)), Some(&Some(b))) = (self.particle_owners.get(i), self.particle_owners.get(j)) {
+                            let pair = if a.0 < b.0 { (a, b) } else { (b, a) };
+                            if a != b && !body_contacts.contains(&pair) {
+                                body_contacts.push(pair);
+                            }
+                        }
+                        if overlap <= 0.0 { continue; }
+                        let correction = normal * (overlap / total_inv_mass);
+                        p1.pos += correction * w1;
+                        p2.pos -= correction * w2;
                     }
                 }
             }
         }
 
+        /// 衝突解決で質点を処理する順序を返します。
+        ///
+        /// ソートキーはパス開始時点の `pos.x`, `pos.y`, `prev_pos.x`, `prev_pos.y` の辞書式順序で、
+        /// 全て一致する場合のみ格納インデックスで比較します。
+        fn collision_order(&self) -> Vec<usize> {
+            let mut order: Vec<usize> = (0..self.particles.len()).collect();
+            order.sort_by(|&a, &b| {
+                let (pa, pb) = (&self.particles[a], &self.particles[b]);
+                pa.pos.x.total_cmp(&pb.pos.x)
+                    .then(pa.pos.y.total_cmp(&pb.pos.y))
+                    .then(pa.prev_pos.x.total_cmp(&pb.prev_pos.x))
+                    .then(pa.prev_pos.y.total_cmp(&pb.prev_pos.y))
+                    .then(a.cmp(&b))
+            });
+            order
+        }
+
         /// ワイヤーフレーム衝突解決ロジック
         fn solve_wire_collisions(&mut self) {
             let body_count = self.soft_bodies.len();
             for i in 0..body_count {
                 for j in 0..body_count {
-                    if i == j { continue; }
+                    if i == j || self.soft_bodies[i].is_sensor || self.soft_bodies[j].is_sensor { continue; }
 
                     if let Some(wires_j) = self.soft_bodies[j].outline_wires.clone() {
                         for &p_idx_i in &self.soft_bodies[i].particle_indices {
                             let p_i = self.particles[p_idx_i].clone(); // 借用規則のためクローン
 
                             // 粒子iに最も近いワイヤーをボディjから探す
-                            let mut min_dist_sq = f64::MAX;
+                            let mut min_dist_sq = Scalar::MAX;
                             let mut closest_wire_info = None;
 
                             for &(w1_idx, w2_idx) in &wires_j {
@@ -668,12 +4020,12 @@ pub mod core {
                                 if dist < p_i.radius {
                                     // 衝突応答: 位置の補正
                                     let penetration_depth = p_i.radius - dist;
-                                    let penetration_normal = if dist > f64::EPSILON { (p_i.pos - closest_point_on_wire).normalize() } else { Vec2::new(0.0, 1.0) };
+                                    let penetration_normal = if dist > Scalar::EPSILON { (p_i.pos - closest_point_on_wire).normalize() } else { Vec2::new(0.0, 1.0) };
                                     
                                     let p1_pos = self.particles[w1_idx].pos;
                                     let p2_pos = self.particles[w2_idx].pos;
                                     
-                                    let t = if (p2_pos - p1_pos).length_squared() < f64::EPSILON { 0.5 } else {
+                                    let t = if (p2_pos - p1_pos).length_squared() < Scalar::EPSILON { 0.5 } else {
                                         Vec2::dot(closest_point_on_wire - p1_pos, p2_pos - p1_pos) / (p2_pos - p1_pos).length_squared()
                                     }.clamp(0.0, 1.0);
 
@@ -681,7 +4033,7 @@ pub mod core {
                                     let w_p2_inv_mass = self.particles[w2_idx].inv_mass;
 
                                     let total_inv_mass = p_i.inv_mass + w_p1_inv_mass * (1.0 - t) + w_p2_inv_mass * t;
-                                    if total_inv_mass < f64::EPSILON { continue; }
+                                    if total_inv_mass < Scalar::EPSILON { continue; }
 
                                     let correction = penetration_normal * (penetration_depth / total_inv_mass);
                                     
@@ -696,16 +4048,99 @@ pub mod core {
             }
         }
 
+        /// 外周同士の衝突を解決します。
+        ///
+        /// 外周を持つ異なるボディの組ごとに、一方の外周の頂点がもう一方の外周の内側にあれば、
+        /// 最も近い辺の上の点まで押し戻します。補正は頂点と辺の両端に質量の逆数で配分し、
+        /// 辺の両端へは頂点の射影位置に応じて線形に分けます。支え（境界の壁・閉じ込め領域の辺・固定質点）に押し込まれる質点は動かしません。
+        fn solve_outline_collisions(&mut self) {
+            let supports = Supports::new(&self.config, &self.particles);
+            let particles = &mut self.particles;
+            for (i, body_i) in self.soft_bodies.iter().enumerate() {
+                if body_i.is_sensor { continue; }
+                let Some(wires_i) = &body_i.outline_wires else { continue };
+                for (j, body_j) in self.soft_bodies.iter().enumerate() {
+                    if i == j || body_j.is_sensor { continue; }
+                    let Some(wires_j) = &body_j.outline_wires else { continue };
+                    // 判定には組の処理を始めた時点の外周を使い、補正は現在の位置に適用する
+                    let polygon: Vec<Vec2> = wires_j.iter().map(|&(a, _)| particles[a].pos).collect();
+                    for &(v, _) in wires_i {
+                        let pos = particles[v].pos;
+                        if !geometry::point_in_polygon(pos, &polygon) { continue; }
+                        let Some((k, closest)) = geometry::find_nearest_segment(&polygon, pos) else { continue };
+                        let depth = (closest - pos).length();
+                        let overlap = self.config.penetration_correction(depth);
+                        if overlap <= 0.0 { continue; }
+                        // 頂点を外へ押し出す向き
+                        let normal = (closest - pos).normalize();
+
+                        let (e1, e2) = wires_j[k];
+                        let edge = particles[e2].pos - particles[e1].pos;
+                        let t = if edge.length_squared() < Scalar::EPSILON { 0.5 } else {
+                            Vec2::dot(closest - particles[e1].pos, edge) / edge.length_squared()
+                        }.clamp(0.0, 1.0);
+
+                        let weight = |p: &Particle, dir: Vec2| if supports.supports(p, dir) { 0.0 } else { p.inv_mass };
+                        let w_v = weight(&particles[v], normal);
+                        let w_1 = weight(&particles[e1], normal * -1.0) * (1.0 - t);
+                        let w_2 = weight(&particles[e2], normal * -1.0) * t;
+                        // 辺の両端の補正量は重み (1 - t), t を掛けて配分するため、拘束の有効質量には2乗で効く
+                        let total_inv_mass = w_v + w_1 * (1.0 - t) + w_2 * t;
+                        if total_inv_mass < Scalar::EPSILON { continue; }
+
+                        let correction = normal * (overlap / total_inv_mass);
+                        particles[v].pos += correction * w_v;
+                        particles[e1].pos -= correction * w_1;
+                        particles[e2].pos -= correction * w_2;
+                    }
+                }
+            }
+        }
+
         /// 境界条件を適用します。
-        fn apply_boundary_conditions(&mut self) {
+        ///
+        /// 境界の各辺を内向き法線を持つ平面拘束として扱います。
+        /// 補正前の位置から辺ごとの侵入量を求め、違反した全ての辺（角では2辺）の
+        /// 法線方向の補正を合算して一度に適用するため、辺の処理順序に依存しません。
+        /// 補正は法線方向のみなので、接線方向の運動はそのまま保たれます。
+        ///
+        /// `hits` が `Some` の場合、辺に侵入した質点と辺の組ごとに、ステップ開始時の速度から求めた
+        /// 衝突の速さを記録します。
+        fn apply_boundary_conditions(&mut self, mut hits: Option<&mut BTreeMap<(usize, Side), Scalar>>) {
             if let Some((min, max)) = self.config.bounds {
-                for p in &mut self.particles {
-                    p.pos.x = p.pos.x.max(min.x + p.radius).min(max.x - p.radius);
-                    p.pos.y = p.pos.y.max(min.y + p.radius).min(max.y - p.radius);
+                for (i, p) in self.particles.iter_mut().enumerate() {
+                    // (辺, 内向き法線, 辺上の点)
+                    let sides = [
+                        (Side::Left, Vec2::new(1.0, 0.0), min),
+                        (Side::Right, Vec2::new(-1.0, 0.0), max),
+                        (Side::Top, Vec2::new(0.0, 1.0), min),
+                        (Side::Bottom, Vec2::new(0.0, -1.0), max),
+                    ];
+                    let mut correction = Vec2::new(0.0, 0.0);
+                    for (side, normal, point) in sides {
+                        let penetration = p.radius - Vec2::dot(p.pos - point, normal);
+                        if penetration > 0.0 {
+                            correction += normal * self.config.penetration_correction(penetration);
+                            if let Some(hits) = hits.as_deref_mut() {
+                                // 拘束の反復中は速度を更新しないため、`p.vel` は接触した瞬間の速度です
+                                hits.entry((i, side)).or_insert((-Vec2::dot(p.vel, normal)).max(0.0));
+                            }
+                        }
+                    }
+                    p.pos += correction;
                 }
             }
         }
         
+        /// `SimulationConfig::containment` の領域の外に出た質点を押し戻します。固定質点は動かしません。
+        fn apply_containment(&mut self) {
+            let Some(region) = &self.config.containment else { return };
+            for p in &mut self.particles {
+                if p.is_fixed { continue; }
+                p.pos += region.correction(p.pos, p.radius);
+            }
+        }
+
         // --- 外部からシミュレーション状態を読み取るためのゲッター ---
         
         /// 全ての質点のスライスを返します。
@@ -713,33 +4148,438 @@ pub mod core {
             &self.particles
         }
         
+        /// 質点の数を返します。
+        pub fn particle_count(&self) -> usize {
+            self.particles.len()
+        }
+
+        /// ソフトボディの数を返します。
+        pub fn soft_body_count(&self) -> usize {
+            self.soft_bodies.len()
+        }
+
+        /// 各質点の直前のステップ開始時の位置 `prev_pos` と現在位置 `pos` を `alpha` で補間した位置を返します。
+        ///
+        /// 固定タイムステップで描画フレームとステップがずれる場合に、`alpha` に
+        /// 未消化の時間の割合を渡すことで滑らかに描画できます。
+        /// `alpha = 0.0` で `prev_pos`、`alpha = 1.0` で `pos` になります。
+        pub fn interpolated_positions(&self, alpha: Scalar) -> Vec<Vec2> {
+            self.particles.iter().map(|p| p.prev_pos + (p.pos - p.prev_pos) * alpha).collect()
+        }
+
         /// 全てのソフトボディのスライスを返します。
         pub fn soft_bodies(&self) -> &[SoftBody] {
             &self.soft_bodies
         }
-        
+
+        /// 全てのボディの外周ワイヤーの各辺を、`(ボディのハンドル, 始点, 終点)` の形で現在位置（ワールド座標）に変換して列挙します。
+        /// ボディの順に、各ボディ内では `outline_wires` の順です。外周を持たないボディは含みません。
+        pub fn iter_outline_edges(&self) -> impl Iterator<Item = (SoftBodyHandle, Vec2, Vec2)> + '_ {
+            self.soft_bodies.iter().flat_map(move |sb| {
+                sb.outline_wires.iter().flatten()
+                    .map(move |&(a, b)| (sb.handle, self.particles[a].pos, self.particles[b].pos))
+            })
+        }
+
+        /// シミュレーション内の全てのバネを、所属に関わらず列挙します。
+        pub fn springs(&self) -> impl Iterator<Item = &Spring> {
+            let connections = self.connections.iter().map(|(_, spring)| spring);
+            self.soft_bodies.iter().flat_map(|sb| sb.springs.iter()).chain(connections)
+        }
+
+        /// シミュレーション内の全てのバネを可変で列挙します。
+        pub fn springs_mut(&mut self) -> impl Iterator<Item = &mut Spring> {
+            let connections = self.connections.iter_mut().map(|(_, spring)| spring);
+            self.soft_bodies.iter_mut().flat_map(|sb| sb.springs.iter_mut()).chain(connections)
+        }
+
+        /// ハンドルに対応するソフトボディを返します。存在しない場合は `None` を返します。
+        pub fn soft_body(&self, handle: SoftBodyHandle) -> Option<&SoftBody> {
+            self.soft_bodies.iter().find(|sb| sb.handle == handle)
+        }
+
+        /// ボディの質点を `particle_indices` の順に列挙します。ボディが存在しない場合は何も返しません。
+        pub fn particles_of_body(&self, handle: SoftBodyHandle) -> impl Iterator<Item = &Particle> {
+            let indices = self.soft_body(handle).map_or(&[][..], |sb| &sb.particle_indices[..]);
+            indices.iter().map(|&i| &self.particles[i])
+        }
+
+        /// ボディの質点を可変で列挙します。ボディが存在しない場合は何も返しません。
+        ///
+        /// 質点の削除などで `particle_indices` が連続していなくても安全に借用できるよう、
+        /// インデックスを並べ替えて前から順に借用するため、順序は質点のインデックス順になります。
+        pub fn particles_of_body_mut(&mut self, handle: SoftBodyHandle) -> impl Iterator<Item = &mut Particle> {
+            let mut indices = self.soft_body(handle).map_or_else(Vec::new, |sb| sb.particle_indices.clone());
+            indices.sort_unstable();
+            indices.dedup();
+            let mut rest = self.particles.iter_mut();
+            let mut next = 0;
+            indices.into_iter().filter_map(move |i| {
+                // `i` より前の質点を読み飛ばす
+                let p = rest.nth(i - next);
+                next = i + 1;
+                p
+            })
+        }
+
+        /// 各質点につながるバネのひずみの最大値を、質点のインデックス順に返します。
+        ///
+        /// `connect` で追加したバネも含みます。固定質点とバネのつながっていない質点は `0.0` です。
+        /// 可視化で質点を応力に応じて色分けする用途を想定しています。
+        pub fn particle_stress(&self) -> Vec<Scalar> {
+            let mut stress = vec![0.0; self.particles.len()];
+            for spring in self.springs() {
+                let strain = spring.strain(&self.particles);
+                for i in [spring.p1_index, spring.p2_index] {
+                    stress[i] = Scalar::max(stress[i], strain);
+                }
+            }
+            for (s, p) in stress.iter_mut().zip(&self.particles) {
+                if p.is_fixed { *s = 0.0; }
+            }
+            stress
+        }
+
+        /// シミュレーションの状態を検査し、見つかった不整合を全て返します。
+        ///
+        /// 全質点の位置・速度が有限であること、全てのバネが範囲内の質点を参照し正の静止長を持つこと、
+        /// 形状維持拘束の質点のインデックスが範囲内であることを確認します。状態は変更しないため、
+        /// 長時間のシミュレーション中に定期的に呼び出してログに記録するといった用途に使えます。
+        pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+            let n = self.particles.len();
+            let mut errors = Vec::new();
+            for (index, p) in self.particles.iter().enumerate() {
+                if !(p.pos.x.is_finite() && p.pos.y.is_finite() && p.vel.x.is_finite() && p.vel.y.is_finite()) {
+                    errors.push(ValidationError::NonFiniteParticle { index });
+                }
+            }
+
+            let body_springs = self.soft_bodies.iter().flat_map(|sb| sb.springs.iter().enumerate().map(|(k, s)| (Some(sb.handle), k, s)));
+            let connections = self.connections.iter().enumerate().map(|(k, (_, s))| (None, k, s));
+            for (body, spring, s) in body_springs.chain(connections) {
+                if s.p1_index >= n || s.p2_index >= n {
+                    errors.push(ValidationError::SpringIndexOutOfBounds { body, spring });
+                }
+                if !(s.rest_length.is_finite() && s.rest_length > 0.0) {
+                    errors.push(ValidationError::InvalidRestLength { body, spring, rest_length: s.rest_length });
+                }
+            }
+
+            for sb in &self.soft_bodies {
+                if let Some(sc) = &sb.shape_constraint {
+                    for &index in sc.particle_indices.iter().filter(|&&i| i >= n) {
+                        errors.push(ValidationError::ShapeIndexOutOfBounds { body: sb.handle, index });
+                    }
+                }
+            }
+
+            if errors.is_empty() { Ok(()) } else { Err(errors) }
+        }
+
+        /// 全てのボディとシミュレーションが持つ拘束を集計した、シーンの規模を返します。
+        pub fn stats(&self) -> SimStats {
+            let mut stats = SimStats {
+                particle_count: self.particles.len(),
+                body_count: self.soft_bodies.len(),
+                spring_count: self.connections.len() + self.fixed_springs.len(),
+                ..Default::default()
+            };
+            for sb in &self.soft_bodies {
+                stats.spring_count += sb.springs.len();
+                stats.bend_constraint_count += sb.bend_constraints.len();
+                stats.angle_constraint_count += sb.angle_constraints.len();
+                stats.shape_constraint_count += usize::from(sb.shape_constraint.is_some());
+                stats.collider_count += sb.outline_wires.as_ref().map_or(0, Vec::len);
+            }
+            stats
+        }
+
+        /// 現在の状態を描画するための図形の一覧を返します。
+        ///
+        /// 質点は円、バネと外周ワイヤーの辺は線分になります。ボディのバウンディングボックスは
+        /// 質点の半径を含めて計算します。
+        pub fn debug_draw(&self) -> DebugDrawData {
+            let circles = self.particles.iter()
+                .map(|p| Circle {
+                    center: p.pos,
+                    radius: p.radius,
+                    kind: if p.is_fixed { CircleKind::Fixed } else { CircleKind::Free },
+                })
+                .collect();
+            let springs = self.springs().map(|s| LineSeg {
+                a: self.particles[s.p1_index].pos,
+                b: self.particles[s.p2_index].pos,
+                color_hint: LineHint::Spring { stretch: s.stretch_ratio(&self.particles) },
+            });
+            let outlines = self.iter_outline_edges().map(|(_, a, b)| LineSeg { a, b, color_hint: LineHint::Outline });
+            let lines = springs.chain(outlines).collect();
+
+            let bounds = self.config.bounds.map(|(min, max)| Aabb { min, max, body: None });
+            let bodies = self.soft_bodies.iter().filter_map(|sb| {
+                let (min, max) = self.body_aabb(sb)?;
+                Some(Aabb { min, max, body: Some(sb.handle) })
+            });
+            let aabbs = bounds.into_iter().chain(bodies).collect();
+
+            DebugDrawData { circles, lines, aabbs }
+        }
+
+        /// 固定されていない全質点の質量の合計を返します。
+        pub fn total_mass(&self) -> Scalar {
+            self.particles.iter().filter_map(Particle::finite_mass).sum()
+        }
+
+        /// 固定されていない全質点の重心を返します。該当する質点が無い場合は原点を返します。
+        pub fn center_of_mass(&self) -> Vec2 {
+            let total_mass = self.total_mass();
+            if total_mass < Scalar::EPSILON {
+                return Vec2::new(0.0, 0.0);
+            }
+            let weighted = self.particles.iter().fold(Vec2::new(0.0, 0.0), |acc, p| match p.finite_mass() {
+                Some(m) => acc + p.pos * m,
+                None => acc,
+            });
+            weighted * (1.0 / total_mass)
+        }
+
+        /// 全質点の運動量の合計を返します。固定質点は含みません。
+        /// 外力（重力・境界・固定質点）の無い閉じた系では、ステップの前後で保存されます。
+        pub fn total_linear_momentum(&self) -> Vec2 {
+            self.particles.iter().fold(Vec2::new(0.0, 0.0), |acc, p| acc + p.momentum())
+        }
+
+        /// ボディの固定されていない質点の、その重心まわりの角運動量 `Σ m (r × v)` を返します。
+        /// 正の値は `Vec2::perpendicular` の向きの回転です。ボディが存在しない場合や、
+        /// 固定されていない質点が無い場合は `None` を返します。
+        pub fn body_angular_momentum(&self, handle: SoftBodyHandle) -> Option<Scalar> {
+            let body_idx = self.body_index(handle)?;
+            let (center, _) = self.body_mass_frame(body_idx)?;
+            Some(self.soft_bodies[body_idx].particle_indices.iter()
+                .map(|&i| &self.particles[i])
+                .map(|p| Vec2::cross(p.pos - center, p.momentum()))
+                .sum())
+        }
+
+        /// 位置が軸平行な矩形 `[min, max]`（境界を含む）の内側にある質点のインデックスを昇順に返します。
+        /// `min` と `max` は成分ごとに並べ替えて扱うため、ドラッグの向きに依存しません。
+        pub fn query_aabb(&self, min: Vec2, max: Vec2) -> Vec<usize> {
+            let (lo, hi) = (Vec2::min(min, max), Vec2::max(min, max));
+            self.particles
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| (lo.x..=hi.x).contains(&p.pos.x) && (lo.y..=hi.y).contains(&p.pos.y))
+                .map(|(i, _)| i)
+                .collect()
+        }
+
+        /// `origin` から `dir` の向きに距離 `max_dist` まで伸ばした半直線と最初に交わる質点を返します。
+        /// 交わる質点が無い場合は `None` を返します。`raycast_all` の最初の要素です。
+        pub fn raycast(&self, origin: Vec2, dir: Vec2, max_dist: Scalar) -> Option<RaycastHit> {
+            self.raycast_all(origin, dir, max_dist).into_iter().next()
+        }
+
+        /// `origin` から `dir` の向きに距離 `max_dist` まで伸ばした半直線と交わる全ての質点の円を、近い順に返します。
+        ///
+        /// `dir` は正規化して使い、長さが `0` の場合は空の `Vec` を返します。`origin` を内側に含む質点は
+        /// 距離 `0` の交差として扱います。距離が等しい場合は質点のインデックスの昇順です。
+        pub fn raycast_all(&self, origin: Vec2, dir: Vec2, max_dist: Scalar) -> Vec<RaycastHit> {
+            let dir = dir.normalize();
+            if dir == Vec2::new(0.0, 0.0) {
+                return Vec::new();
+            }
+            let mut hits: Vec<RaycastHit> = self.particles.iter().enumerate().filter_map(|(i, p)| {
+                // |origin + dir * t - pos| = radius の解 t
+                let offset = origin - p.pos;
+                let b = Vec2::dot(offset, dir);
+                let c = offset.length_squared() - p.radius * p.radius;
+                let discriminant = b * b - c;
+                if discriminant < 0.0 { return None; }
+                let root = discriminant.sqrt();
+                if -b + root < 0.0 { return None; }
+                let distance = (-b - root).max(0.0);
+                if distance > max_dist { return None; }
+                let point = origin + dir * distance;
+                let normal = if c <= 0.0 { dir * -1.0 } else { (point - p.pos).normalize() };
+                Some(RaycastHit { particle: i, distance, point, normal })
+            }).collect();
+            hits.sort_by(|a, b| a.distance.total_cmp(&b.distance).then(a.particle.cmp(&b.particle)));
+            hits
+        }
+
+        /// 軸平行な矩形 `[min, max]` と交わるボディのハンドルを、ボディの追加順に返します。
+        /// 各ボディは質点の半径を含めたバウンディングボックスで判定します。`min` と `max` は成分ごとに並べ替えて扱います。
+        pub fn bodies_in_aabb(&self, min: Vec2, max: Vec2) -> Vec<SoftBodyHandle> {
+            let (lo, hi) = (Vec2::min(min, max), Vec2::max(min, max));
+            self.bodies_where(|(body_min, body_max)| {
+                body_min.x <= hi.x && lo.x <= body_max.x && body_min.y <= hi.y && lo.y <= body_max.y
+            })
+        }
+
+        /// 中心 `center`・半径 `radius` の円と交わるボディのハンドルを、ボディの追加順に返します。
+        /// 各ボディは質点の半径を含めたバウンディングボックスで判定するため、凹んだボディでは
+        /// 実際の質点が円から外れていても選ばれることがあります。
+        pub fn bodies_in_circle(&self, center: Vec2, radius: Scalar) -> Vec<SoftBodyHandle> {
+            self.bodies_where(|(body_min, body_max)| {
+                let closest = Vec2::max(body_min, Vec2::min(center, body_max));
+                (closest - center).length_squared() <= radius * radius
+            })
+        }
+
+        /// バウンディングボックスが `hit` を満たすボディのハンドルを集めます。
+        fn bodies_where(&self, hit: impl Fn((Vec2, Vec2)) -> bool) -> Vec<SoftBodyHandle> {
+            self.soft_bodies.iter()
+                .filter(|sb| self.body_aabb(sb).is_some_and(&hit))
+                .map(|sb| sb.handle)
+                .collect()
+        }
+
+        /// ボディの質点を半径を含めて囲むバウンディングボックスを返します。質点が無い場合は `None` です。
+        fn body_aabb(&self, sb: &SoftBody) -> Option<(Vec2, Vec2)> {
+            sb.particle_indices.iter().map(|&i| &self.particles[i]).fold(None, |acc, p| {
+                let r = Vec2::new(p.radius, p.radius);
+                let (lo, hi) = (p.pos - r, p.pos + r);
+                Some(match acc {
+                    Some((min, max)) => (Vec2::min(min, lo), Vec2::max(max, hi)),
+                    None => (lo, hi),
+                })
+            })
+        }
+
+        /// 全質点を囲む軸平行バウンディングボックス `(min, max)` を返します。
+        /// 各質点の半径を含めて計算します。質点が無い場合は `None` を返します。
+        pub fn bounding_box(&self) -> Option<(Vec2, Vec2)> {
+            let first = self.particles.first()?;
+            let r = Vec2::new(first.radius, first.radius);
+            let init = (first.pos - r, first.pos + r);
+            Some(self.particles.iter().fold(init, |(min, max), p| {
+                let r = Vec2::new(p.radius, p.radius);
+                (Vec2::min(min, p.pos - r), Vec2::max(max, p.pos + r))
+            }))
+        }
+
+        /// これまでに進めたステップ数を返します。`dt` が無効で何もしなかった呼び出しは数えません。
+        pub fn step_count(&self) -> usize {
+            self.step_count
+        }
+
         /// シミュレーション設定への参照を返します。
         pub fn config(&self) -> &SimulationConfig {
             &self.config
         }
         
         /// シミュレーション設定を可変で取得します。
+        ///
+        /// 値の検証は行われないため、`set_gravity` などの個別のセッターの使用を推奨します。
         pub fn config_mut(&mut self) -> &mut SimulationConfig {
             &mut self.config
         }
+
+        /// シミュレーション設定を `config` で置き換えます。実行中の調整用で、次のステップから反映されます。
+        ///
+        /// 個別のセッターと同じ検証を行います（`solver_iterations` の `0` は `1` に切り上げ、
+        /// `bounds` は成分ごとに並べ替え）。`seed` が変わった場合は、乱数生成器をそのシードで
+        /// 初期化し直すため、以降の乱数は `Simulation::new(config)` で作った場合と同じになります。
+        /// 反復回数などから導かれる値は毎ステップ設定から計算されるため、他に作り直す状態はありません。
+        pub fn apply_config(&mut self, config: SimulationConfig) {
+            let reseed = config.seed != self.config.seed;
+            self.config = config;
+            self.set_solver_iterations(self.config.solver_iterations);
+            self.set_bounds(self.config.bounds);
+            if reseed {
+                self.rng = Rng::new(self.config.seed);
+            }
+        }
+
+        /// 重力を設定します。次のステップから反映されます。
+        pub fn set_gravity(&mut self, gravity: Vec2) {
+            self.config.gravity = gravity;
+        }
+
+        /// ソルバーの反復回数を設定します。`0` は `1` に切り上げられます。
+        pub fn set_solver_iterations(&mut self, iterations: usize) {
+            self.config.solver_iterations = iterations.max(1);
+        }
+
+        /// シーンの硬さと大きさから見積もった、ソルバーの反復回数の目安を返します。
+        ///
+        /// 硬い拘束の補正がボディ全体に行き渡るには、ボディの差し渡しの質点数（格子状のボディでは
+        /// 質点数の平方根）に比例した反復が必要です。そのため
+        /// `ceil(2 * 最大の剛性 * sqrt(最大のボディの質点数))` を `4..=64` に収めた値を返します。
+        /// 剛性には `stiffness_multiplier` を掛けた解決時の値を使います。
+        /// 助言のための値で、`SimulationConfig::auto_iterations` が `true` の場合のみ `step` で使われます。
+        pub fn recommended_iterations(&self) -> usize {
+            const MIN_ITERATIONS: usize = 4;
+            const MAX_ITERATIONS: usize = 64;
+            let body_stiffness = self.soft_bodies.iter().flat_map(|sb| {
+                sb.springs.iter().map(|s| s.stiffness)
+                    .chain(sb.bend_constraints.iter().map(|b| b.stiffness))
+                    .chain(sb.angle_constraints.iter().map(|a| a.stiffness))
+                    .chain(sb.shape_constraint.iter().map(|sc| sc.stiffness))
+            });
+            let max_stiffness = body_stiffness
+                .chain(self.connections.iter().map(|(_, s)| s.stiffness))
+                .chain(self.fixed_springs.iter().map(|(_, s)| s.stiffness))
+                .map(|k| self.config.effective_stiffness(k))
+                .fold(0.0, Scalar::max);
+            let largest_body = self.soft_bodies.iter().map(|sb| sb.particle_indices.len()).max().unwrap_or(0);
+            let estimate = (2.0 * max_stiffness * (largest_body as Scalar).sqrt()).ceil();
+            if estimate.is_finite() {
+                (estimate as usize).clamp(MIN_ITERATIONS, MAX_ITERATIONS)
+            } else {
+                MAX_ITERATIONS
+            }
+        }
+
+        /// 境界を設定します。`min` と `max` は成分ごとに並べ替えて保持します。
+        pub fn set_bounds(&mut self, bounds: Option<(Vec2, Vec2)>) {
+            self.config.bounds = bounds.map(|(a, b)| (Vec2::min(a, b), Vec2::max(a, b)));
+        }
+
+        /// 質点を多角形 `polygon` の内側に閉じ込めます。頂点が3つ未満の場合は領域を解除します。
+        pub fn set_containment(&mut self, polygon: Vec<Vec2>) {
+            self.config.containment = ContainmentRegion::new(polygon);
+        }
+
+        /// 多角形の閉じ込め領域を解除します。
+        pub fn clear_containment(&mut self) {
+            self.config.containment = None;
+        }
     }
 
     /// ジオメトリ演算ヘルパーモジュール
-    mod geometry {
-        use super::{Vec2};
+    pub mod geometry {
+        use super::{Line, Scalar, Vec2};
+        use alloc::vec::Vec;
+
+        /// 2つの点を同じ位置とみなす距離。座標のスケール（ピクセル単位）に対して十分小さく、
+        /// 丸め誤差で生じるずれより大きい値です。
+        pub const COINCIDENT_EPSILON: Scalar = 1e-9;
+
+        /// 距離が `COINCIDENT_EPSILON` 以下の点の組（隣接していなくても）を含むかを返します。
+        pub fn has_coincident_points(points: &[Vec2]) -> bool {
+            points.iter().enumerate().any(|(i, &a)| {
+                points[i + 1..].iter().any(|&b| (a - b).length() <= COINCIDENT_EPSILON)
+            })
+        }
+
+        /// 閉じたリングとして隣り合う要素のペア `(items[i], items[i + 1])` を列挙します。
+        /// 最後のペアは `(items[n - 1], items[0])` です。
+        pub fn circular_windows<T: Copy>(items: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+            let n = items.len();
+            (0..n).map(move |i| (items[i], items[(i + 1) % n]))
+        }
+
+        /// 閉じたリングとして連続する `n` 個の要素の組を列挙します。
+        /// `i` 番目の組は `items[i], items[i + 1], ..., items[i + n - 1]` （添字は要素数で剰余）です。
+        pub fn circular_windows_n<T: Copy>(items: &[T], n: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+            let len = items.len();
+            (0..len).map(move |i| (0..n).map(|k| items[(i + k) % len]).collect())
+        }
 
         /// 線分ABと線分CDの交差判定 (端点での接触は交差とみなさない)
         fn segments_intersect(p1: Vec2, p2: Vec2, p3: Vec2, p4: Vec2) -> bool {
-            let d1 = Vec2::cross(p4 - p3, p1 - p3);
-            let d2 = Vec2::cross(p4 - p3, p2 - p3);
-            let d3 = Vec2::cross(p2 - p1, p3 - p1);
-            let d4 = Vec2::cross(p2 - p1, p4 - p1);
-            ((d1 * d2) < 0.0) && ((d3 * d4) < 0.0)
+            let interior = |x: Scalar| 0.0 < x && x < 1.0;
+            segment_intersection_params(&Line::new(p1, p2), &Line::new(p3, p4)).is_some_and(|(t, u)| interior(t) && interior(u))
         }
 
         /// 凸多角形の自己交差チェック
@@ -759,12 +4599,159 @@ pub mod core {
             false
         }
         
+        /// 2つの線分の交差パラメータ `(t, u)` を返します。
+        /// 交点は `a.start + (a.end - a.start) * t` および `b.start + (b.end - b.start) * u` で、
+        /// `t`, `u` はともに `[0, 1]` に収まります。平行または交差しない場合は `None` を返します。
+        pub fn segment_intersection_params(a: &Line, b: &Line) -> Option<(Scalar, Scalar)> {
+            let r = a.end - a.start;
+            let s = b.end - b.start;
+            let denom = Vec2::cross(r, s);
+            if denom.abs() < Scalar::EPSILON { return None; }
+            let qp = b.start - a.start;
+            let t = Vec2::cross(qp, s) / denom;
+            let u = Vec2::cross(qp, r) / denom;
+            if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) { Some((t, u)) } else { None }
+        }
+
+        /// 2つの線分の交点を返します。端点での接触も交差とみなします。
+        /// 平行または交差しない場合は `None` を返します。
+        pub fn segment_intersection(a: &Line, b: &Line) -> Option<Vec2> {
+            segment_intersection_params(a, b).map(|(t, _)| a.point_at(t))
+        }
+
+        /// 多角形の面積を返します（靴紐公式）。頂点の巡回方向には依存しません。
+        pub fn polygon_area(points: &[Vec2]) -> Scalar {
+            let twice_signed: Scalar = circular_windows(points).map(|(a, b)| Vec2::cross(a, b)).sum();
+            twice_signed.abs() * 0.5
+        }
+
+        /// 閉じた多角形の周長（各辺の長さの合計）を返します。
+        pub fn polygon_perimeter(points: &[Vec2]) -> Scalar {
+            circular_windows(points).map(|(a, b)| (b - a).length()).sum()
+        }
+
+        /// 点 `point` から +x 方向に伸ばした半直線が辺 `ab` と交わるかを判定します。
+        /// 交差数の偶奇による内外判定に使用します。
+        pub(super) fn ray_crosses_edge(point: Vec2, a: Vec2, b: Vec2) -> bool {
+            if (a.y > point.y) == (b.y > point.y) {
+                return false;
+            }
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            point.x < x
+        }
+
+        /// 点 `point` が多角形 `polygon` の内側にあるかを偶奇規則で判定します。
+        /// 頂点の巡回方向には依存しません。
+        pub fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+            circular_windows(polygon).filter(|&(a, b)| ray_crosses_edge(point, a, b)).count() % 2 == 1
+        }
+
+        /// 線分 `line` と閉じた多角形 `polygon` の各辺との交点を、辺の順に全て返します。
+        pub fn find_all_intersections(polygon: &[Vec2], line: &Line) -> Vec<Vec2> {
+            circular_windows(polygon)
+                .filter_map(|(a, b)| segment_intersection(&Line::new(a, b), line))
+                .collect()
+        }
+
+        /// 閉じた多角形 `polygon` の辺のうち点 `point` に最も近いものについて、
+        /// 辺の番号 `i`（辺 `polygon[i]` → `polygon[i + 1]`）と辺上の最近接点を返します。
+        /// 多角形が空の場合は `None` を返します。
+        pub fn find_nearest_segment(polygon: &[Vec2], point: Vec2) -> Option<(usize, Vec2)> {
+            circular_windows(polygon)
+                .map(|(a, b)| dist_sq_to_segment(point, a, b))
+                .enumerate()
+                .min_by(|(_, x), (_, y)| x.0.total_cmp(&y.0))
+                .map(|(i, (_, closest))| (i, closest))
+        }
+
+        /// 点の集合の凸包を反時計回り（y 軸上向きの座標系で）に返します（Andrew のモノトーンチェーン法）。
+        /// 同一直線上の点は含みません。
+        pub fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+            let mut sorted = points.to_vec();
+            sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+            sorted.dedup();
+            if sorted.len() < 3 {
+                return sorted;
+            }
+            let mut hull: Vec<Vec2> = Vec::with_capacity(sorted.len() * 2);
+            for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+                let start = hull.len();
+                for p in pass {
+                    while hull.len() >= start + 2 && Vec2::cross(hull[hull.len() - 1] - hull[hull.len() - 2], p - hull[hull.len() - 2]) <= 0.0 {
+                        hull.pop();
+                    }
+                    hull.push(p);
+                }
+                // 各パスの終点は次のパスの始点と重複する
+                hull.pop();
+            }
+            hull
+        }
+
+        /// 2つの凸多角形が重なっている場合、分離軸定理による貫通深さ（最小の重なり幅）を返します。
+        /// 重なっていない、または接しているだけの場合は `None` を返します。
+        pub fn convex_penetration_depth(a: &[Vec2], b: &[Vec2]) -> Option<Scalar> {
+            if a.len() < 3 || b.len() < 3 {
+                return None;
+            }
+            let project = |polygon: &[Vec2], axis: Vec2| {
+                polygon.iter().map(|&p| Vec2::dot(p, axis)).fold((Scalar::INFINITY, Scalar::NEG_INFINITY), |(lo, hi), d| (lo.min(d), hi.max(d)))
+            };
+            let mut depth = Scalar::INFINITY;
+            for (p, q) in circular_windows(a).chain(circular_windows(b)) {
+                let axis = (q - p).perpendicular().normalize();
+                if axis == Vec2::new(0.0, 0.0) {
+                    continue;
+                }
+                let (a_min, a_max) = project(a, axis);
+                let (b_min, b_max) = project(b, axis);
+                let overlap = a_max.min(b_max) - a_min.max(b_min);
+                if overlap <= 0.0 {
+                    return None;
+                }
+                depth = depth.min(overlap);
+            }
+            Some(depth)
+        }
+
+        /// 多角形 `polygon` を軸平行な矩形 `[min, max]` で切り抜いた多角形を返します（Sutherland–Hodgman 法）。
+        ///
+        /// 頂点の巡回方向は元の多角形のまま保たれます。`min` と `max` は成分ごとに並べ替えて扱います。
+        /// 矩形に完全に含まれる多角形はそのまま、矩形と重ならない多角形は空の `Vec` になります。
+        /// 凹多角形では、矩形の辺に沿った面積0の部分が残ることがあります。
+        pub fn clip_polygon_to_rect(polygon: &[Vec2], min: Vec2, max: Vec2) -> Vec<Vec2> {
+            let (lo, hi) = (Vec2::min(min, max), Vec2::max(min, max));
+            // 各半平面を (x 軸か, 境界値, 内側が境界値以上か) で表す
+            let planes = [(true, lo.x, true), (true, hi.x, false), (false, lo.y, true), (false, hi.y, false)];
+            let mut output = polygon.to_vec();
+            for (is_x, bound, keep_above) in planes {
+                let axis = |v: Vec2| if is_x { v.x } else { v.y };
+                let inside = |v: Vec2| if keep_above { axis(v) >= bound } else { axis(v) <= bound };
+                let input = core::mem::take(&mut output);
+                let n = input.len();
+                // 辺 a → b の終点 b を順に出力するため、最後の頂点からの辺で始めて元の並びを保つ
+                for (a, b) in (0..n).map(|i| (input[(i + n - 1) % n], input[i])) {
+                    let crossing = || a + (b - a) * ((bound - axis(a)) / (axis(b) - axis(a)));
+                    match (inside(a), inside(b)) {
+                        (true, true) => output.push(b),
+                        (true, false) => output.push(crossing()),
+                        (false, true) => {
+                            output.push(crossing());
+                            output.push(b);
+                        }
+                        (false, false) => {}
+                    }
+                }
+            }
+            output
+        }
+
         /// 点と線分の距離の2乗と、線分上の最近接点を返す
-        pub fn dist_sq_to_segment(p: Vec2, a: Vec2, b: Vec2) -> (f64, Vec2) {
+        pub fn dist_sq_to_segment(p: Vec2, a: Vec2, b: Vec2) -> (Scalar, Vec2) {
             let ab = b - a;
             let ap = p - a;
             let len_sq = ab.length_squared();
-            if len_sq < f64::EPSILON { return ((p - a).length_squared(), a); }
+            if len_sq < Scalar::EPSILON { return ((p - a).length_squared(), a); }
             let t = (Vec2::dot(ap, ab) / len_sq).clamp(0.0, 1.0);
             let closest_point = a + ab * t;
             ((p - closest_point).length_squared(), closest_point)
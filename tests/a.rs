@@ -6,7 +6,7 @@ use macroquad::text::draw_text;
 use macroquad::time::{get_fps, get_frame_time};
 use macroquad::window::{clear_background, next_frame, screen_height, screen_width, Conf};
 
-use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
 
 
 /// 描画色を定義
@@ -26,7 +26,7 @@ async fn test00() {
     
     // ウィンドウサイズに基づいて境界を設定
     let sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 600.0),
         solver_iterations: 8,
         ..Default::default()
@@ -36,7 +36,7 @@ async fn test00() {
 
     // 落下するボディ
     let falling_body = SoftBodyConfig {
-        center: Vec2::new(screen_width() as f64 * 0.5, screen_height() as f64 * 0.25),
+        center: Vec2::new(screen_width() as Scalar * 0.5, screen_height() as Scalar * 0.25),
         size: Vec2::new(120.0, 120.0),
         rows: 8,
         cols: 8,
@@ -47,8 +47,8 @@ async fn test00() {
 
     // 地面として機能するボディ
     let ground_body = SoftBodyConfig {
-        center: Vec2::new(screen_width() as f64 * 0.5, screen_height() as f64 * 0.8),
-        size: Vec2::new(screen_width() as f64 * 0.6, 60.0),
+        center: Vec2::new(screen_width() as Scalar * 0.5, screen_height() as Scalar * 0.8),
+        size: Vec2::new(screen_width() as Scalar * 0.6, 60.0),
         rows: 4,
         cols: 20,
         stiffness: 0.3,
@@ -58,7 +58,7 @@ async fn test00() {
     
     // 固定されたアンカーボディ
     let fixed_anchor = SoftBodyConfig {
-        center: Vec2::new(screen_width() as f64 * 0.8, screen_height() as f64 * 0.2),
+        center: Vec2::new(screen_width() as Scalar * 0.8, screen_height() as Scalar * 0.2),
         size: Vec2::new(50.0, 50.0),
         rows: 3,
         cols: 3,
@@ -68,9 +68,9 @@ async fn test00() {
         ..Default::default()
     };
 
-    sim.add_soft_body(&falling_body);
-    sim.add_soft_body(&ground_body);
-    sim.add_soft_body(&fixed_anchor);
+    sim.add_soft_body(&falling_body).unwrap();
+    sim.add_soft_body(&ground_body).unwrap();
+    sim.add_soft_body(&fixed_anchor).unwrap();
 
     // マウスドラッグ用の状態変数
     let mut dragged_particle_index: Option<usize> = None;
@@ -80,7 +80,7 @@ async fn test00() {
     loop {
         // --- 2a. 入力処理 (マウスドラッグ) ---
         let (mx, my) = mouse_position();
-        let mouse_pos = Vec2::new(mx as f64, my as f64);
+        let mouse_pos = Vec2::new(mx as Scalar, my as Scalar);
 
         if is_mouse_button_pressed(MouseButton::Left) {
             let mut closest_dist_sq = 400.0; // 20pxの半径内
@@ -104,17 +104,15 @@ async fn test00() {
         // ドラッグ中の質点の位置を更新
         if let Some(idx) = dragged_particle_index {
             if let Some(p) = sim.particles.get_mut(idx) {
-                p.pos = mouse_pos;
                 // 速度と前の位置をリセットして、不自然な飛び出しを防ぐ
-                p.vel = Vec2::new(0.0, 0.0);
-                p.prev_pos = mouse_pos;
+                p.teleport(mouse_pos);
             }
         }
         
         // --- 2b. 物理演算の更新 ---
         // 可変フレームレートに対応するため、get_frame_time() を使用
         // より安定したシミュレーションには固定タイムステップの導入を検討
-        let dt = get_frame_time() as f64;
+        let dt = get_frame_time() as Scalar;
         sim.step(dt);
 
 
@@ -127,11 +125,8 @@ async fn test00() {
                 let p1 = &sim.particles[spring.p1_index];
                 let p2 = &sim.particles[spring.p2_index];
                 
-                let dist = (p1.pos - p2.pos).length();
-                let stretch = (dist - spring.rest_length).abs() / spring.rest_length;
-                
                 // 伸び率に応じて色を線形補間
-                let intensity = (stretch * 3.0).min(1.0) as f32;
+                let intensity = spring.stretch_ratio(&sim.particles) as f32;
                 let color = Color {
                     r: SPRING_BASE_COLOR.r * (1.0 - intensity) + SPRING_STRETCH_COLOR.r * intensity,
                     g: SPRING_BASE_COLOR.g * (1.0 - intensity) + SPRING_STRETCH_COLOR.g * intensity,
@@ -167,12 +162,13 @@ async fn test01()
 {
      // --- 1. シミュレーションの初期設定 ---
     let sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 800.0),
         solver_iterations: 4, // オブジェクトが多いので少し減らす
         damping: 0.99,
         use_wire_collisions: false,
-        use_volumetric_collisions:true
+        use_volumetric_collisions:true,
+        ..Default::default()
     };
 
     let mut sim = Simulation::new(sim_config);
@@ -187,8 +183,8 @@ async fn test01()
 
     for i in 0..grid_rows {
         for j in 0..grid_cols {
-            let x = start_x as f64 + j as f64 * spacing as f64;
-            let y = start_y as f64 + i as f64 * spacing as f64;
+            let x = start_x as Scalar + j as Scalar * spacing as Scalar;
+            let y = start_y as Scalar + i as Scalar * spacing as Scalar;
 
             let cube_config = SoftBodyConfig {
                 center: Vec2::new(x, y),
@@ -200,7 +196,7 @@ async fn test01()
                 particle_radius: 5.0, // 質点を小さくする
                 ..Default::default()
             };
-            sim.add_soft_body(&cube_config);
+            sim.add_soft_body(&cube_config).unwrap();
         }
     }
 
@@ -211,7 +207,7 @@ async fn test01()
     loop {
         // --- 2a. 入力処理 (マウスドラッグ) ---
         let (mx, my) = mouse_position();
-        let mouse_pos = Vec2::new(mx as f64, my as f64);
+        let mouse_pos = Vec2::new(mx as Scalar, my as Scalar);
 
         if is_mouse_button_pressed(MouseButton::Left) {
             let mut closest_dist_sq = 400.0; // 20pxの半径内
@@ -234,14 +230,12 @@ async fn test01()
 
         if let Some(idx) = dragged_particle_index {
             if let Some(p) = sim.particles.get_mut(idx) {
-                p.pos = mouse_pos;
-                p.vel = Vec2::new(0.0, 0.0);
-                p.prev_pos = mouse_pos;
+                p.teleport(mouse_pos);
             }
         }
 
         // --- 2b. 物理演算の更新 ---
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0); // フレームレート低下時の爆発を防ぐ
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0); // フレームレート低下時の爆発を防ぐ
         sim.step(dt);
 
 
@@ -288,12 +282,13 @@ async fn test02() {
     let initial_gravity = Vec2::new(0.0, 0.0); // ★ 最初は無重力
 
     let sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: initial_gravity,
         solver_iterations: 6,
         damping: 0.99,
         use_wire_collisions: false,
-        use_volumetric_collisions:true
+        use_volumetric_collisions:true,
+        ..Default::default()
     };
     
     let mut sim = Simulation::new(sim_config);
@@ -307,22 +302,22 @@ async fn test02() {
     let start_y = (screen_height() - (grid_rows - 1) as f32 * spacing) / 2.0;
     for i in 0..grid_rows {
         for j in 0..grid_cols {
-            let x = start_x as f64 + j as f64 * spacing as f64;
-            let y = start_y as f64 + i as f64 * spacing as f64;
+            let x = start_x as Scalar + j as Scalar * spacing as Scalar;
+            let y = start_y as Scalar + i as Scalar * spacing as Scalar;
             let cube_config = SoftBodyConfig {
                 center: Vec2::new(x, y),
                 size: Vec2::new(cube_size, cube_size),
                 rows: 5, cols: 5, stiffness: 0.6, shape_stiffness: 0.5, particle_radius: 5.0,
                 ..Default::default()
             };
-            sim.add_soft_body(&cube_config);
+            sim.add_soft_body(&cube_config).unwrap();
         }
     }
     
     let mut dragged_particle_index: Option<usize> = None;
 
     // ★ 1. つまみUIの状態変数を定義
-    let knob_base_pos = Vec2::new(screen_width() as f64 - 100.0, screen_height() as f64 - 100.0);
+    let knob_base_pos = Vec2::new(screen_width() as Scalar - 100.0, screen_height() as Scalar - 100.0);
     let knob_radius = 60.0;
     let handle_radius = 25.0;
     let mut knob_handle_pos = knob_base_pos; // ハンドルの初期位置は中心
@@ -332,7 +327,7 @@ async fn test02() {
 
     loop {
         let (mx, my) = mouse_position();
-        let mouse_pos = Vec2::new(mx as f64, my as f64);
+        let mouse_pos = Vec2::new(mx as Scalar, my as Scalar);
 
         // ★ 2. つまみUIの入力処理
         // マウスが押された瞬間
@@ -391,13 +386,11 @@ async fn test02() {
 
         if let Some(idx) = dragged_particle_index {
              if let Some(p) = sim.particles.get_mut(idx) {
-                p.pos = mouse_pos;
-                p.vel = Vec2::new(0.0, 0.0);
-                p.prev_pos = mouse_pos;
+                p.teleport(mouse_pos);
             }
         }
         
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0);
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0);
         sim.step(dt);
 
         clear_background(BACKGROUND_COLOR);
@@ -406,8 +399,7 @@ async fn test02() {
         for sb in sim.soft_bodies() {
             for spring in &sb.springs {
                 let p1 = &sim.particles[spring.p1_index]; let p2 = &sim.particles[spring.p2_index];
-                let dist = (p1.pos - p2.pos).length(); let stretch = (dist - spring.rest_length).abs() / spring.rest_length;
-                let intensity = (stretch * 3.0).min(1.0) as f32;
+                let intensity = spring.stretch_ratio(&sim.particles) as f32;
                 let color = Color { r: SPRING_BASE_COLOR.r * (1.0 - intensity) + SPRING_STRETCH_COLOR.r * intensity, g: SPRING_BASE_COLOR.g * (1.0 - intensity) + SPRING_STRETCH_COLOR.g * intensity, b: SPRING_BASE_COLOR.b * (1.0 - intensity) + SPRING_STRETCH_COLOR.b * intensity, a: 0.8, };
                 draw_line(p1.pos.x as f32, p1.pos.y as f32, p2.pos.x as f32, p2.pos.y as f32, 1.5, color);
             }
@@ -445,7 +437,7 @@ async fn test03() {
         }
 
         // (入力処理、ステップ更新は以前のコードと同様)
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0);
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0);
         sim.step(dt);
 
         clear_background(Color::from_rgba(20, 20, 30, 255));
@@ -484,18 +476,15 @@ async fn test04() {
     loop {
         if is_key_pressed(KeyCode::R) { sim = create_simulation2(); }
 
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0);
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0);
         sim.step(dt);
 
         clear_background(Color::from_rgba(20, 20, 30, 255));
 
+        for (_, p1, p2) in sim.iter_outline_edges() {
+            draw_line(p1.x as f32, p1.y as f32, p2.x as f32, p2.y as f32, 2.5, WHITE);
+        }
         for sb in sim.soft_bodies() {
-            if let Some(wires) = &sb.outline_wires {
-                for &(p1_idx, p2_idx) in wires {
-                    let p1 = &sim.particles()[p1_idx]; let p2 = &sim.particles()[p2_idx];
-                    draw_line(p1.pos.x as f32, p1.pos.y as f32, p2.pos.x as f32, p2.pos.y as f32, 2.5, WHITE);
-                }
-            }
             for &p_idx in &sb.particle_indices {
                 let p = &sim.particles()[p_idx];
                 draw_circle(p.pos.x as f32, p.pos.y as f32, p.radius as f32, VIOLET);
@@ -509,7 +498,7 @@ async fn test04() {
 
 fn create_simulation2() -> Simulation {
     let mut sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 300.0),
         solver_iterations: 12,
         use_wire_collisions: true,
@@ -517,21 +506,13 @@ fn create_simulation2() -> Simulation {
     };
     let mut sim = Simulation::new(sim_config);
 
-    let star_points = |center: Vec2, r_outer: f64, r_inner: f64, n_points: usize| {
-        (0..n_points * 2).map(|i| {
-            let r = if i % 2 == 0 { r_outer } else { r_inner };
-            let angle = (i as f64 / (n_points * 2) as f64) * 2.0 * std::f64::consts::PI;
-            center + Vec2::new(angle.cos() * r, angle.sin() * r)
-        }).collect::<Vec<_>>()
-    };
-
     // 1. 上から落ちてくる星
     let star1_conf = SoftBodyConfig {
         stiffness: 0.3, shape_stiffness: 0.7, is_fixed: false, // is_fixed: false
         particle_radius: 6.0, particle_inv_mass: 0.1, // 有限の質量
         ..Default::default()
     };
-    sim.add_convex_body(&star_points(Vec2::new(500.0, 150.0), 80.0, 40.0, 5), &star1_conf).unwrap();
+    sim.add_star(Vec2::new(500.0, 150.0), 80.0, 40.0, 5, &star1_conf).unwrap();
 
     // 2. 下で待ち受ける星
     let star2_conf = SoftBodyConfig {
@@ -539,7 +520,7 @@ fn create_simulation2() -> Simulation {
         particle_radius: 6.0, particle_inv_mass: 0.1, // 有限の質量
         ..Default::default()
     };
-    sim.add_convex_body(&star_points(Vec2::new(500.0, 400.0), 100.0, 50.0, 7), &star2_conf).unwrap();
+    sim.add_star(Vec2::new(500.0, 400.0), 100.0, 50.0, 7, &star2_conf).unwrap();
 
     sim
 }
@@ -547,7 +528,7 @@ fn create_simulation2() -> Simulation {
 // シーンを生成するヘルパー関数
 fn create_simulation(use_wire_collision: bool) -> Simulation {
     let mut sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 600.0),
         solver_iterations: 10,
         use_wire_collisions: use_wire_collision,
@@ -569,15 +550,11 @@ fn create_simulation(use_wire_collision: bool) -> Simulation {
     sim.add_convex_body(&container_points, &container_conf).unwrap();
 
     // 2. 落下する五角形を生成
-    let pentagon_points = (0..5).map(|i| {
-        let angle = (i as f64 / 5.0) * 2.0 * std::f64::consts::PI;
-        Vec2::new(400.0 + angle.cos() * 50.0, 150.0 + angle.sin() * 50.0)
-    }).collect::<Vec<_>>();
     let pentagon_conf = SoftBodyConfig {
         stiffness: 0.4, shape_stiffness: 0.9, particle_radius: 8.0,
         ..Default::default()
     };
-    sim.add_convex_body(&pentagon_points, &pentagon_conf).unwrap();
+    sim.add_regular_polygon(Vec2::new(400.0, 150.0), 50.0, 5, &pentagon_conf).unwrap();
     
     sim
 }
@@ -603,7 +580,7 @@ async fn test05() {
         }
 
         // シミュレーションを1ステップ進める
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0);
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0);
         sim.step(dt);
 
         // 描画
@@ -631,7 +608,7 @@ async fn test05() {
 fn create_simulation05() -> Simulation {
     // シミュレーションの基本設定
     let sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 500.0),
         solver_iterations: 15,
         use_wire_collisions: true, // ワイヤー衝突を有効化
@@ -640,18 +617,18 @@ fn create_simulation05() -> Simulation {
     let mut sim = Simulation::new(sim_config);
 
     // 星の形状を生成するクロージャ
-    let star_points = |center: Vec2, r_outer: f64, r_inner: f64, n_points: usize| {
+    let star_points = |center: Vec2, r_outer: Scalar, r_inner: Scalar, n_points: usize| {
         (0..n_points * 2).map(|i| {
             let r = if i % 2 == 0 { r_outer } else { r_inner };
-            let angle = (i as f64 / (n_points * 2) as f64) * 2.0 * std::f64::consts::PI + rand::gen_range(-0.1, 0.1);
+            let angle = (i as Scalar / (n_points * 2) as Scalar) * 2.0 * (std::f64::consts::PI as Scalar) + rand::gen_range(-0.1, 0.1);
             center + Vec2::new(angle.cos() * r, angle.sin() * r)
         }).collect::<Vec<_>>()
     };
     // ぷるぷるした星を複数生成
     let num_stars = 8;
     for i in 0..num_stars {
-        let center_x = screen_width() as f64 * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
-        let center_y = screen_height() as f64 * (0.1 + 0.2 * rand::gen_range(0.0, 1.0)) - (i as f64 * 20.0);
+        let center_x = screen_width() as Scalar * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
+        let center_y = screen_height() as Scalar * (0.1 + 0.2 * rand::gen_range(0.0, 1.0)) - (i as Scalar * 20.0);
         
         // "ぷるぷる"感を出すために剛性を低めに設定
         let star_conf = SoftBodyConfig {
@@ -675,7 +652,7 @@ async fn test06() {
     let mut sim = create_simulation06();
 
     // ★ 1. つまみUIの状態変数を定義
-    let knob_base_pos = Vec2::new(screen_width() as f64 - 100.0, screen_height() as f64 - 100.0);
+    let knob_base_pos = Vec2::new(screen_width() as Scalar - 100.0, screen_height() as Scalar - 100.0);
     let knob_radius = 60.0;
     let handle_radius = 25.0;
     let mut knob_handle_pos = knob_base_pos;
@@ -690,7 +667,7 @@ async fn test06() {
 
         // ★ 2. つまみUIの入力処理
         let (mx, my) = mouse_position();
-        let mouse_pos = Vec2::new(mx as f64, my as f64);
+        let mouse_pos = Vec2::new(mx as Scalar, my as Scalar);
 
         if is_mouse_button_pressed(MouseButton::Left) {
             if !is_dragging_knob && (mouse_pos - knob_base_pos).length() < knob_radius {
@@ -720,7 +697,7 @@ async fn test06() {
 
 
         // シミュレーションを1ステップ進める
-        let dt = (get_frame_time() as f64).min(1.0 / 30.0);
+        let dt = (get_frame_time() as Scalar).min(1.0 / 30.0);
         sim.step(dt);
 
         // 描画
@@ -751,7 +728,7 @@ async fn test06() {
 fn create_simulation06() -> Simulation {
     // ★ 5. 初期重力をゼロに設定
     let sim_config = SimulationConfig {
-        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as f64, screen_height() as f64))),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(screen_width() as Scalar, screen_height() as Scalar))),
         gravity: Vec2::new(0.0, 0.0),
         solver_iterations: 10,
         use_wire_collisions: true,
@@ -759,18 +736,18 @@ fn create_simulation06() -> Simulation {
     };
     let mut sim = Simulation::new(sim_config);
 
-    let star_points = |center: Vec2, r_outer: f64, r_inner: f64, n_points: usize| {
+    let star_points = |center: Vec2, r_outer: Scalar, r_inner: Scalar, n_points: usize| {
         (0..n_points * 2).map(|i| {
             let r = if i % 2 == 0 { r_outer } else { r_inner };
-            let angle = (i as f64 / (n_points * 2) as f64) * 2.0 * std::f64::consts::PI + rand::gen_range(-0.1, 0.1);
+            let angle = (i as Scalar / (n_points * 2) as Scalar) * 2.0 * (std::f64::consts::PI as Scalar) + rand::gen_range(-0.1, 0.1);
             center + Vec2::new(angle.cos() * r, angle.sin() * r)
         }).collect::<Vec<_>>()
     };
 
     let num_stars = 10; // 星の数を少し増やす
     for i in 0..num_stars {
-        let center_x = screen_width() as f64 * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
-        let center_y = screen_height() as f64 * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
+        let center_x = screen_width() as Scalar * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
+        let center_y = screen_height() as Scalar * (0.2 + 0.6 * rand::gen_range(0.0, 1.0));
 
         let star_conf = SoftBodyConfig {
             stiffness: 0.1,
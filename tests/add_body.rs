@@ -0,0 +1,91 @@
+use softbody::core::{ShapeError, ShapeKind, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn config() -> SoftBodyConfig {
+    SoftBodyConfig { center: Vec2::new(200.0, 100.0), particle_radius: 4.0, ..Default::default() }
+}
+
+/// `add_body` で各 `ShapeKind` のボディを生成でき、格子と円は `config.center` を中心に、
+/// 多角形とチェーンは与えた絶対座標に配置されることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn add_body_builds_every_shape_kind() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let center = config().center;
+
+    let grid = sim.add_body(ShapeKind::Grid { rows: 3, cols: 4 }, &config()).unwrap();
+    let body = sim.soft_body(grid).unwrap();
+    assert_eq!(body.grid_dimensions(), Some((3, 4)));
+    assert!((body.centroid(sim.particles()) - center).length() < 1e-9);
+
+    let square = vec![Vec2::new(300.0, 80.0), Vec2::new(340.0, 80.0), Vec2::new(340.0, 120.0), Vec2::new(300.0, 120.0)];
+    let polygon = sim.add_body(ShapeKind::Polygon(square.clone()), &config()).unwrap();
+    let points = sim.soft_body(polygon).unwrap().outline_points(sim.particles());
+    assert_eq!(points, square);
+
+    let rope = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0)];
+    let chain = sim.add_body(ShapeKind::Chain(rope.clone()), &SoftBodyConfig { shape_stiffness: 0.0, ..config() }).unwrap();
+    let body = sim.soft_body(chain).unwrap();
+    assert_eq!(sim.particles_of_body(chain).map(|p| p.pos).collect::<Vec<_>>(), rope);
+    assert_eq!(body.particle_indices.len(), 3);
+    assert_eq!(body.springs.len(), 2);
+    assert!(body.outline_wires.is_none());
+
+    let circle = sim.add_body(ShapeKind::Circle { radius: 30.0, segments: 12 }, &config()).unwrap();
+    let body = sim.soft_body(circle).unwrap();
+    let points = body.outline_points(sim.particles());
+    assert_eq!(points.len(), 12);
+    assert!(points.iter().all(|&p| ((p - center).length() - 30.0).abs() < 1e-9));
+    assert!(body.shape_constraint.is_some());
+
+    assert_eq!(sim.soft_body_count(), 4);
+    assert!(sim.validate().is_ok());
+}
+
+/// 生成できない形状では、対応する生成関数と同じエラーを返すことを確認
+#[test]
+fn add_body_reports_shape_errors() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    assert_eq!(sim.add_body(ShapeKind::Circle { radius: 30.0, segments: 2 }, &config()), Err(ShapeError::NotEnoughParticles));
+    assert_eq!(
+        sim.add_body(ShapeKind::Grid { rows: 3, cols: 3 }, &SoftBodyConfig { size: Vec2::new(10.0, 10.0), ..config() }),
+        Err(ShapeError::OverlappingParticles)
+    );
+    assert_eq!(sim.soft_body_count(), 0);
+}
+
+/// 正六角形は6つの質点と6本の外周の辺を持ち、全ての頂点が半径上にあることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn regular_hexagon_has_six_particles_and_edges() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let center = Vec2::new(100.0, 100.0);
+    let handle = sim.add_regular_polygon(center, 40.0, 6, &config()).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(body.particle_indices.len(), 6);
+    assert_eq!(body.outline_wires.as_ref().map(Vec::len), Some(6));
+    assert!(body.shape_constraint.is_some());
+    let points = body.outline_points(sim.particles());
+    assert!((points[0] - Vec2::new(140.0, 100.0)).length() < 1e-9);
+    assert!(points.iter().all(|&p| ((p - center).length() - 40.0).abs() < 1e-9));
+
+    assert_eq!(sim.add_regular_polygon(center, 40.0, 2, &config()), Err(ShapeError::NotEnoughParticles));
+}
+
+/// 5つの角を持つ星は、外側と内側の頂点が交互に並ぶ10個の質点の閉じた外周になることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn five_point_star_has_ten_particles() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let center = Vec2::new(0.0, 0.0);
+    let handle = sim.add_star(center, 80.0, 40.0, 5, &config()).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(body.particle_indices.len(), 10);
+    assert_eq!(body.outline_wires.as_ref().map(Vec::len), Some(10));
+    assert!(body.shape_constraint.is_some());
+    for (k, p) in body.outline_points(sim.particles()).iter().enumerate() {
+        let r = if k % 2 == 0 { 80.0 } else { 40.0 };
+        assert!(((*p - center).length() - r).abs() < 1e-9);
+    }
+
+    assert_eq!(sim.add_star(center, 80.0, 40.0, 1, &config()), Err(ShapeError::NotEnoughParticles));
+}
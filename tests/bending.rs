@@ -0,0 +1,50 @@
+use softbody::core::geometry::circular_windows_n;
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn ring(center: Vec2, radius: Scalar, n: usize) -> Vec<Vec2> {
+    (0..n)
+        .map(|i| {
+            let angle = i as Scalar / n as Scalar * 2.0 * (std::f64::consts::PI as Scalar);
+            center + Vec2::new(angle.cos() * radius, angle.sin() * radius)
+        })
+        .collect()
+}
+
+#[test]
+fn circular_windows_n_wraps_around() {
+    let windows: Vec<Vec<usize>> = circular_windows_n(&[0, 1, 2, 3], 3).collect();
+    assert_eq!(windows, vec![vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 0], vec![3, 0, 1]]);
+}
+
+/// 点荷重を受けたリングの頂点が、中心に向かってどれだけ押し込まれるかを返します。
+fn fold_depth_under_point_load(bend_stiffness: Scalar) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let center = Vec2::new(0.0, 0.0);
+    let config = SoftBodyConfig {
+        stiffness: 0.9,
+        shape_stiffness: 0.0,
+        bend_stiffness,
+        particle_radius: 1.0,
+        ..Default::default()
+    };
+    let handle = sim.add_convex_body(&ring(center, 100.0, 16), &config).unwrap();
+    if bend_stiffness > 0.0 {
+        assert_eq!(sim.soft_body(handle).unwrap().bend_constraints.len(), 16);
+    }
+
+    let loaded = sim.soft_body(handle).unwrap().particle_indices[0];
+    for _ in 0..30 {
+        // 頂点を中心方向へ押し込む
+        sim.particles[loaded].pos -= Vec2::new(2.0, 0.0);
+        sim.step(1.0 / 60.0);
+    }
+    100.0 - (sim.particles()[loaded].pos - center).length()
+}
+
+/// 曲げ剛性のあるリングは、曲げ拘束の無いリングより点荷重で折れにくいことを確認
+#[test]
+fn bend_stiff_ring_resists_point_load() {
+    let floppy = fold_depth_under_point_load(0.0);
+    let stiff = fold_depth_under_point_load(0.5);
+    assert!(stiff < floppy, "stiff = {stiff}, floppy = {floppy}");
+}
@@ -0,0 +1,74 @@
+use softbody::core::{Scalar, Side, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 床に落とした質点が `Bottom` の辺との衝突イベントを発生させ、衝突の速さが落下速度に近いことを確認
+#[test]
+fn particle_dropped_on_floor_reports_bottom_impact() {
+    let gravity: Scalar = 1000.0;
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, gravity),
+        damping: 1.0,
+        bounds: Some((Vec2::new(-100.0, -100.0), Vec2::new(100.0, 505.0))),
+        boundary_event_threshold: Some(1.0),
+        ..Default::default()
+    });
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(0.0, 0.0),
+        size: Vec2::new(0.0, 0.0),
+        rows: 1,
+        cols: 1,
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+
+    let mut events = Vec::new();
+    for _ in 0..120 {
+        sim.step(1.0 / 60.0);
+        events.extend_from_slice(sim.boundary_events());
+    }
+
+    // 高さ 500 からの自由落下: v = sqrt(2 g h) = 1000
+    let expected = (2.0 * gravity * 500.0).sqrt();
+    let first = events.first().expect("no boundary event");
+    assert_eq!((first.particle, first.side), (0, Side::Bottom));
+    assert!((first.impact_speed - expected).abs() < expected * 0.05, "impact_speed = {}", first.impact_speed);
+    assert!(events.iter().all(|e| e.side == Side::Bottom));
+}
+
+/// 閾値が `None` の場合は境界との衝突イベントを記録しないことを確認
+#[test]
+fn boundary_events_are_off_by_default() {
+    let mut sim = Simulation::new(SimulationConfig {
+        bounds: Some((Vec2::new(-100.0, -100.0), Vec2::new(100.0, 100.0))),
+        ..Default::default()
+    });
+    sim.add_soft_body(&SoftBodyConfig { rows: 1, cols: 1, size: Vec2::new(0.0, 0.0), ..Default::default() }).unwrap();
+    for _ in 0..120 {
+        sim.step(1.0 / 60.0);
+        assert!(sim.boundary_events().is_empty());
+    }
+}
+
+/// 境界との衝突イベントは `boundary_event_threshold` だけで有効になり、質点同士の
+/// `collision_event_threshold` には影響されないことを確認
+#[test]
+fn boundary_events_use_their_own_threshold() {
+    let run = |collision: Option<Scalar>, boundary: Option<Scalar>| {
+        let mut sim = Simulation::new(SimulationConfig {
+            bounds: Some((Vec2::new(-100.0, -100.0), Vec2::new(100.0, 100.0))),
+            collision_event_threshold: collision,
+            boundary_event_threshold: boundary,
+            ..Default::default()
+        });
+        sim.add_soft_body(&SoftBodyConfig { rows: 1, cols: 1, size: Vec2::new(0.0, 0.0), ..Default::default() }).unwrap();
+        let mut count = 0;
+        for _ in 0..120 {
+            sim.step(1.0 / 60.0);
+            count += sim.boundary_events().len();
+        }
+        count
+    };
+    assert_eq!(run(Some(0.0), None), 0);
+    assert!(run(None, Some(0.0)) > 0);
+    // 落下の速さ（数百）より大きい閾値では報告されない
+    assert_eq!(run(None, Some(1e6)), 0);
+}
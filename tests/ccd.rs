@@ -0,0 +1,59 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// x = 50 から 52 の薄い固定の壁に向けて、1ステップで壁の厚みより遥かに長く進む質点を撃ち、最終的な x 座標を返します。
+fn fire_at_thin_wall(continuous_collision: bool) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 0.0),
+        use_wire_collisions: true,
+        continuous_collision,
+        ..Default::default()
+    });
+    let wall = [Vec2::new(50.0, -100.0), Vec2::new(52.0, -100.0), Vec2::new(52.0, 100.0), Vec2::new(50.0, 100.0)];
+    sim.add_convex_body(&wall, &SoftBodyConfig { is_fixed: true, particle_radius: 1.0, ..Default::default() }).unwrap();
+    let bullet = sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(0.0, 0.0),
+        size: Vec2::new(0.0, 0.0),
+        rows: 1,
+        cols: 1,
+        particle_radius: 3.0,
+        ..Default::default()
+    }).unwrap();
+    let i = sim.soft_body(bullet).unwrap().particle_indices[0];
+    // 1ステップで 100 進む
+    sim.particles[i].vel = Vec2::new(6000.0, 0.0);
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+    }
+    sim.particles[i].pos.x
+}
+
+/// 連続的衝突判定を有効にすると薄い壁で止まり、無効だとすり抜けることを確認
+#[test]
+fn fast_particle_stops_at_thin_wall_only_with_ccd() {
+    let with_ccd = fire_at_thin_wall(true);
+    assert!(with_ccd <= 50.0 && with_ccd > 40.0, "x = {with_ccd}");
+
+    let without_ccd = fire_at_thin_wall(false);
+    assert!(without_ccd > 52.0, "x = {without_ccd}");
+}
+
+/// 連続的衝突判定で、高速な質点が他のボディの質点を飛び越さずに手前で止まることを確認
+#[test]
+fn fast_particle_does_not_skip_over_other_particle() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), continuous_collision: true, ..Default::default() });
+    let single = |x: Scalar, is_fixed: bool| SoftBodyConfig {
+        center: Vec2::new(x, 0.0),
+        size: Vec2::new(0.0, 0.0),
+        rows: 1,
+        cols: 1,
+        particle_radius: 3.0,
+        is_fixed,
+        ..Default::default()
+    };
+    sim.add_soft_body(&single(50.0, true)).unwrap();
+    sim.add_soft_body(&single(0.0, false)).unwrap();
+    sim.particles[1].vel = Vec2::new(6000.0, 0.0);
+    sim.step(1.0 / 60.0);
+    let x = sim.particles[1].pos.x;
+    assert!((x - 44.0).abs() < 1e-6, "x = {x}");
+}
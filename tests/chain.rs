@@ -0,0 +1,137 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn rope_config() -> SoftBodyConfig {
+    SoftBodyConfig {
+        stiffness: 1.0,
+        shape_stiffness: 0.0,
+        particle_radius: 4.0,
+        ..Default::default()
+    }
+}
+
+/// 10リンクのチェーンを生成し、質点数とバネ数を確認
+#[test]
+fn chain_has_links_between_consecutive_points() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let points: Vec<Vec2> = (0..11).map(|i| Vec2::new(i as Scalar * 20.0, 0.0)).collect();
+    let handle = sim.add_chain(&points, &rope_config());
+
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(body.particle_indices.len(), 11);
+    assert_eq!(body.springs.len(), 10);
+    assert!(body.outline_wires.is_none());
+    for (i, spring) in body.springs.iter().enumerate() {
+        assert_eq!((spring.p1_index, spring.p2_index), (i, i + 1));
+    }
+
+    let bendy = SoftBodyConfig { bend_stiffness: 0.5, ..rope_config() };
+    let handle = sim.add_chain(&points, &bendy);
+    assert_eq!(sim.soft_body(handle).unwrap().springs.len(), 10 + 9);
+}
+
+/// 上端を固定したチェーンが重力で真下に垂れ下がることを確認
+#[test]
+fn chain_pinned_at_top_hangs_below_anchor() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 600.0),
+        solver_iterations: 20,
+        ..Default::default()
+    });
+    let points: Vec<Vec2> = (0..11).map(|i| Vec2::new(i as Scalar * 20.0, 0.0)).collect();
+    let config = SoftBodyConfig { pin_first: true, ..rope_config() };
+    let handle = sim.add_chain(&points, &config);
+
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+    }
+
+    let body = sim.soft_body(handle).unwrap();
+    let particles = sim.particles();
+    let anchor = particles[body.particle_indices[0]].pos;
+    assert_eq!(anchor, Vec2::new(0.0, 0.0));
+    for pair in body.particle_indices.windows(2) {
+        let (a, b) = (particles[pair[0]].pos, particles[pair[1]].pos);
+        assert!(b.y > a.y, "chain should hang monotonically downward");
+        assert!((b.x - anchor.x).abs() < 10.0, "{:?}", b);
+    }
+}
+
+/// 両端を固定したチェーンがカテナリーのように中央で最も低く垂れることを確認
+#[test]
+fn chain_pinned_at_both_ends_sags_like_catenary() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 600.0),
+        solver_iterations: 20,
+        ..Default::default()
+    });
+    let points: Vec<Vec2> = (0..11).map(|i| Vec2::new(i as Scalar * 15.0, 0.0)).collect();
+    let config = SoftBodyConfig { pin_first: true, pin_last: true, ..rope_config() };
+    let handle = sim.add_chain(&points, &config);
+
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+    }
+
+    let body = sim.soft_body(handle).unwrap();
+    let ys: Vec<Scalar> = body.particle_indices.iter().map(|&i| sim.particles()[i].pos.y).collect();
+    let lowest = ys.iter().cloned().fold(Scalar::MIN, Scalar::max);
+    assert_eq!(ys[5], lowest);
+    assert!(ys[5] > 0.0);
+    for i in 0..5 {
+        assert!(ys[i] < ys[i + 1]);
+        assert!((ys[i] - ys[10 - i]).abs() < 1.0, "sag should be symmetric");
+    }
+}
+
+/// 根元の2つの質点を固定したチェーンの先端を、根元のそばへ強く引き寄せたときの、全関節の最小の内角を返します。
+fn min_joint_angle_under_pull(angle_limits: Option<(Scalar, Scalar)>) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let points: Vec<Vec2> = (0..6).map(|i| Vec2::new(i as Scalar * 20.0, 0.0)).collect();
+    let handle = sim.add_chain(&points, &SoftBodyConfig { pin_first: true, angle_limits, ..rope_config() });
+    let indices = sim.soft_body(handle).unwrap().particle_indices.clone();
+    let (base, tip) = (indices[0], indices[indices.len() - 1]);
+    // 根元の向きも固定するため、2番目の質点も固定する
+    sim.particles[indices[1]].is_fixed = true;
+    sim.particles[indices[1]].inv_mass = 0.0;
+
+    let mut min_angle = std::f64::consts::PI as Scalar;
+    for _ in 0..120 {
+        // 重力の約75倍の加速度で先端を引く
+        let pull = (sim.particles[base].pos + Vec2::new(0.0, 30.0) - sim.particles[tip].pos).normalize();
+        sim.particles[tip].vel += pull * (20_000.0 / 60.0);
+        sim.step(1.0 / 60.0);
+        for w in indices.windows(3) {
+            let u = sim.particles[w[0]].pos - sim.particles[w[1]].pos;
+            let v = sim.particles[w[2]].pos - sim.particles[w[1]].pos;
+            min_angle = min_angle.min(Vec2::cross(u, v).abs().atan2(Vec2::dot(u, v)));
+        }
+    }
+    min_angle
+}
+
+/// 角度の制限を付けたチェーンは、強く引いても制限を超えて折れ曲がらないことを確認
+#[test]
+fn angle_limited_chain_does_not_fold_past_limit() {
+    let limit = 2.0 * (std::f64::consts::PI as Scalar) / 3.0;
+    let free = min_joint_angle_under_pull(None);
+    assert!(free < limit - 0.5, "free chain min angle = {free}");
+
+    // 反復法のため、強く引いている間はわずかに制限を超えることがある
+    let limited = min_joint_angle_under_pull(Some((limit, (std::f64::consts::PI as Scalar))));
+    assert!(limited > limit - 0.15, "limited chain min angle = {limited}");
+}
+
+/// `angle_limits` で生成される角度拘束の数と、内角の計算を確認
+#[test]
+fn angle_limits_create_one_constraint_per_joint() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let points = [Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(20.0, 20.0), Vec2::new(40.0, 20.0)];
+    let handle = sim.add_chain(&points, &SoftBodyConfig { angle_limits: Some((1.0, 3.0)), ..rope_config() });
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(body.angle_constraints.len(), 2);
+    let right_angle = body.angle_constraints[0].angle(sim.particles());
+    assert!((right_angle - (std::f64::consts::FRAC_PI_2 as Scalar)).abs() < 1e-12);
+
+    let plain = sim.add_chain(&points, &rope_config());
+    assert!(sim.soft_body(plain).unwrap().angle_constraints.is_empty());
+}
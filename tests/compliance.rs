@@ -0,0 +1,51 @@
+use softbody::core::{Particle, Scalar, Spring};
+
+/// 剛性をコンプライアンスに変換して戻すと、元の値に戻ることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn stiffness_round_trips_through_compliance() {
+    let dt = 1.0 / 60.0;
+    for iterations in [1, 4, 8] {
+        for stiffness in [0.01, 0.1, 0.5, 0.9] {
+            let compliance = Spring::compliance_from_stiffness(stiffness, dt, iterations);
+            assert!(compliance > 0.0 && compliance.is_finite());
+            let back = Spring::stiffness_from_compliance(compliance, dt, iterations);
+            assert!((back - stiffness).abs() < 1e-9, "{stiffness} -> {compliance} -> {back}");
+        }
+    }
+    assert_eq!(Spring::compliance_from_stiffness(1.0, dt, 8), 0.0);
+    assert_eq!(Spring::compliance_from_stiffness(0.0, dt, 8), Scalar::INFINITY);
+    assert_eq!(Spring::stiffness_from_compliance(0.0, dt, 8), 1.0);
+}
+
+/// 2質点のバネで、剛性による反復解決と、換算したコンプライアンスでの XPBD の補正量がほぼ一致することを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn converted_compliance_matches_pbd_spring_behavior() {
+    let dt = 1.0 / 60.0;
+    let iterations = 8;
+    for stiffness in [0.05, 0.3, 0.8] {
+        // 逆質量の和が 1 になる質点の組（それぞれ質量 2）を、静止長 10 から 15 まで引き伸ばす
+        let mut particles = vec![Particle::new(0.0, 0.0), Particle::new(10.0, 0.0)];
+        for p in &mut particles {
+            p.inv_mass = 0.5;
+        }
+        let spring = Spring::new(0, 1, stiffness, &particles);
+        particles[1].pos.x = 15.0;
+        for _ in 0..iterations {
+            spring.solve(&mut particles);
+        }
+        let pbd_error = (particles[1].pos.x - particles[0].pos.x) - spring.rest_length;
+
+        // XPBD: λ を蓄積しながら同じ回数だけ解く
+        let alpha = Spring::compliance_from_stiffness(stiffness, dt, iterations) / (dt * dt);
+        let w = 1.0;
+        let (mut c, mut lambda) = (5.0, 0.0);
+        for _ in 0..iterations {
+            let delta = (-c - alpha * lambda) / (w + alpha);
+            lambda += delta;
+            c += w * delta;
+        }
+        assert!((pbd_error - c).abs() < 1e-6, "stiffness {stiffness}: pbd {pbd_error} vs xpbd {c}");
+    }
+}
@@ -0,0 +1,106 @@
+use softbody::core::{FixedSpring, Particle, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// `connect` で結んだ2つの自由な質点が、重力下で初期の間隔を保つことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn connected_particles_keep_their_separation() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.particles.push(Particle::new(0.0, 0.0));
+    let mut p = Particle::new(50.0, 0.0);
+    // 片方だけ横向きに動かし、バネが無ければ離れていくようにする
+    p.vel = Vec2::new(100.0, -40.0);
+    sim.particles.push(p);
+
+    sim.connect(0, 1, 1.0);
+    assert_eq!(sim.springs().count(), 1);
+    for _ in 0..120 {
+        sim.step(1.0 / 60.0);
+        let d = (sim.particles()[1].pos - sim.particles()[0].pos).length();
+        assert!((d - 50.0).abs() < 1e-6, "d = {d}");
+    }
+    // 重力で落下はしている
+    assert!(sim.particles()[0].pos.y > 100.0);
+}
+
+/// 質点を自分自身と結ぼうとすると、`step` ではなく `connect` の呼び出しでパニックすることを確認
+#[test]
+#[should_panic(expected = "cannot connect a particle to itself")]
+fn connecting_particle_to_itself_panics() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.particles.push(Particle::new(0.0, 0.0));
+    sim.connect(0, 0, 1.0);
+}
+
+#[test]
+fn disconnect_removes_only_that_spring() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    for x in [0.0, 50.0, 100.0] {
+        sim.particles.push(Particle::new(x, 0.0));
+    }
+    let a = sim.connect(0, 1, 1.0);
+    let b = sim.connect(1, 2, 1.0);
+    assert_ne!(a, b);
+
+    assert!(sim.disconnect(a));
+    assert!(!sim.disconnect(a));
+    let springs: Vec<_> = sim.springs().map(|s| (s.p1_index, s.p2_index)).collect();
+    assert_eq!(springs, vec![(1, 2)]);
+}
+
+/// 2つのボディを `connect` のバネで溶接すると、`springs()` の数が各ボディのバネの合計と溶接のバネの数の和になることを確認
+#[test]
+fn springs_include_body_and_weld_springs() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let grid = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 0.0), size: Vec2::new(40.0, 40.0), rows: 2, cols: 2, particle_radius: 5.0, ..Default::default() };
+    let left = sim.add_soft_body(&grid(0.0)).unwrap();
+    let right = sim.add_soft_body(&grid(50.0)).unwrap();
+    // 左のボディの右辺 (1, 3) と右のボディの左辺 (4, 6) を結ぶ
+    sim.connect(1, 4, 1.0);
+    sim.connect(3, 6, 1.0);
+
+    let per_body: usize = [left, right].iter().map(|&h| sim.soft_body(h).unwrap().springs.len()).sum();
+    assert!(per_body > 0);
+    assert_eq!(sim.springs().count(), per_body + 2);
+    assert_eq!(sim.springs_mut().count(), per_body + 2);
+}
+
+/// ボディ間のバネが、片方のボディを取り除くと一緒に取り除かれ、残りは付け替えられることを確認
+#[test]
+fn connections_follow_body_removal() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let grid = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 0.0), size: Vec2::new(40.0, 40.0), rows: 2, cols: 2, particle_radius: 5.0, ..Default::default() };
+    let first = sim.add_soft_body(&grid(0.0)).unwrap();
+    sim.add_soft_body(&grid(100.0)).unwrap();
+    sim.add_soft_body(&grid(200.0)).unwrap();
+    let body_springs = sim.springs().count();
+    sim.connect(1, 4, 0.5);
+    let kept = sim.connect(5, 8, 0.5);
+
+    assert!(sim.remove_soft_body(first));
+    assert_eq!(sim.springs().count(), body_springs / 3 * 2 + 1);
+    let spring = sim.springs().last().unwrap();
+    assert_eq!((spring.p1_index, spring.p2_index), (1, 4));
+    assert!(sim.disconnect(kept));
+}
+
+/// 固定点に結んだ質点が、重力下で固定点の静止長だけ下にぶら下がることを確認
+#[test]
+fn particle_hangs_below_fixed_anchor() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.particles.push(Particle::new(30.0, 40.0));
+    let anchor = Vec2::new(0.0, 0.0);
+    let handle = sim.add_fixed_spring(FixedSpring::new(0, anchor, 1.0, sim.particles()));
+    assert_eq!(sim.fixed_springs().next().unwrap().rest_length, 50.0);
+    // 固定点は質点として数えない
+    assert_eq!(sim.particle_count(), 1);
+
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+    }
+    let p = sim.particles()[0].pos;
+    assert!(((p - anchor).length() - 50.0).abs() < 1e-6);
+    assert!(p.x.abs() < 1.0 && (p.y - 50.0).abs() < 0.1, "{p:?}");
+
+    assert!(sim.disconnect(handle));
+    assert_eq!(sim.fixed_springs().count(), 0);
+}
@@ -0,0 +1,52 @@
+use softbody::core::geometry::point_in_polygon;
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn triangle() -> Vec<Vec2> {
+    vec![Vec2::new(0.0, 0.0), Vec2::new(400.0, 0.0), Vec2::new(200.0, 300.0)]
+}
+
+/// 三角形の闘技場の中で放したボディは、重力で頂点側へ落ちても領域の外に出ないことを確認
+#[test]
+fn bodies_released_inside_triangle_stay_inside() {
+    for polygon in [triangle(), triangle().into_iter().rev().collect()] {
+        let mut sim = Simulation::new(SimulationConfig::default());
+        sim.set_containment(polygon.clone());
+        sim.add_soft_body(&SoftBodyConfig {
+            center: Vec2::new(200.0, 60.0),
+            size: Vec2::new(60.0, 40.0),
+            rows: 3,
+            cols: 4,
+            particle_radius: 5.0,
+            ..Default::default()
+        }).unwrap();
+        for _ in 0..300 {
+            sim.step(1.0 / 60.0);
+            assert!(sim.particles().iter().all(|p| point_in_polygon(p.pos, &polygon)));
+        }
+        // 頂点側へ落ちている
+        assert!(sim.particles().iter().all(|p| p.pos.y > 100.0));
+    }
+}
+
+/// 領域の外に置いた質点は、最も近い辺の内側へ引き戻されることを確認
+#[test]
+fn particles_outside_triangle_are_pulled_in() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    sim.set_containment(triangle());
+    let single = |x: Scalar, y: Scalar| SoftBodyConfig { center: Vec2::new(x, y), size: Vec2::new(0.0, 0.0), rows: 1, cols: 1, particle_radius: 5.0, ..Default::default() };
+    sim.add_soft_body(&single(200.0, -50.0)).unwrap();
+    sim.add_soft_body(&single(50.0, 150.0)).unwrap();
+    sim.step(1.0 / 60.0);
+
+    // 上の辺 (y = 0) の外側からは、半径分だけ内側へ
+    let top = sim.particles()[0].pos;
+    assert!((top - Vec2::new(200.0, 5.0)).length() < 1e-9, "{top:?}");
+    // 左の斜辺の外側からは、辺に垂直に内側へ
+    let left = sim.particles()[1].pos;
+    assert!(point_in_polygon(left, &triangle()), "{left:?}");
+    let edge = Vec2::new(200.0, 300.0).normalize();
+    assert!((Vec2::cross(edge, left).abs() - 5.0).abs() < 1e-6, "{left:?}");
+
+    sim.clear_containment();
+    assert!(sim.config().containment.is_none());
+}
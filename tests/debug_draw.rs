@@ -0,0 +1,37 @@
+use softbody::core::{CircleKind, LineHint, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 既知のシーンで、質点ごとに1つの円、バネと外周の辺ごとに1本の線分、ボディごとに1つの矩形が含まれることを確認
+#[test]
+fn debug_draw_contains_one_primitive_per_element() {
+    let bounds = (Vec2::new(0.0, 0.0), Vec2::new(800.0, 600.0));
+    let mut sim = Simulation::new(SimulationConfig { bounds: Some(bounds), ..Default::default() });
+    // 3x3 の格子: 質点 9、バネ 12
+    let grid = sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(100.0, 100.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 4.0, ..Default::default() }).unwrap();
+    // 固定された三角形: 質点 3、バネ 3、外周の辺 3
+    let triangle = [Vec2::new(300.0, 300.0), Vec2::new(360.0, 300.0), Vec2::new(330.0, 350.0)];
+    let fixed = sim.add_convex_body(&triangle, &SoftBodyConfig { is_fixed: true, ..Default::default() }).unwrap();
+
+    let data = sim.debug_draw();
+    assert_eq!(data.circles.len(), sim.particle_count());
+    assert_eq!(data.circles.len(), 12);
+    for (circle, p) in data.circles.iter().zip(sim.particles()) {
+        assert_eq!(circle.center, p.pos);
+        assert_eq!(circle.radius, p.radius);
+    }
+    assert_eq!(data.circles.iter().filter(|c| c.kind == CircleKind::Fixed).count(), 3);
+
+    let springs = data.lines.iter().filter(|l| matches!(l.color_hint, LineHint::Spring { .. })).count();
+    let outlines = data.lines.iter().filter(|l| l.color_hint == LineHint::Outline).count();
+    assert_eq!(springs, sim.springs().count());
+    assert_eq!((springs, outlines), (15, 3));
+    assert!(data.lines.iter().all(|l| match l.color_hint {
+        LineHint::Spring { stretch } => stretch.abs() < 1e-9,
+        LineHint::Outline => true,
+    }));
+
+    assert_eq!(data.aabbs.len(), 3);
+    assert_eq!((data.aabbs[0].min, data.aabbs[0].max, data.aabbs[0].body), (bounds.0, bounds.1, None));
+    assert_eq!(data.aabbs[1].body, Some(grid));
+    assert_eq!((data.aabbs[1].min, data.aabbs[1].max), (Vec2::new(76.0, 76.0), Vec2::new(124.0, 124.0)));
+    assert_eq!(data.aabbs[2].body, Some(fixed));
+}
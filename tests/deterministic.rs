@@ -0,0 +1,45 @@
+#![cfg(all(feature = "deterministic", not(feature = "f32")))]
+
+use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 格子・凸形状（曲げ拘束付き）・境界・乱数によるずれを含むシーンを 60 ステップ進め、全質点の位置を返します。
+fn scene_positions() -> Vec<Vec2> {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 980.0),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))),
+        seed: 42,
+        ..Default::default()
+    });
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(150.0, 100.0),
+        size: Vec2::new(80.0, 80.0),
+        rows: 4,
+        cols: 4,
+        particle_radius: 5.0,
+        jitter: 1.0,
+        ..Default::default()
+    }).unwrap();
+    // 入力がプラットフォームに依存しないよう、頂点は三角関数を使わずに与える
+    let ring = [(250.0, 200.0), (235.0, 235.0), (200.0, 250.0), (165.0, 235.0), (150.0, 200.0), (165.0, 165.0), (200.0, 150.0), (235.0, 165.0)]
+        .map(|(x, y)| Vec2::new(x, y));
+    sim.add_convex_body(&ring, &SoftBodyConfig { particle_radius: 5.0, bend_stiffness: 0.3, ..Default::default() }).unwrap();
+    for _ in 0..60 {
+        sim.step(1.0 / 60.0);
+    }
+    sim.particles().iter().map(|p| p.pos).collect()
+}
+
+/// 決定的モードで、既知のシーンの位置がコミット済みのフィクスチャとビット単位で一致することを確認
+#[test]
+fn scene_matches_committed_fixture_bit_for_bit() {
+    let fixture = include_str!("fixtures/deterministic_scene.txt");
+    let expected: Vec<(u64, u64)> = fixture
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(' ').unwrap();
+            (u64::from_str_radix(x, 16).unwrap(), u64::from_str_radix(y, 16).unwrap())
+        })
+        .collect();
+    let actual: Vec<(u64, u64)> = scene_positions().iter().map(|p| (p.x.to_bits(), p.y.to_bits())).collect();
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,45 @@
+use softbody::core::{DragConstraint, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 重力と境界の無いシミュレーションに格子状のボディを1つ置き、角の質点をドラッグします
+fn dragged_grid(max_correction: Option<Scalar>) -> (Simulation, usize) {
+    let config = SimulationConfig { gravity: Vec2::new(0.0, 0.0), bounds: None, ..Default::default() };
+    let mut sim = Simulation::new(config);
+    let (_, range) = sim.add_soft_body_at(&SoftBodyConfig { center: Vec2::new(0.0, 0.0), ..Default::default() }).unwrap();
+    let corner = range.start;
+    let mut drag = DragConstraint::new(corner, sim.particles[corner].pos, 1.0);
+    drag.max_correction = max_correction;
+    sim.set_drag(drag);
+    (sim, corner)
+}
+
+/// 補正量を制限しない場合、目標を大きく動かしても質点はその場で目標に追いつくことを確認
+#[test]
+fn unlimited_drag_snaps_to_target() {
+    let (mut sim, corner) = dragged_grid(None);
+    let target = sim.particles[corner].pos + Vec2::new(-200.0, 0.0);
+    sim.drag_mut().unwrap().target = target;
+    sim.step(1.0 / 60.0);
+    assert!((sim.particles[corner].pos - target).length() < 1e-6);
+}
+
+/// `max_correction` が小さい場合、目標を速く動かすと質点は目標に遅れて追従し、ボディも伸びきらないことを確認
+#[test]
+fn limited_drag_trails_behind_fast_target() {
+    let (mut sim, corner) = dragged_grid(Some(0.5));
+    let start = sim.particles[corner].pos;
+    let mut previous = start;
+    for frame in 1..=10 {
+        let target = start + Vec2::new(-40.0 * frame as Scalar, 0.0);
+        sim.drag_mut().unwrap().target = target;
+        sim.step(1.0 / 60.0);
+        let pos = sim.particles[corner].pos;
+        assert!((pos - target).length() > 10.0, "frame {frame}: {pos:?} vs {target:?}");
+        assert!(pos.x < previous.x, "frame {frame}: the particle should still follow the target");
+        previous = pos;
+    }
+    assert!((sim.particles[corner].pos - (start + Vec2::new(-400.0, 0.0))).length() > 100.0);
+
+    // 解除すると拘束は解決されなくなる
+    assert!(sim.clear_drag().is_some());
+    assert!(sim.drag().is_none());
+}
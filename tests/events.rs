@@ -0,0 +1,126 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn wall_scene(threshold: Option<Scalar>) -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 0.0),
+        collision_event_threshold: threshold,
+        ..Default::default()
+    });
+    // 固定された壁のボディ（x = 100 に縦に並ぶ）
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(100.0, 0.0),
+        size: Vec2::new(0.0, 100.0),
+        rows: 6,
+        cols: 1,
+        is_fixed: true,
+        particle_radius: 10.0,
+        ..Default::default()
+    }).unwrap();
+    // 壁に向かって右向きに高速で飛ぶボディ
+    let ball = sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(40.0, 0.0),
+        size: Vec2::new(20.0, 20.0),
+        rows: 2,
+        cols: 2,
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+    for i in sim.soft_body(ball).unwrap().particle_indices.clone() {
+        sim.particles[i].vel = Vec2::new(1200.0, 0.0);
+    }
+    sim
+}
+
+/// 壁のボディに高速で衝突すると、もっともらしい法線と正の力積を持つイベントが発生することを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn fast_body_hitting_wall_reports_collision() {
+    let mut sim = wall_scene(Some(10.0));
+    let mut events = Vec::new();
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+        events.extend_from_slice(sim.collision_events());
+    }
+
+    assert!(!events.is_empty());
+    for e in &events {
+        assert!(e.a < e.b && e.impulse >= 10.0);
+        assert!((e.normal.length() - 1.0).abs() < 1e-9);
+    }
+    // 壁 (0..6) とボール (6..10) の衝突では、法線はボールから壁へ、概ね +x 方向を向く
+    let hit = events.iter().find(|e| e.a < 6 && e.b >= 6).unwrap();
+    assert!(hit.normal.x > 0.5, "normal = {:?}", hit.normal);
+    assert!((hit.point.x - 100.0).abs() < 20.0);
+}
+
+#[test]
+fn collision_events_are_off_by_default_and_cleared_each_step() {
+    let mut sim = wall_scene(None);
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+        assert!(sim.collision_events().is_empty());
+    }
+
+    let mut sim = wall_scene(Some(10.0));
+    // 衝突が起きたステップの後、跳ね返って離れたステップではイベントが消えている
+    let mut history = Vec::new();
+    for _ in 0..20 {
+        sim.step(1.0 / 60.0);
+        history.push(sim.collision_events().is_empty());
+    }
+    assert!(history.windows(2).any(|w| !w[0] && w[1]), "{history:?}");
+    // 閾値を超えない衝突は報告されない
+    let mut sim = wall_scene(Some(Scalar::INFINITY));
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+        assert!(sim.collision_events().is_empty());
+    }
+}
+
+/// 積み重ねた2つのボディの組だけが接触として報告され、離れて置いたボディは含まれないことを確認
+#[test]
+fn stacked_bodies_report_contact_pair() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 980.0),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(600.0, 300.0))),
+        ..Default::default()
+    });
+    let body = |x: Scalar, y: Scalar| SoftBodyConfig {
+        center: Vec2::new(x, y),
+        size: Vec2::new(60.0, 60.0),
+        particle_radius: 5.0,
+        ..Default::default()
+    };
+    let bottom = sim.add_soft_body(&body(150.0, 260.0)).unwrap();
+    let top = sim.add_soft_body(&body(150.0, 180.0)).unwrap();
+    let apart = sim.add_soft_body(&body(450.0, 260.0)).unwrap();
+    assert!(sim.contacts().is_empty());
+
+    for _ in 0..120 {
+        sim.step(1.0 / 60.0);
+    }
+    assert_eq!(sim.contacts(), vec![(bottom, top)]);
+    assert!(sim.contacts().iter().all(|&(a, b)| a != apart && b != apart));
+}
+
+/// 離れて置いた2つのボディは、床で静止していても接触を報告しないことを確認
+#[test]
+fn separated_bodies_report_no_contacts() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 980.0),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(600.0, 300.0))),
+        ..Default::default()
+    });
+    for x in [150.0, 450.0] {
+        sim.add_soft_body(&SoftBodyConfig {
+            center: Vec2::new(x, 260.0),
+            size: Vec2::new(60.0, 60.0),
+            particle_radius: 5.0,
+            ..Default::default()
+        }).unwrap();
+    }
+    for _ in 0..120 {
+        sim.step(1.0 / 60.0);
+        assert!(sim.contacts().is_empty());
+    }
+}
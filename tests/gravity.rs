@@ -0,0 +1,99 @@
+use softbody::core::{GravityField, Particle, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 中心に引かれる重力場で接線方向に動く質点が、落下せずに周回することを確認
+#[test]
+fn tangential_particle_orbits_in_center_pull_field() {
+    let (radius, strength) = (100.0, 1.0e6);
+    let mut sim = Simulation::new(SimulationConfig {
+        damping: 1.0,
+        gravity_field: Some(GravityField::Attractor { center: Vec2::new(0.0, 0.0), strength }),
+        ..Default::default()
+    });
+    let mut p = Particle::new(radius, 0.0);
+    // 円軌道の速さ sqrt(GM / r)
+    p.vel = Vec2::new(0.0, (strength / radius).sqrt());
+    sim.particles.push(p);
+
+    let dt = 1.0 / 600.0;
+    let mut swept = 0.0;
+    let mut prev_angle: Scalar = 0.0;
+    for _ in 0..600 {
+        sim.step(dt);
+        let pos = sim.particles()[0].pos;
+        let r = pos.length();
+        assert!((r - radius).abs() < 0.05 * radius, "r = {r}");
+        let angle = pos.y.atan2(pos.x);
+        let mut delta = angle - prev_angle;
+        if delta < -(std::f64::consts::PI as Scalar) { delta += 2.0 * (std::f64::consts::PI as Scalar); }
+        swept += delta;
+        prev_angle = angle;
+    }
+    // 周期 2πr / v ≈ 6.28 秒のうち 1 秒ぶん、約 1 ラジアン回っている
+    assert!((swept - 1.0).abs() < 0.05, "swept = {swept}");
+}
+
+#[test]
+fn gravity_field_overrides_constant_gravity() {
+    fn sideways(_: Vec2) -> Vec2 {
+        Vec2::new(10.0, 0.0)
+    }
+    let config = SimulationConfig { gravity_field: Some(GravityField::Custom(sideways)), ..Default::default() };
+    assert_eq!(config.gravity_at(Vec2::new(3.0, 4.0)), Vec2::new(10.0, 0.0));
+
+    let vortex = GravityField::Vortex { center: Vec2::new(0.0, 0.0), strength: 50.0 };
+    let a = vortex.acceleration_at(Vec2::new(10.0, 0.0));
+    assert!((a - Vec2::new(0.0, 5.0)).length() < 1e-12);
+    assert_eq!(SimulationConfig::default().gravity_at(Vec2::new(1.0, 1.0)), Vec2::new(0.0, 270.0));
+}
+
+/// `gravity_ramp_steps` を設定すると、最初のステップでは重力がほぼ働かず、指定したステップ数の後は元の強さになることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn gravity_ramps_in_over_configured_steps() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 100.0),
+        damping: 1.0,
+        gravity_ramp_steps: 10,
+        ..Default::default()
+    });
+    sim.add_chain(&[Vec2::new(0.0, 0.0)], &SoftBodyConfig::default());
+    let dt = 0.1;
+    let mut gains = Vec::new();
+    for _ in 0..15 {
+        let before = sim.particles()[0].vel.y;
+        sim.step(dt);
+        gains.push((sim.particles()[0].vel.y - before) / dt);
+    }
+    assert_eq!(sim.step_count(), 15);
+    assert!(gains[0].abs() < 1e-9, "{gains:?}");
+    assert!((gains[5] - 50.0).abs() < 1e-6, "{gains:?}");
+    assert!(gains.windows(2).all(|w| w[1] >= w[0] - 1e-9), "{gains:?}");
+    for &gain in &gains[10..] {
+        assert!((gain - 100.0).abs() < 1e-6, "{gains:?}");
+    }
+
+    // 無効な dt のステップは数えない
+    sim.step(0.0);
+    assert_eq!(sim.step_count(), 15);
+}
+
+/// 空気抵抗は半径に比例し、同じ質量でも大きい質点ほど終端速度が小さくなることを確認
+#[test]
+fn air_drag_scales_with_radius() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 100.0),
+        damping: 1.0,
+        air_drag: Some(0.01),
+        ..Default::default()
+    });
+    sim.add_chain(&[Vec2::new(0.0, 0.0)], &SoftBodyConfig { particle_radius: 2.0, ..Default::default() });
+    sim.add_chain(&[Vec2::new(100.0, 0.0)], &SoftBodyConfig { particle_radius: 8.0, ..Default::default() });
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+    }
+    let (small, large) = (sim.particles()[0].vel.y, sim.particles()[1].vel.y);
+    // 終端速度 sqrt(g / (c * 2r * w)): 半径 2 で 50、半径 8 で 25
+    assert!((small - 50.0).abs() < 0.05, "{small}");
+    assert!((large - 25.0).abs() < 0.025, "{large}");
+    assert!(large < small * 0.55, "{small} {large}");
+}
@@ -0,0 +1,63 @@
+use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 格子の四隅の行・列が、対応する位置の質点に対応することを確認
+#[test]
+fn grid_index_maps_corners() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    // 先に別のボディを追加し、インデックスがシミュレーション全体のものであることも確かめる
+    sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(-500.0, 0.0), ..Default::default() }).unwrap();
+    let handle = sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(0.0, 0.0),
+        size: Vec2::new(60.0, 40.0),
+        rows: 3,
+        cols: 4,
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(body.grid_dimensions(), Some((3, 4)));
+
+    let pos = |row, col| sim.particles()[body.grid_index(row, col).unwrap()].pos;
+    assert_eq!(pos(0, 0), Vec2::new(-30.0, -20.0));
+    assert_eq!(pos(0, 3), Vec2::new(30.0, -20.0));
+    assert_eq!(pos(2, 0), Vec2::new(-30.0, 20.0));
+    assert_eq!(pos(2, 3), Vec2::new(30.0, 20.0));
+}
+
+/// 範囲外の行・列や、格子でないボディでは `None` になることを確認
+#[test]
+fn grid_index_rejects_out_of_range_and_non_grid_bodies() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let grid = sim.add_soft_body(&SoftBodyConfig { rows: 2, cols: 3, ..Default::default() }).unwrap();
+    let body = sim.soft_body(grid).unwrap();
+    assert_eq!(body.grid_index(2, 0), None);
+    assert_eq!(body.grid_index(0, 3), None);
+    assert!(body.grid_index(1, 2).is_some());
+
+    let chain = sim.add_chain(&[Vec2::new(0.0, 300.0), Vec2::new(50.0, 300.0), Vec2::new(100.0, 300.0)], &SoftBodyConfig::default());
+    let body = sim.soft_body(chain).unwrap();
+    assert_eq!(body.grid_dimensions(), None);
+    assert_eq!(body.grid_index(0, 0), None);
+
+    // 質点を取り除くと格子ではなくなる
+    let removed = sim.soft_body(grid).unwrap().grid_index(0, 0).unwrap();
+    sim.remove_particle(removed).unwrap();
+    assert_eq!(sim.soft_body(grid).unwrap().grid_dimensions(), None);
+}
+
+/// `interior_springs = false` の 4x4 の格子では外周の 12 本のみ、`true` では格子全体の 24 本のバネが生成されることを確認
+#[test]
+fn interior_springs_flag_limits_springs_to_border() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let config = SoftBodyConfig { size: Vec2::new(60.0, 60.0), rows: 4, cols: 4, particle_radius: 5.0, ..Default::default() };
+    let full = sim.add_soft_body(&config).unwrap();
+    let hollow = sim.add_soft_body(&SoftBodyConfig { interior_springs: false, center: Vec2::new(200.0, 0.0), ..config }).unwrap();
+
+    assert_eq!(sim.soft_body(full).unwrap().springs.len(), 24);
+    let body = sim.soft_body(hollow).unwrap();
+    assert_eq!(body.springs.len(), 12);
+    // 内部の質点 (1, 1) はどのバネにもつながらない
+    let inner = body.grid_index(1, 1).unwrap();
+    assert!(body.springs.iter().all(|s| s.p1_index != inner && s.p2_index != inner));
+    assert!(body.shape_constraint.is_some());
+}
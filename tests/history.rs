@@ -0,0 +1,62 @@
+use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 容量5の履歴で10ステップ進めた後に巻き戻すと、記録されたステップの状態が復元されることを確認
+#[test]
+fn step_back_restores_recorded_frames() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 3, ..Default::default() }).unwrap();
+    sim.enable_history(5);
+
+    let mut frames: Vec<Vec<Vec2>> = Vec::new();
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+        frames.push(sim.particles().iter().map(|p| p.pos).collect());
+    }
+    assert_eq!(sim.history_len(), 5);
+    assert_eq!(sim.history_at(0).unwrap().step_count(), 10);
+    assert_eq!(sim.history_at(4).unwrap().step_count(), 6);
+    assert!(sim.history_at(5).is_none());
+
+    // 最も古い記録は6ステップ目の終わり（`frames[5]`）で、それより前は容量を超えて捨てられている
+    let recorded: Vec<Vec2> = sim.history_at(4).unwrap().particles().iter().map(|p| p.pos).collect();
+    assert_eq!(recorded, frames[5]);
+
+    assert!(sim.step_back());
+    let positions: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    assert_eq!(positions, frames[8]);
+    assert_eq!(sim.step_count(), 9);
+
+    // 最も古い記録（6ステップ目）まで戻ると、それ以上は戻れない
+    for _ in 0..3 {
+        assert!(sim.step_back());
+    }
+    let positions: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    assert_eq!(positions, frames[5]);
+    assert!(!sim.step_back());
+    assert_eq!(sim.history_len(), 1);
+
+    // 巻き戻した状態から進め直すと、同じ結果になる
+    sim.step(1.0 / 60.0);
+    let positions: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    assert_eq!(positions, frames[6]);
+}
+
+/// スナップショットから復元すると、その後のステップが保存時と同じ結果になることを確認
+#[test]
+fn restore_snapshot_replays_identically() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig::default()).unwrap();
+    sim.step(1.0 / 60.0);
+    let snapshot = sim.snapshot();
+    for _ in 0..5 {
+        sim.step(1.0 / 60.0);
+    }
+    let expected: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+
+    sim.restore(&snapshot);
+    assert_eq!(sim.step_count(), 1);
+    for _ in 0..5 {
+        sim.step(1.0 / 60.0);
+    }
+    assert_eq!(sim.particles().iter().map(|p| p.pos).collect::<Vec<_>>(), expected);
+}
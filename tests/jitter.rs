@@ -0,0 +1,37 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn spawn(seed: u64, jitter: Scalar) -> Vec<Vec2> {
+    let mut sim = Simulation::new(SimulationConfig { seed, ..Default::default() });
+    sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(80.0, 80.0), rows: 3, cols: 3, jitter, ..Default::default() }).unwrap();
+    sim.add_convex_body(
+        &[Vec2::new(200.0, 0.0), Vec2::new(260.0, 0.0), Vec2::new(230.0, 50.0)],
+        &SoftBodyConfig { jitter, ..Default::default() },
+    ).unwrap();
+    sim.particles().iter().map(|p| p.pos).collect()
+}
+
+/// 同じシードなら同じ位置にずれ、ずれは `jitter` 以内に収まることを確認
+#[test]
+fn jitter_is_reproducible_with_fixed_seed() {
+    let exact = spawn(42, 0.0);
+    let a = spawn(42, 3.0);
+    let b = spawn(42, 3.0);
+    assert_eq!(a, b);
+    assert_ne!(a, spawn(7, 3.0));
+    for (p, q) in a.iter().zip(&exact) {
+        assert!((*p - *q).length() <= 3.0);
+    }
+    assert!(a.iter().zip(&exact).any(|(p, q)| p != q));
+}
+
+/// `jitter = 0` のときは正確なグリッドになることを確認
+#[test]
+fn zero_jitter_yields_exact_grid() {
+    let positions = spawn(42, 0.0);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(positions[i * 3 + j], Vec2::new(-40.0 + 40.0 * j as Scalar, -40.0 + 40.0 * i as Scalar));
+        }
+    }
+    assert_eq!(positions[9..], [Vec2::new(200.0, 0.0), Vec2::new(260.0, 0.0), Vec2::new(230.0, 50.0)]);
+}
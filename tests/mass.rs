@@ -0,0 +1,42 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, SoftBodyHandle, Vec2};
+
+fn center(sim: &Simulation, handle: SoftBodyHandle) -> Vec2 {
+    let positions: Vec<Vec2> = sim.particles_of_body(handle).map(|p| p.pos).collect();
+    positions.iter().fold(Vec2::new(0.0, 0.0), |acc, &p| acc + p) * (1.0 / positions.len() as Scalar)
+}
+
+/// 重ねて置いた同じ形の2つのボディのうち片方を重くすると、軽い方が大きく押しのけられることを確認
+#[test]
+fn heavier_body_displaces_lighter_one() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let body = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 100.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, ..Default::default() };
+    let light = sim.add_soft_body(&body(100.0)).unwrap();
+    let heavy = sim.add_soft_body(&body(136.0)).unwrap();
+    assert!(sim.set_body_mass_scale(heavy, 4.0));
+    assert!(sim.particles_of_body(heavy).all(|p| (p.inv_mass - 0.25).abs() < 1e-12));
+
+    let (light_start, heavy_start) = (center(&sim, light), center(&sim, heavy));
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+    }
+    let light_moved = (center(&sim, light) - light_start).length();
+    let heavy_moved = (center(&sim, heavy) - heavy_start).length();
+    assert!(light_moved > 0.0);
+    assert!(light_moved > 3.0 * heavy_moved, "light {light_moved}, heavy {heavy_moved}");
+}
+
+/// 固定された質点は固定のまま残り、不正な倍率や存在しないボディは拒否されることを確認
+#[test]
+fn mass_scale_keeps_fixed_particles_and_rejects_invalid_input() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let chain = sim.add_chain(&[Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0)], &SoftBodyConfig { pin_first: true, ..Default::default() });
+    assert!(sim.set_body_mass_scale(chain, 2.0));
+    assert_eq!(sim.particles()[0].inv_mass, 0.0);
+    assert!(sim.particles()[0].is_fixed);
+    assert_eq!(sim.particles()[1].inv_mass, 0.5);
+
+    assert!(!sim.set_body_mass_scale(chain, 0.0));
+    assert!(!sim.set_body_mass_scale(chain, Scalar::NAN));
+    assert!(sim.remove_soft_body(chain));
+    assert!(!sim.set_body_mass_scale(chain, 2.0));
+}
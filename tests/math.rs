@@ -0,0 +1,171 @@
+use softbody::core::geometry::{clip_polygon_to_rect, polygon_area, polygon_perimeter};
+use softbody::core::{Line, Mat2, Scalar, Vec2};
+
+fn assert_vec_eq(a: Vec2, b: Vec2) {
+    assert!((a - b).length() < 1e-12, "{a:?} != {b:?}");
+}
+
+#[test]
+fn symmetric_eigen_of_diagonal_matrix() {
+    let m = Mat2::new(Vec2::new(3.0, 0.0), Vec2::new(0.0, 1.0));
+    let (l1, l2, v1, v2) = m.symmetric_eigen();
+    assert_eq!((l1, l2), (3.0, 1.0));
+    assert_vec_eq(v1, Vec2::new(1.0, 0.0));
+    assert_vec_eq(v2, Vec2::new(0.0, 1.0));
+
+    // 大きい方の固有値が先に来る
+    let m = Mat2::new(Vec2::new(1.0, 0.0), Vec2::new(0.0, 3.0));
+    let (l1, l2, v1, _) = m.symmetric_eigen();
+    assert_eq!((l1, l2), (3.0, 1.0));
+    assert_vec_eq(v1, Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn symmetric_eigen_with_off_diagonal_terms() {
+    let m = Mat2::new(Vec2::new(2.0, 1.0), Vec2::new(1.0, 2.0));
+    let (l1, l2, v1, v2) = m.symmetric_eigen();
+    assert!((l1 - 3.0).abs() < 1e-12 && (l2 - 1.0).abs() < 1e-12);
+    let s = std::f64::consts::FRAC_1_SQRT_2 as Scalar;
+    assert_vec_eq(v1, Vec2::new(s, s));
+    assert_vec_eq(v2, Vec2::new(-s, s));
+
+    // M v = λ v かつ正規直交
+    assert_vec_eq(m.mul_vec(v1), v1 * l1);
+    assert_vec_eq(m.mul_vec(v2), v2 * l2);
+    assert!(Vec2::dot(v1, v2).abs() < 1e-12);
+}
+
+#[test]
+fn midpoint_of_two_points() {
+    assert_eq!(Vec2::mid(Vec2::new(1.0, 2.0), Vec2::new(5.0, -4.0)), Vec2::new(3.0, -1.0));
+}
+
+#[test]
+fn perimeter_of_unit_square() {
+    let square = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+    ];
+    assert_eq!(polygon_perimeter(&square), 4.0);
+    assert_eq!(polygon_perimeter(&[]), 0.0);
+}
+
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn unit_diagonal_line_helpers() {
+    let h = std::f64::consts::FRAC_1_SQRT_2 as Scalar;
+    let line = Line::new(Vec2::new(1.0, 1.0), Vec2::new(1.0 + h, 1.0 + h));
+    assert!((line.length() - 1.0).abs() < 1e-12);
+    assert_vec_eq(line.direction(), Vec2::new(h, h));
+    assert_vec_eq(line.point_at(0.0), line.start);
+    assert_vec_eq(line.point_at(1.0), line.end);
+    assert_vec_eq(line.point_at(0.5), Vec2::mid(line.start, line.end));
+    assert_eq!(Line::new(Vec2::new(2.0, 2.0), Vec2::new(2.0, 2.0)).direction(), Vec2::new(0.0, 0.0));
+}
+
+/// 線分外の点からの最近接点が、垂線の足または端点になることを確認
+#[test]
+fn closest_point_on_line_from_off_line_query() {
+    let line = Line::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+    assert_vec_eq(line.closest_point(Vec2::new(0.0, 10.0)), Vec2::new(5.0, 5.0));
+    assert_vec_eq(line.closest_point(Vec2::new(-5.0, -1.0)), line.start);
+    assert_vec_eq(line.closest_point(Vec2::new(20.0, 14.0)), line.end);
+}
+
+/// タプルとの相互変換。`f32` から `f64` への変換は誤差なく往復できることを確認
+#[test]
+#[cfg(not(feature = "f32"))]
+fn vec2_converts_to_and_from_tuples() {
+    let v = Vec2::from((1.5, -2.25));
+    assert_eq!(v, Vec2::new(1.5, -2.25));
+    assert_eq!(<(f64, f64)>::from(v), (1.5, -2.25));
+
+    let p32 = (0.1_f32, 3.7_f32);
+    let v = Vec2::from(p32);
+    let (x, y): (f64, f64) = v.into();
+    assert_eq!((x as f32, y as f32), p32);
+    // f64 の 0.1 とは f32 の丸め誤差 (約 1.5e-9) だけ異なる
+    assert!((x - 0.1).abs() < 1e-8);
+}
+
+#[test]
+fn vec2_and_mat2_display_respects_precision() {
+    let v = Vec2::new(1.0, -2.34567);
+    assert_eq!(v.to_string(), "(1.000, -2.346)");
+    assert_eq!(format!("{v:.1}"), "(1.0, -2.3)");
+    assert_eq!(format!("{v:.0}"), "(1, -2)");
+
+    // 列 c1 = (1, 3), c2 = (2, 4) の行列は行ごとに [[1, 2], [3, 4]]
+    let m = Mat2::new(Vec2::new(1.0, 3.0), Vec2::new(2.0, 4.5));
+    assert_eq!(m.to_string(), "[[1.000, 2.000], [3.000, 4.500]]");
+    assert_eq!(format!("{m:.2}"), "[[1.00, 2.00], [3.00, 4.50]]");
+}
+
+/// `move_towards` は範囲内なら目標にちょうど到達し、範囲外なら `max_delta` だけ進むことを確認
+#[test]
+fn move_towards_snaps_within_range() {
+    let start = Vec2::new(0.0, 0.0);
+    let target = Vec2::new(3.0, 4.0);
+    assert_eq!(start.move_towards(target, 5.0), target);
+    assert_eq!(start.move_towards(target, 100.0), target);
+    assert_vec_eq(start.move_towards(target, 2.5), Vec2::new(1.5, 2.0));
+    assert_eq!(start.move_towards(target, -1.0), start);
+    assert_eq!(target.move_towards(target, 0.0), target);
+    // 始点と目標が一致していても、負の `max_delta` で NaN にならない
+    assert_eq!(target.move_towards(target, -1.0), target);
+}
+
+/// 大きな `max_delta` で繰り返し近づけても、目標を行き過ぎないことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn move_towards_never_overshoots() {
+    let target = Vec2::new(-7.0, 11.0);
+    let mut pos = Vec2::new(20.0, -3.0);
+    let mut previous = (target - pos).length();
+    for _ in 0..20 {
+        pos = pos.move_towards(target, 4.0);
+        let remaining = (target - pos).length();
+        assert!(remaining <= previous);
+        // 目標を通り越さず、常に始点から目標への線分上にある
+        assert!(Vec2::cross(pos - target, Vec2::new(20.0, -3.0) - target).abs() < 1e-9);
+        previous = remaining;
+    }
+    assert_eq!(pos, target);
+}
+
+/// 矩形から一部はみ出した三角形は矩形の内側の部分だけが残り、内側の三角形は変わらず、外側の三角形は空になることを確認
+#[test]
+fn clip_polygon_to_rect_cases() {
+    let (min, max) = (Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+    let inside = [Vec2::new(1.0, 1.0), Vec2::new(9.0, 2.0), Vec2::new(5.0, 8.0)];
+    assert_eq!(clip_polygon_to_rect(&inside, min, max), inside.to_vec());
+    // min と max を入れ替えても同じ
+    assert_eq!(clip_polygon_to_rect(&inside, max, min), inside.to_vec());
+
+    let outside = [Vec2::new(20.0, 20.0), Vec2::new(30.0, 20.0), Vec2::new(25.0, 30.0)];
+    assert!(clip_polygon_to_rect(&outside, min, max).is_empty());
+
+    // 右側にはみ出した直角三角形は台形になる
+    let partial = [Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(0.0, 10.0)];
+    let clipped = clip_polygon_to_rect(&partial, min, max);
+    assert_eq!(clipped.len(), 4);
+    assert!(clipped.iter().all(|p| (0.0..=10.0).contains(&p.x) && (0.0..=10.0).contains(&p.y)));
+    assert!((polygon_area(&clipped) - 75.0).abs() < 1e-9, "{clipped:?}");
+    for expected in [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 5.0), Vec2::new(0.0, 10.0)] {
+        assert!(clipped.iter().any(|&p| (p - expected).length() < 1e-9), "{clipped:?}");
+    }
+}
+
+/// 通常の大きさのベクトルで `length_fast` が `length` とほぼ一致することを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn length_fast_matches_length() {
+    for &(x, y) in &[(3.0, 4.0), (0.0, 0.0), (-1e-3, 2e-3), (123.456, -789.012), (1e6, 1e6), (0.1, 0.2)] {
+        let v = Vec2::new(x, y);
+        assert!((v.length_fast() - v.length()).abs() <= 1e-12 * v.length().max(1.0), "{v:?}");
+    }
+    assert_eq!(Vec2::new(3.0, 4.0).length_fast(), 5.0);
+}
@@ -0,0 +1,71 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 隣接する2つのグリッドを融合すると、全質点にまたがる形状維持拘束を持つ1つのボディになることを確認
+#[test]
+fn merging_adjacent_grids_yields_single_body() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let grid = |x: Scalar| SoftBodyConfig {
+        center: Vec2::new(x, 0.0),
+        size: Vec2::new(50.0, 50.0),
+        rows: 3,
+        cols: 3,
+        particle_radius: 5.0,
+        ..Default::default()
+    };
+    // 左のグリッドの右端 (x = 25) と右のグリッドの左端 (x = 50) が 25 離れて並ぶ
+    let a = sim.add_soft_body(&grid(0.0)).unwrap();
+    let b = sim.add_soft_body(&grid(75.0)).unwrap();
+    let springs_before = sim.springs().count();
+
+    let merged = sim.merge_bodies(a, b, 26.0).unwrap();
+
+    assert_eq!(sim.soft_body_count(), 1);
+    assert!(sim.soft_body(a).is_none() && sim.soft_body(b).is_none());
+    let body = sim.soft_body(merged).unwrap();
+    assert_eq!(body.particle_indices.len(), 18);
+    // 境界を挟んで向かい合う各行の質点の組が接合される
+    assert_eq!(body.springs.len(), springs_before + 3);
+    let sc = body.shape_constraint.as_ref().unwrap();
+    let mut spanned = sc.particle_indices.clone();
+    spanned.sort();
+    assert_eq!(spanned, (0..18).collect::<Vec<_>>());
+}
+
+#[test]
+fn merging_a_body_with_itself_returns_none() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_soft_body(&SoftBodyConfig::default()).unwrap();
+    assert!(sim.merge_bodies(a, a, 10.0).is_none());
+    assert_eq!(sim.soft_body_count(), 1);
+}
+
+/// 外周を持つ2つのボディを融合すると外周を持たないボディになり、外周同士の衝突で
+/// 2つの元の外周の間に置かれたボディが押し出されないことを確認
+#[test]
+fn merged_outlined_bodies_have_no_outline() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), outline_collisions: true, ..Default::default() });
+    let square = |x: Scalar, size: Scalar| [
+        Vec2::new(x, 0.0),
+        Vec2::new(x + size, 0.0),
+        Vec2::new(x + size, size),
+        Vec2::new(x, size),
+    ];
+    let config = SoftBodyConfig { particle_radius: 2.0, ..Default::default() };
+    let a = sim.add_convex_body(&square(0.0, 40.0), &config).unwrap();
+    let b = sim.add_convex_body(&square(100.0, 40.0), &config).unwrap();
+    let between = sim.add_convex_body(&square(60.0, 20.0), &config).unwrap();
+
+    let merged = sim.merge_bodies(a, b, 0.0).unwrap();
+    let body = sim.soft_body(merged).unwrap();
+    assert!(body.outline_wires.is_none());
+    assert!(body.edge_normals(sim.particles()).is_empty());
+    assert!(body.vertex_normals(sim.particles()).is_empty());
+
+    let before: Vec<Vec2> = sim.particles_of_body(between).map(|p| p.pos).collect();
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+    }
+    for (p, start) in sim.particles_of_body(between).zip(before) {
+        assert!((p.pos - start).length() < 1e-9, "{:?} moved from {start:?}", p.pos);
+    }
+}
@@ -0,0 +1,95 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn centroid(points: &[Vec2]) -> Vec2 {
+    points.iter().fold(Vec2::new(0.0, 0.0), |acc, &p| acc + p) * (1.0 / points.len() as Scalar)
+}
+
+/// 反時計回りの正方形で、各辺の法線が重心から離れる向きの単位ベクトルになることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn edge_normals_of_ccw_square_point_away_from_centroid() {
+    let square = [Vec2::new(0.0, 0.0), Vec2::new(40.0, 0.0), Vec2::new(40.0, 40.0), Vec2::new(0.0, 40.0)];
+    let c = centroid(&square);
+    for points in [square.to_vec(), square.iter().rev().copied().collect()] {
+        let mut sim = Simulation::new(SimulationConfig::default());
+        let handle = sim.add_convex_body(&points, &SoftBodyConfig::default()).unwrap();
+        let body = sim.soft_body(handle).unwrap();
+        let wires = body.outline_wires.as_ref().unwrap();
+
+        let normals = body.edge_normals(sim.particles());
+        assert_eq!(normals.len(), 4);
+        for (&(a, b), n) in wires.iter().zip(&normals) {
+            let mid = Vec2::mid(sim.particles()[a].pos, sim.particles()[b].pos);
+            assert!((n.length() - 1.0).abs() < 1e-12);
+            assert!(Vec2::dot(*n, mid - c) > 0.0);
+            // 辺に垂直
+            assert!(Vec2::dot(*n, sim.particles()[b].pos - sim.particles()[a].pos).abs() < 1e-9);
+        }
+
+        let vertex_normals = body.vertex_normals(sim.particles());
+        for (&(a, _), n) in wires.iter().zip(&vertex_normals) {
+            let outward = (sim.particles()[a].pos - c).normalize();
+            assert!((*n - outward).length() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn body_without_outline_has_no_normals() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let handle = sim.add_soft_body(&SoftBodyConfig::default()).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert!(body.edge_normals(sim.particles()).is_empty());
+    assert!(body.vertex_normals(sim.particles()).is_empty());
+}
+
+/// `iter_outline_edges` の辺の数が全ボディの `outline_wires` の合計と一致し、各辺が所属ボディの外周の頂点を結ぶことを確認
+#[test]
+fn outline_edges_cover_all_bodies() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let square = [Vec2::new(0.0, 0.0), Vec2::new(60.0, 0.0), Vec2::new(60.0, 60.0), Vec2::new(0.0, 60.0)];
+    let triangle = [Vec2::new(200.0, 0.0), Vec2::new(260.0, 0.0), Vec2::new(230.0, 50.0)];
+    let a = sim.add_convex_body(&square, &SoftBodyConfig::default()).unwrap();
+    sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(100.0, 200.0), rows: 3, cols: 3, ..Default::default() }).unwrap();
+    let b = sim.add_convex_body(&triangle, &SoftBodyConfig::default()).unwrap();
+
+    let edges: Vec<_> = sim.iter_outline_edges().collect();
+    let wires: usize = sim.soft_bodies().iter().filter_map(|sb| sb.outline_wires.as_ref()).map(Vec::len).sum();
+    assert_eq!(edges.len(), wires);
+    assert_eq!(edges.len(), 7);
+
+    assert_eq!(edges[0], (a, square[0], square[1]));
+    assert_eq!(edges[3], (a, square[3], square[0]));
+    assert_eq!(edges[4], (b, triangle[0], triangle[1]));
+    assert!(edges[4..].iter().all(|&(handle, _, _)| handle == b));
+}
+
+/// 形状維持拘束を無効にした格子でも `outline = true` なら外周ワイヤーが格子の外周を一周し、法線が外を向くことを確認
+#[test]
+fn grid_body_with_outline_has_border_ring() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let config = SoftBodyConfig { rows: 3, cols: 4, shape_stiffness: 0.0, outline: true, ..Default::default() };
+    let handle = sim.add_soft_body(&config).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert!(body.shape_constraint.is_none());
+
+    let wires = body.outline_wires.as_ref().expect("outline wires");
+    // 3x4 の格子の外周の質点は 12 - 2 = 10 個
+    assert_eq!(wires.len(), 10);
+    assert!(wires.windows(2).all(|w| w[0].1 == w[1].0));
+    assert_eq!(wires.last().unwrap().1, wires[0].0);
+    assert_eq!(wires[0].0, body.grid_index(0, 0).unwrap());
+    assert!(!wires.iter().any(|&(a, b)| [a, b].contains(&body.grid_index(1, 1).unwrap())));
+
+    let points = body.outline_points(sim.particles());
+    let center = centroid(&points);
+    for (normal, &(a, b)) in body.edge_normals(sim.particles()).iter().zip(wires) {
+        let midpoint = (sim.particles()[a].pos + sim.particles()[b].pos) * 0.5;
+        assert!(Vec2::dot(*normal, midpoint - center) > 0.0);
+    }
+
+    // 1列の格子では外周を作らない
+    let column = SoftBodyConfig { center: Vec2::new(300.0, 0.0), cols: 1, outline: true, ..Default::default() };
+    let handle = sim.add_soft_body(&column).unwrap();
+    assert!(sim.soft_body(handle).unwrap().outline_wires.is_none());
+}
@@ -0,0 +1,50 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 固定した正方形の上に正方形を置き、落ち着いた後の上の正方形の下辺と下の正方形の上辺の隙間を返します
+fn resting_gap(outline_collisions: bool) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig { outline_collisions, ..Default::default() });
+    let square = |x: Scalar, y: Scalar| [Vec2::new(x, y), Vec2::new(x + 60.0, y), Vec2::new(x + 60.0, y + 60.0), Vec2::new(x, y + 60.0)];
+    let config = SoftBodyConfig { particle_radius: 4.0, stiffness: 0.8, shape_stiffness: 0.8, ..Default::default() };
+    let bottom = sim.add_convex_body(&square(0.0, 200.0), &SoftBodyConfig { is_fixed: true, ..config.clone() }).unwrap();
+    // 上の正方形は少し横にずらし、角の質点どうしが重ならないようにする
+    let top = sim.add_convex_body(&square(20.0, 130.0), &config).unwrap();
+    for _ in 0..300 {
+        sim.step(1.0 / 60.0);
+    }
+    let bottom_top_edge = sim.soft_body(bottom).unwrap().outline_points(sim.particles())[0].y;
+    let top_points = sim.soft_body(top).unwrap().outline_points(sim.particles());
+    let top_bottom_edge = (top_points[2].y + top_points[3].y) * 0.5;
+    bottom_top_edge - top_bottom_edge
+}
+
+/// 外周同士の衝突では、上の正方形の下辺が下の正方形の上辺に隙間なく接することを確認
+#[test]
+fn stacked_squares_rest_flush_with_outline_collisions() {
+    let gap = resting_gap(true);
+    assert!(gap.abs() < 0.5, "gap {gap}");
+}
+
+/// 外周同士の衝突が無効の場合は、上の正方形が下の正方形の辺の上をすり抜けて落ちる（質点の間に沈む）ことを確認
+#[test]
+fn stacked_squares_sink_without_outline_collisions() {
+    let gap = resting_gap(false);
+    assert!(gap < -10.0, "gap {gap}");
+}
+
+/// 外周を持つグリッドの内部の質点は、外周同士の衝突が有効でも他のボディの質点と半径で衝突することを確認
+#[test]
+fn interior_particles_of_outlined_grid_still_collide() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), outline_collisions: true, ..Default::default() });
+    // 間隔 10 の 3x3 グリッドで、内部の質点は中央 (0, 0) の1つだけ
+    let grid = sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(20.0, 20.0), rows: 3, cols: 3, particle_radius: 4.5, outline: true, ..Default::default() }).unwrap();
+    let center = sim.soft_body(grid).unwrap().particle_indices[4];
+    // 三角形の頂点 (12, 0) はグリッドの外周の外側にあるが、中央の質点とは半径の和 14.5 より近い
+    let triangle = [Vec2::new(12.0, 0.0), Vec2::new(60.0, -30.0), Vec2::new(60.0, 30.0)];
+    let other = sim.add_convex_body(&triangle, &SoftBodyConfig { particle_radius: 10.0, ..Default::default() }).unwrap();
+    let tip = sim.soft_body(other).unwrap().particle_indices[0];
+
+    sim.step(1.0 / 60.0);
+    assert_eq!(sim.contacts(), vec![(grid, other)]);
+    let d = (sim.particles()[tip].pos - sim.particles()[center].pos).length();
+    assert!(d > 13.0, "d = {d}");
+}
@@ -0,0 +1,49 @@
+use softbody::core::geometry::convex_hull;
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn square(x: Scalar, y: Scalar, half: Scalar) -> Vec<Vec2> {
+    vec![
+        Vec2::new(x - half, y - half),
+        Vec2::new(x + half, y - half),
+        Vec2::new(x + half, y + half),
+        Vec2::new(x - half, y + half),
+    ]
+}
+
+#[test]
+fn convex_hull_drops_interior_and_collinear_points() {
+    let mut points = square(0.0, 0.0, 10.0);
+    points.extend([Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(3.0, -2.0)]);
+    let hull = convex_hull(&points);
+    assert_eq!(hull, vec![Vec2::new(-10.0, -10.0), Vec2::new(10.0, -10.0), Vec2::new(10.0, 10.0), Vec2::new(-10.0, 10.0)]);
+}
+
+/// 明らかに重なった2つのボディでは正の貫通深さが返ることを確認
+#[test]
+fn overlapping_bodies_report_penetration_depth() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_convex_body(&square(0.0, 0.0, 20.0), &SoftBodyConfig::default()).unwrap();
+    let b = sim.add_convex_body(&square(30.0, 5.0, 20.0), &SoftBodyConfig::default()).unwrap();
+    // 格子状のボディ（外周なし）は質点の凸包で判定される
+    let grid = sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(-15.0, 0.0), size: Vec2::new(20.0, 20.0), rows: 2, cols: 2, particle_radius: 4.0, ..Default::default() }).unwrap();
+    let before = sim.particles().to_vec();
+
+    let depth = sim.bodies_overlap(a, b).unwrap();
+    assert!((depth - 10.0).abs() < 1e-9, "depth = {depth}");
+    assert_eq!(sim.bodies_overlap(b, a), Some(depth));
+    assert!((sim.bodies_overlap(a, grid).unwrap() - 15.0).abs() < 1e-9);
+    assert_eq!(sim.particles(), &before[..]);
+}
+
+#[test]
+fn separated_bodies_do_not_overlap() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_convex_body(&square(0.0, 0.0, 20.0), &SoftBodyConfig::default()).unwrap();
+    let b = sim.add_convex_body(&square(50.0, 0.0, 20.0), &SoftBodyConfig::default()).unwrap();
+    // 軸平行な範囲は重なるが、斜めの辺で分離される三角形
+    let c = sim.add_convex_body(&[Vec2::new(15.0, 30.0), Vec2::new(30.0, 15.0), Vec2::new(30.0, 30.0)], &SoftBodyConfig::default()).unwrap();
+
+    assert_eq!(sim.bodies_overlap(a, b), None);
+    assert_eq!(sim.bodies_overlap(a, c), None);
+    assert_eq!(sim.bodies_overlap(a, a), None);
+}
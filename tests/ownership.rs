@@ -0,0 +1,102 @@
+use softbody::core::{Line, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn grid(x: Scalar) -> SoftBodyConfig {
+    SoftBodyConfig {
+        center: Vec2::new(x, 0.0),
+        size: Vec2::new(40.0, 40.0),
+        rows: 3,
+        cols: 3,
+        particle_radius: 5.0,
+        ..Default::default()
+    }
+}
+
+/// 各質点が、それを生成したボディに対応付けられることを確認
+#[test]
+fn particles_map_to_their_body() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_soft_body(&grid(0.0)).unwrap();
+    let b = sim.add_soft_body(&grid(100.0)).unwrap();
+    let ring: Vec<Vec2> = (0..6)
+        .map(|i| {
+            let t = i as Scalar / 6.0 * (std::f64::consts::TAU as Scalar);
+            Vec2::new(300.0 + 40.0 * t.cos(), 40.0 * t.sin())
+        })
+        .collect();
+    let c = sim.add_convex_body(&ring, &SoftBodyConfig { shell_layers: 2, particle_radius: 3.0, ..Default::default() }).unwrap();
+
+    for handle in [a, b, c] {
+        for &i in &sim.soft_body(handle).unwrap().particle_indices {
+            assert_eq!(sim.body_of_particle(i), Some(handle));
+        }
+    }
+    assert_eq!(sim.body_of_particle(sim.particle_count()), None);
+}
+
+/// ボディを取り除いた後も、詰め直された質点が正しいボディに対応付けられることを確認
+#[test]
+fn owner_map_follows_body_removal() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_soft_body(&grid(0.0)).unwrap();
+    let b = sim.add_soft_body(&grid(100.0)).unwrap();
+    let c = sim.add_soft_body(&grid(200.0)).unwrap();
+
+    assert!(sim.remove_soft_body(b));
+    assert_eq!(sim.particle_count(), 18);
+    for handle in [a, c] {
+        for &i in &sim.soft_body(handle).unwrap().particle_indices {
+            assert_eq!(sim.body_of_particle(i), Some(handle));
+        }
+    }
+    assert_eq!(sim.body_of_particle(18), None);
+
+    // 結合すると、元のボディの質点は新しいボディに対応付けられる
+    let merged = sim.merge_bodies(a, c, 0.0).unwrap();
+    assert!((0..sim.particle_count()).all(|i| sim.body_of_particle(i) == Some(merged)));
+}
+
+/// ボディの質点の列挙から求めた重心が `SoftBody::centroid` と一致し、可変の列挙がそのボディの質点だけを動かすことを確認
+#[test]
+fn particles_of_body_walks_only_that_body() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let a = sim.add_soft_body(&grid(0.0)).unwrap();
+    let b = sim.add_soft_body(&grid(100.0)).unwrap();
+    // 質点を取り除いてインデックスを飛び飛びにする
+    sim.remove_particle(sim.soft_body(b).unwrap().particle_indices[4]).unwrap();
+
+    let (sum, count) = sim.particles_of_body(b).fold((Vec2::new(0.0, 0.0), 0), |(s, n), p| (s + p.pos, n + 1));
+    assert_eq!(count, 8);
+    let centroid = sim.soft_body(b).unwrap().centroid(sim.particles());
+    assert!((sum * (1.0 / count as Scalar) - centroid).length() < 1e-9);
+
+    for p in sim.particles_of_body_mut(b) {
+        p.pos.y += 10.0;
+    }
+    assert!(sim.particles_of_body(a).all(|p| p.pos.y.abs() <= 20.0));
+    assert!(sim.particles_of_body(b).all(|p| p.pos.y >= -10.0 && p.pos.y <= 30.0));
+    let moved = sim.soft_body(b).unwrap().centroid(sim.particles());
+    assert!((moved - centroid - Vec2::new(0.0, 10.0)).length() < 1e-9);
+
+    sim.remove_soft_body(a);
+    assert_eq!(sim.particles_of_body(a).count(), 0);
+    assert_eq!(sim.particles_of_body_mut(a).count(), 0);
+}
+
+/// `particle_indices` が昇順でないボディ（切断で生成したボディ）でも、可変の列挙がボディの質点を
+/// ちょうど1回ずつ返すことを確認
+#[test]
+fn particles_of_body_mut_handles_unsorted_indices() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let square = [Vec2::new(0.0, 0.0), Vec2::new(100.0, 0.0), Vec2::new(100.0, 100.0), Vec2::new(0.0, 100.0)];
+    let body = sim.add_convex_body(&square, &SoftBodyConfig::default()).unwrap();
+    let (_, b) = sim.slice_body(body, Line::new(Vec2::new(50.0, -10.0), Vec2::new(50.0, 110.0))).unwrap();
+    let indices = sim.soft_body(b).unwrap().particle_indices.clone();
+    assert!(!indices.is_sorted());
+
+    let before: Vec<Scalar> = sim.particles().iter().map(|p| p.radius).collect();
+    assert_eq!(sim.particles_of_body_mut(b).map(|p| p.radius += 1.0).count(), indices.len());
+    for (i, p) in sim.particles().iter().enumerate() {
+        let grown = if indices.contains(&i) { 1.0 } else { 0.0 };
+        assert_eq!(p.radius, before[i] + grown, "{i}");
+    }
+}
@@ -0,0 +1,82 @@
+use softbody::core::{Particle, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+#[test]
+fn kinetic_energy_and_momentum_of_moving_particle() {
+    let mut p = Particle::new(0.0, 0.0);
+    p.vel = Vec2::new(3.0, 4.0);
+    assert_eq!(p.kinetic_energy(), 12.5);
+    assert_eq!(p.momentum(), Vec2::new(3.0, 4.0));
+
+    // 質量 2 の質点
+    p.inv_mass = 0.5;
+    assert_eq!(p.kinetic_energy(), 25.0);
+    assert_eq!(p.momentum(), Vec2::new(6.0, 8.0));
+}
+
+#[test]
+fn fixed_particle_has_no_energy_or_momentum() {
+    let mut p = Particle::new(0.0, 0.0);
+    p.is_fixed = true;
+    p.inv_mass = 0.0;
+    p.vel = Vec2::new(3.0, 4.0);
+    assert_eq!(p.kinetic_energy(), 0.0);
+    assert_eq!(p.momentum(), Vec2::new(0.0, 0.0));
+}
+
+fn drifting_particle() -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, ..Default::default() });
+    sim.add_chain(&[Vec2::new(0.0, 0.0)], &SoftBodyConfig::default());
+    sim.particles[0].vel = Vec2::new(50.0, 0.0);
+    sim.step(1.0 / 60.0);
+    sim
+}
+
+/// 動いている質点を `teleport` で移動させても、次のステップで移動量が速度にならないことを確認
+#[test]
+fn teleport_leaves_no_derived_velocity() {
+    let mut sim = drifting_particle();
+    sim.particles[0].teleport(Vec2::new(300.0, 200.0));
+    sim.step(1.0 / 60.0);
+    let p = &sim.particles()[0];
+    assert_eq!(p.pos, Vec2::new(300.0, 200.0));
+    assert_eq!(p.pos - p.prev_pos, Vec2::new(0.0, 0.0));
+    assert_eq!(p.vel, Vec2::new(0.0, 0.0));
+}
+
+/// `set_velocity` で設定した速度が、ステップ後の速度と `pos - prev_pos` の両方に現れることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn set_velocity_is_recovered_by_verlet() {
+    let dt = 1.0 / 60.0;
+    let mut sim = drifting_particle();
+    let v = Vec2::new(-30.0, 12.0);
+    sim.particles[0].set_velocity(v, dt);
+    let p = &sim.particles()[0];
+    assert!(((p.pos - p.prev_pos) * (1.0 / dt) - v).length() < 1e-9);
+
+    sim.step(dt);
+    let p = &sim.particles()[0];
+    assert!((p.vel - v).length() < 1e-9, "{:?}", p.vel);
+    assert!(((p.pos - p.prev_pos) * (1.0 / dt) - v).length() < 1e-9);
+}
+
+/// `apply_to_particles` で全質点の速度を `0` にでき、渡されるインデックスがその質点のものであることを確認
+#[test]
+fn apply_to_particles_visits_every_particle_with_its_index() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 4, ..Default::default() }).unwrap();
+    for _ in 0..5 {
+        sim.step(1.0 / 60.0);
+    }
+    assert!(sim.particles().iter().any(|p| p.vel.length() > 0.0));
+    let positions: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+
+    let mut visited = Vec::new();
+    sim.apply_to_particles(|i, p| {
+        assert_eq!(p.pos, positions[i]);
+        visited.push(i);
+        p.vel = Vec2::new(0.0, 0.0);
+    });
+    assert_eq!(visited, (0..sim.particle_count()).collect::<Vec<_>>());
+    assert!(sim.particles().iter().all(|p| p.vel == Vec2::new(0.0, 0.0)));
+}
@@ -0,0 +1,112 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn config(bias: Scalar) -> SimulationConfig {
+    SimulationConfig { gravity: Vec2::new(0.0, 0.0), penetration_bias: bias, ..Default::default() }
+}
+
+fn overlap(sim: &Simulation) -> Scalar {
+    let (a, b) = (&sim.particles()[0], &sim.particles()[1]);
+    a.radius + b.radius - (a.pos - b.pos).length()
+}
+
+fn add_particle(sim: &mut Simulation, pos: Vec2) {
+    sim.add_chain(&[pos], &SoftBodyConfig { particle_radius: 10.0, ..Default::default() });
+}
+
+/// `penetration_bias < 1` では、質点同士の深い重なりが1ステップで解消されず、数ステップかけて解消されることを確認
+#[test]
+fn deep_particle_overlap_resolves_gradually_with_bias() {
+    let mut instant = Simulation::new(config(1.0));
+    let mut gradual = Simulation::new(config(0.01));
+    for sim in [&mut instant, &mut gradual] {
+        add_particle(sim, Vec2::new(100.0, 100.0));
+        add_particle(sim, Vec2::new(104.0, 100.0));
+    }
+    instant.step(1.0 / 60.0);
+    gradual.step(1.0 / 60.0);
+    assert!(overlap(&instant) < 1e-9);
+    assert!(overlap(&gradual) > 10.0, "{}", overlap(&gradual));
+
+    // 重なりは単調に減り、数ステップかけて解消される
+    let mut steps = 1;
+    while overlap(&gradual) > 1e-9 {
+        let previous = overlap(&gradual);
+        gradual.step(1.0 / 60.0);
+        assert!(overlap(&gradual) < previous);
+        steps += 1;
+        assert!(steps < 30);
+    }
+    assert!(steps >= 3, "{steps}");
+}
+
+/// 境界へのめり込みも `penetration_bias` に従って徐々に解消され、`penetration_slop` 以内の重なりは残ることを確認
+#[test]
+fn boundary_penetration_respects_bias_and_slop() {
+    let floor = |bias: Scalar, slop: Scalar, y: Scalar| {
+        let mut sim = Simulation::new(SimulationConfig {
+            bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))),
+            penetration_slop: slop,
+            ..config(bias)
+        });
+        add_particle(&mut sim, Vec2::new(200.0, y));
+        sim
+    };
+    let depth = |sim: &Simulation| sim.particles()[0].pos.y + 10.0 - 300.0;
+
+    let mut gradual = floor(0.01, 0.0, 305.0);
+    gradual.step(1.0 / 60.0);
+    assert!(depth(&gradual) > 10.0, "{}", depth(&gradual));
+    for _ in 0..2 {
+        gradual.step(1.0 / 60.0);
+    }
+    assert!(depth(&gradual) > 0.0, "{}", depth(&gradual));
+    for _ in 0..30 {
+        gradual.step(1.0 / 60.0);
+    }
+    assert!(depth(&gradual) < 1e-9, "{}", depth(&gradual));
+
+    // 重力で床に押し付けられた質点は、slop の深さまで沈んで止まる
+    let mut sloppy = floor(1.0, 2.0, 290.0);
+    sloppy.config_mut().gravity = Vec2::new(0.0, 980.0);
+    for _ in 0..120 {
+        sloppy.step(1.0 / 60.0);
+    }
+    assert!((depth(&sloppy) - 2.0).abs() < 1e-6, "{}", depth(&sloppy));
+}
+
+/// `penetration_slop` 以内の重なりは補正されないが、衝突イベントとボディ間の接触としては記録されることを確認
+#[test]
+fn contacts_within_slop_are_recorded_without_correction() {
+    let mut sim = Simulation::new(SimulationConfig {
+        penetration_slop: 2.0,
+        collision_event_threshold: Some(0.0),
+        ..config(1.0)
+    });
+    add_particle(&mut sim, Vec2::new(100.0, 100.0));
+    add_particle(&mut sim, Vec2::new(119.0, 100.0));
+    sim.step(1.0 / 60.0);
+
+    assert!((overlap(&sim) - 1.0).abs() < 1e-6, "{}", overlap(&sim));
+    let events = sim.collision_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].a, events[0].b), (0, 1));
+    assert_eq!(events[0].impulse, 0.0);
+    assert_eq!(sim.contacts().len(), 1);
+}
+
+/// 範囲外や NaN の `penetration_bias` は `[0, 1]` に収められ、位置が発散したり NaN になったりしないことを確認
+#[test]
+fn out_of_range_penetration_bias_is_clamped() {
+    for (bias, resolved) in [(-1.0, false), (Scalar::NAN, true), (5.0, true)] {
+        let mut sim = Simulation::new(config(bias));
+        add_particle(&mut sim, Vec2::new(100.0, 100.0));
+        add_particle(&mut sim, Vec2::new(104.0, 100.0));
+        sim.step(1.0 / 60.0);
+        assert!(sim.particles().iter().all(|p| p.pos.x.is_finite() && p.pos.y.is_finite()), "{bias}");
+        if resolved {
+            assert!(overlap(&sim).abs() < 1e-4, "{bias}: {}", overlap(&sim));
+        } else {
+            assert!((overlap(&sim) - 16.0).abs() < 1e-4, "{bias}: {}", overlap(&sim));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use softbody::core::geometry::{find_all_intersections, find_nearest_segment, point_in_polygon};
+use softbody::core::{Line, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn square(x: Scalar, y: Scalar, half: Scalar) -> Vec<Vec2> {
+    vec![
+        Vec2::new(x - half, y - half),
+        Vec2::new(x + half, y - half),
+        Vec2::new(x + half, y + half),
+        Vec2::new(x - half, y + half),
+    ]
+}
+
+#[test]
+fn point_in_polygon_uses_even_odd_rule() {
+    let sq = square(0.0, 0.0, 10.0);
+    assert!(point_in_polygon(Vec2::new(0.0, 0.0), &sq));
+    assert!(point_in_polygon(Vec2::new(9.0, -9.0), &sq));
+    assert!(!point_in_polygon(Vec2::new(11.0, 0.0), &sq));
+    let mut reversed = sq.clone();
+    reversed.reverse();
+    assert!(point_in_polygon(Vec2::new(0.0, 0.0), &reversed));
+}
+
+/// 2つのボディのうち一方の内側をクリックすると、そのボディのハンドルが返ることを確認
+#[test]
+fn clicking_inside_a_body_selects_it() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let config = SoftBodyConfig { particle_radius: 4.0, ..Default::default() };
+    let left = sim.add_convex_body(&square(0.0, 0.0, 40.0), &config).unwrap();
+    let right = sim.add_convex_body(&square(200.0, 0.0, 40.0), &config).unwrap();
+
+    assert_eq!(sim.nearest_body(Vec2::new(5.0, -10.0)), Some(left));
+    assert_eq!(sim.nearest_body(Vec2::new(210.0, 30.0)), Some(right));
+    // 外周のわずかに外側でも、辺の太さの内側なら選択される
+    assert_eq!(sim.nearest_body(Vec2::new(242.0, 0.0)), Some(right));
+}
+
+/// 外周の無いグリッドのボディは、質点やバネから離れたセルの中をクリックしても選択されることを確認
+#[test]
+fn clicking_inside_grid_cell_selects_body() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    // 質点は (±40, ±40) の4つだけで、セルの中央付近は質点・バネのどれからも 10 以上離れている
+    let grid = sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(80.0, 80.0), rows: 2, cols: 2, particle_radius: 4.0, ..Default::default() }).unwrap();
+    let other = sim.add_convex_body(&square(60.0, -5.0, 10.0), &SoftBodyConfig { particle_radius: 4.0, ..Default::default() }).unwrap();
+    assert!(sim.soft_body(grid).unwrap().outline_wires.is_none());
+
+    assert_eq!(sim.nearest_body(Vec2::new(20.0, -5.0)), Some(grid));
+    assert_eq!(sim.nearest_body(Vec2::new(-25.0, 10.0)), Some(grid));
+    // グリッドの外側では、近くの別のボディが選ばれる
+    assert_eq!(sim.nearest_body(Vec2::new(58.0, -5.0)), Some(other));
+}
+
+/// 何もない場所をクリックすると `None` が返ることを確認
+#[test]
+fn clicking_empty_space_selects_nothing() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let config = SoftBodyConfig { particle_radius: 4.0, ..Default::default() };
+    sim.add_convex_body(&square(0.0, 0.0, 40.0), &config).unwrap();
+    sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(200.0, 0.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 4.0, ..Default::default() }).unwrap();
+
+    assert_eq!(sim.nearest_body(Vec2::new(100.0, 0.0)), None);
+    assert_eq!(sim.nearest_body(Vec2::new(0.0, 300.0)), None);
+    assert_eq!(Simulation::new(SimulationConfig::default()).nearest_body(Vec2::new(0.0, 0.0)), None);
+}
+
+/// シミュレーション中の凸ボディの外周に対して、ジオメトリ関数がそのまま使えることを確認
+#[test]
+fn geometry_functions_accept_simulated_outline() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let handle = sim.add_convex_body(&square(0.0, 0.0, 40.0), &SoftBodyConfig::default()).unwrap();
+    let outline = sim.soft_body(handle).unwrap().outline_points(sim.particles());
+    assert_eq!(outline, square(0.0, 0.0, 40.0));
+
+    let line = Line::new(Vec2::new(-100.0, 10.0), Vec2::new(100.0, 10.0));
+    let hits = find_all_intersections(&outline, &line);
+    assert_eq!(hits, vec![Vec2::new(40.0, 10.0), Vec2::new(-40.0, 10.0)]);
+    assert!(find_all_intersections(&outline, &Line::new(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0))).is_empty());
+
+    assert_eq!(find_nearest_segment(&outline, Vec2::new(35.0, 5.0)), Some((1, Vec2::new(40.0, 5.0))));
+    assert_eq!(find_nearest_segment(&[], Vec2::new(0.0, 0.0)), None);
+    assert!(point_in_polygon(Vec2::new(0.0, 0.0), &outline));
+}
+
+/// 矩形選択でグリッドの既知の部分集合が選ばれ、境界上の質点は含まれることを確認
+#[test]
+fn query_aabb_selects_grid_subset_inclusively() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    // x, y とも -40, -20, 0, 20, 40 に並ぶ 5x5 グリッド
+    sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(80.0, 80.0), particle_radius: 4.0, ..Default::default() }).unwrap();
+
+    // 境界ちょうどの -20 と 20 を含む
+    let selected = sim.query_aabb(Vec2::new(-20.0, -20.0), Vec2::new(20.0, 0.0));
+    assert_eq!(selected, vec![6, 7, 8, 11, 12, 13]);
+    // 逆向きにドラッグしても同じ
+    assert_eq!(sim.query_aabb(Vec2::new(20.0, 0.0), Vec2::new(-20.0, -20.0)), selected);
+    // 境界のわずかに内側では外れる
+    assert_eq!(sim.query_aabb(Vec2::new(-19.999, -20.0), Vec2::new(19.999, 0.0)), vec![7, 12]);
+    assert!(sim.query_aabb(Vec2::new(-15.0, -15.0), Vec2::new(-5.0, -5.0)).is_empty());
+}
+
+/// 一列に並んだ質点を貫く半直線が、全ての質点を近い順に、距離が増えていく順で返すことを確認
+#[test]
+fn raycast_all_returns_hits_near_to_far() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    // x = 100, 140, ..., 260 に半径 8 の質点を逆順に追加
+    for k in (0..5).rev() {
+        sim.add_chain(&[Vec2::new(100.0 + 40.0 * k as Scalar, 50.0)], &SoftBodyConfig::default());
+    }
+    // 列から外れた質点
+    sim.add_chain(&[Vec2::new(180.0, 150.0)], &SoftBodyConfig::default());
+
+    let hits = sim.raycast_all(Vec2::new(0.0, 50.0), Vec2::new(2.0, 0.0), 1000.0);
+    assert_eq!(hits.iter().map(|h| h.particle).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    for (k, hit) in hits.iter().enumerate() {
+        assert!((hit.distance - (92.0 + 40.0 * k as Scalar)).abs() < 1e-9, "{hit:?}");
+        assert!((hit.point - Vec2::new(hit.distance, 50.0)).length() < 1e-9);
+        assert!((hit.normal - Vec2::new(-1.0, 0.0)).length() < 1e-9);
+    }
+    assert!(hits.windows(2).all(|w| w[0].distance < w[1].distance));
+    assert_eq!(sim.raycast(Vec2::new(0.0, 50.0), Vec2::new(1.0, 0.0), 1000.0), Some(hits[0]));
+
+    // 最大距離で打ち切られ、逆向きや長さ0の向きでは何も返さない
+    assert_eq!(sim.raycast_all(Vec2::new(0.0, 50.0), Vec2::new(1.0, 0.0), 150.0).len(), 2);
+    assert!(sim.raycast_all(Vec2::new(0.0, 50.0), Vec2::new(-1.0, 0.0), 1000.0).is_empty());
+    assert!(sim.raycast_all(Vec2::new(0.0, 50.0), Vec2::new(0.0, 0.0), 1000.0).is_empty());
+}
+
+/// 複数ボディのシーンで、円と矩形に交わるボディだけが追加順に選ばれることを確認
+#[test]
+fn bodies_in_region_select_overlapping_subset() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    // 半径を含めたバウンディングボックスは各中心から ±25
+    let add = |sim: &mut Simulation, x: Scalar, y: Scalar| {
+        sim.add_soft_body(&SoftBodyConfig {
+            center: Vec2::new(x, y),
+            size: Vec2::new(40.0, 40.0),
+            particle_radius: 5.0,
+            ..Default::default()
+        }).unwrap()
+    };
+    let a = add(&mut sim, 0.0, 0.0);
+    let b = add(&mut sim, 100.0, 0.0);
+    let c = add(&mut sim, 0.0, 200.0);
+    let d = add(&mut sim, 300.0, 300.0);
+
+    // 中心 (50, 0) の半径 30 の円は a と b の箱に届く
+    assert_eq!(sim.bodies_in_circle(Vec2::new(50.0, 0.0), 30.0), vec![a, b]);
+    // 角の外側: 箱の角 (25, 25) までの距離は 5√2 ≈ 7.07
+    assert_eq!(sim.bodies_in_circle(Vec2::new(30.0, 30.0), 7.0), vec![]);
+    assert_eq!(sim.bodies_in_circle(Vec2::new(30.0, 30.0), 7.1), vec![a]);
+    // 円がボディを内側に含む場合も選ばれる
+    assert_eq!(sim.bodies_in_circle(Vec2::new(0.0, 200.0), 1.0), vec![c]);
+
+    assert_eq!(sim.bodies_in_aabb(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 180.0)), vec![a, c]);
+    assert_eq!(sim.bodies_in_aabb(Vec2::new(400.0, 400.0), Vec2::new(275.0, 275.0)), vec![d]);
+    assert!(sim.bodies_in_aabb(Vec2::new(30.0, 30.0), Vec2::new(70.0, 170.0)).is_empty());
+}
@@ -0,0 +1,70 @@
+use softbody::core::{Line, Scalar, Simulation, SimulationConfig, SoftBodyConfig, SoftBodyHandle, Vec2};
+
+const CENTER: Vec2 = Vec2::new(200.0, 200.0);
+
+/// 半径 60 の16角形の泡を作り、中心に向かって `scale` 倍に縮めた（膨らませた）状態にします。
+fn bubble(scale: Scalar) -> (Simulation, SoftBodyHandle) {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 0.95, ..Default::default() });
+    let ring: Vec<Vec2> = (0..16)
+        .map(|i| {
+            let angle = i as Scalar * (std::f64::consts::TAU as Scalar) / 16.0;
+            CENTER + Vec2::new(angle.cos(), angle.sin()) * 60.0
+        })
+        .collect();
+    // バネと形状維持拘束を無効にし、圧力だけで形を戻す
+    let config = SoftBodyConfig { particle_radius: 4.0, stiffness: 0.0, shape_stiffness: 0.0, target_pressure: Some(10.0), ..Default::default() };
+    let handle = sim.add_convex_body(&ring, &config).unwrap();
+    for p in &mut sim.particles {
+        p.teleport(CENTER + (p.pos - CENTER) * scale);
+    }
+    (sim, handle)
+}
+
+fn area_ratio(sim: &Simulation, handle: SoftBodyHandle) -> Scalar {
+    let pressure = &sim.soft_body(handle).unwrap().pressure_constraints[0];
+    pressure.area(sim.particles()) / pressure.target_area
+}
+
+/// しぼませた泡が目標の面積に向かって膨らみ、わずかに行き過ぎてから落ち着くことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn deflated_bubble_reinflates_and_settles() {
+    let (mut sim, handle) = bubble(0.7);
+    assert!((area_ratio(&sim, handle) - 0.49).abs() < 1e-9);
+
+    let mut peak: Scalar = 0.0;
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+        peak = peak.max(area_ratio(&sim, handle));
+    }
+    assert!(peak > 1.01 && peak < 1.1, "{peak}");
+    assert!((area_ratio(&sim, handle) - 1.0).abs() < 1e-3, "{}", area_ratio(&sim, handle));
+}
+
+/// 膨らみすぎた泡は内向きの圧力で目標の面積まで縮むことを確認
+#[test]
+fn overinflated_bubble_shrinks_back() {
+    let (mut sim, handle) = bubble(1.2);
+    sim.step(1.0 / 60.0);
+    assert!(area_ratio(&sim, handle) < 1.44);
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+    }
+    assert!((area_ratio(&sim, handle) - 1.0).abs() < 1e-3, "{}", area_ratio(&sim, handle));
+}
+
+/// しぼませた泡を切断すると、元の目標の面積が切断時の面積の比で2つのボディに分けられることを確認
+#[test]
+fn slicing_deflated_bubble_splits_target_area() {
+    let (mut sim, handle) = bubble(0.7);
+    let target = sim.soft_body(handle).unwrap().pressure_constraints[0].target_area;
+    let line = Line::new(CENTER + Vec2::new(20.0, -100.0), CENTER + Vec2::new(20.0, 100.0));
+    let (a, b) = sim.slice_body(handle, line).unwrap();
+
+    let target_of = |h| sim.soft_body(h).unwrap().pressure_constraints[0].target_area;
+    assert!((target_of(a) + target_of(b) - target).abs() < 1e-4 * target);
+    // 切断時の面積はどちらも目標の 0.49 倍
+    for h in [a, b] {
+        assert!((area_ratio(&sim, h) - 0.49).abs() < 1e-4, "{}", area_ratio(&sim, h));
+    }
+}
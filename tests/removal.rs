@@ -0,0 +1,126 @@
+use softbody::core::{Particle, ParticleError, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn grid_sim() -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() }).unwrap();
+    sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(100.0, 0.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() }).unwrap();
+    sim
+}
+
+/// グリッドの内部の質点を取り除くと、残りのバネと拘束が有効なインデックスを指すことを確認
+#[test]
+fn removing_interior_grid_particle_keeps_indices_valid() {
+    let mut sim = grid_sim();
+    let springs_before = sim.springs().count();
+    let positions_before: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+
+    // 1つ目のグリッドの中央の質点
+    sim.remove_particle(4).unwrap();
+
+    let n = sim.particle_count();
+    assert_eq!(n, 17);
+    // 中央の質点につながる4本のバネが消える
+    assert_eq!(sim.springs().count(), springs_before - 4);
+    for body in sim.soft_bodies() {
+        assert!(body.particle_indices.iter().all(|&i| i < n));
+        let sc = body.shape_constraint.as_ref().unwrap();
+        assert_eq!(sc.particle_indices, body.particle_indices);
+    }
+    assert_eq!(sim.soft_bodies()[0].particle_indices, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    for spring in sim.springs() {
+        assert!(spring.p1_index < n && spring.p2_index < n);
+        // インデックスが詰められても、バネは元と同じ質点の組を指している
+        let d = (sim.particles()[spring.p1_index].pos - sim.particles()[spring.p2_index].pos).length();
+        assert!((d - spring.rest_length).abs() < 1e-9);
+    }
+    assert_eq!(sim.particles()[4].pos, positions_before[5]);
+
+    // 初期形状が保たれているため、形状維持拘束は静止状態を崩さない
+    sim.set_gravity(Vec2::new(0.0, 0.0));
+    let before: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    sim.step(1.0 / 60.0);
+    for (p, q) in sim.particles().iter().zip(&before) {
+        assert!((p.pos - *q).length() < 1e-9);
+    }
+}
+
+#[test]
+fn removing_unremovable_particles_is_an_error() {
+    let mut sim = grid_sim();
+    assert_eq!(sim.remove_particle(18), Err(ParticleError::IndexOutOfBounds));
+
+    let ring = [Vec2::new(300.0, 0.0), Vec2::new(340.0, 0.0), Vec2::new(320.0, 30.0)];
+    sim.add_convex_body(&ring, &SoftBodyConfig::default()).unwrap();
+    assert_eq!(sim.remove_particle(19), Err(ParticleError::OnOutline));
+    assert_eq!(sim.particle_count(), 21);
+
+    // どのボディにも属さない質点は取り除ける
+    sim.particles.push(Particle::new(500.0, 0.0));
+    assert_eq!(sim.remove_particle(21), Ok(()));
+    assert_eq!(sim.particle_count(), 21);
+}
+
+/// いくつかのボディを取り除いて `compact` した後も、質点の数が正確で、全ての拘束が詰められた連続するインデックスを指すことを確認
+#[test]
+fn compact_after_removing_bodies_keeps_indices_contiguous() {
+    let mut sim = Simulation::with_capacity(SimulationConfig::default(), 200, 8);
+    let config = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 0.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() };
+    let handles: Vec<_> = (0..5).map(|k| sim.add_soft_body(&config(100.0 * k as Scalar)).unwrap()).collect();
+    let square = [Vec2::new(600.0, 0.0), Vec2::new(640.0, 0.0), Vec2::new(640.0, 40.0), Vec2::new(600.0, 40.0)];
+    sim.add_convex_body(&square, &SoftBodyConfig { particle_radius: 5.0, ..Default::default() }).unwrap();
+    sim.connect(sim.soft_body(handles[3]).unwrap().particle_indices[0], sim.soft_body(handles[4]).unwrap().particle_indices[0], 0.5);
+
+    for &handle in &[handles[0], handles[2], handles[3]] {
+        assert!(sim.remove_soft_body(handle));
+    }
+    let n = sim.compact();
+    assert_eq!(n, 2 * 9 + 4);
+    assert_eq!(n, sim.particle_count());
+    assert!(sim.particles.capacity() < 200);
+
+    // ボディの質点は先頭から隙間なく並ぶ
+    let all: Vec<usize> = sim.soft_bodies().iter().flat_map(|b| b.particle_indices.iter().copied()).collect();
+    assert_eq!(all, (0..n).collect::<Vec<_>>());
+    // 取り除いたボディにつながるバネは消え、残りは有効なインデックスを指す
+    assert_eq!(sim.springs().count(), sim.soft_bodies().iter().map(|b| b.springs.len()).sum::<usize>());
+    for body in sim.soft_bodies() {
+        assert!(body.outline_wires.iter().flatten().all(|&(a, b)| a < n && b < n));
+    }
+    assert!(sim.validate().is_ok());
+    sim.step(1.0 / 60.0);
+    assert_eq!(sim.compact(), n);
+}
+
+fn grid(x: Scalar) -> SoftBodyConfig {
+    SoftBodyConfig {
+        center: Vec2::new(x, 0.0),
+        size: Vec2::new(40.0, 40.0),
+        rows: 3,
+        cols: 3,
+        particle_radius: 5.0,
+        ..Default::default()
+    }
+}
+
+/// ボディを取り除くと、その質点も取り除かれ、残りのボディの拘束が正しい質点を指し続けることを確認
+#[test]
+fn removing_a_body_reindexes_the_remaining_bodies() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let first = sim.add_soft_body(&grid(0.0)).unwrap();
+    let second = sim.add_soft_body(&grid(100.0)).unwrap();
+    let positions_before: Vec<Vec2> = sim.soft_body(second).unwrap().particle_indices.iter().map(|&i| sim.particles()[i].pos).collect();
+
+    assert!(sim.remove_soft_body(first));
+    assert!(!sim.remove_soft_body(first));
+
+    assert_eq!(sim.particle_count(), 9);
+    let body = sim.soft_body(second).unwrap();
+    let positions_after: Vec<Vec2> = body.particle_indices.iter().map(|&i| sim.particles()[i].pos).collect();
+    assert_eq!(positions_after, positions_before);
+    for spring in &body.springs {
+        let d = (sim.particles()[spring.p1_index].pos - sim.particles()[spring.p2_index].pos).length();
+        assert!((d - spring.rest_length).abs() < 1e-9);
+    }
+    sim.step(1.0 / 60.0);
+    assert!(sim.particles().iter().all(|p| p.pos.x.is_finite() && p.pos.y.is_finite()));
+}
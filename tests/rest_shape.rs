@@ -0,0 +1,116 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn shape_correction(sim: &Simulation, handle: softbody::core::SoftBodyHandle) -> Scalar {
+    let mut sc = sim.soft_body(handle).unwrap().shape_constraint.clone().unwrap();
+    let mut particles = sim.particles().to_vec();
+    sc.solve(&mut particles);
+    particles.iter().zip(sim.particles()).map(|(p, q)| (p.pos - q.pos).length()).fold(0.0, Scalar::max)
+}
+
+/// 変形したボディの静止形状を取り直すと、変形した状態での形状維持拘束の補正がほぼ0になることを確認
+#[test]
+fn resetting_rest_shape_accepts_deformed_configuration() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let handle = sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, shape_stiffness: 0.5, ..Default::default() }).unwrap();
+    assert!(shape_correction(&sim, handle) < 1e-9);
+
+    // 右上の角を引っ張って変形させる
+    sim.particles[2].pos += Vec2::new(15.0, -10.0);
+    sim.particles[5].pos += Vec2::new(8.0, 0.0);
+    assert!(shape_correction(&sim, handle) > 1.0);
+
+    assert!(sim.reset_body_rest_shape(handle));
+    assert!(shape_correction(&sim, handle) < 1e-9);
+    let sc = sim.soft_body(handle).unwrap().shape_constraint.as_ref().unwrap();
+    assert_eq!(sc.particle_indices, (0..9).collect::<Vec<_>>());
+    assert_eq!(sc.stiffness, 0.5);
+}
+
+/// グリッドの静止状態を 1.2 倍にすると、落ち着いた後に約 1.2 倍の大きさになることを確認
+#[test]
+fn inflating_grid_grows_toward_scaled_size() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let handle = sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(60.0, 60.0), rows: 4, cols: 4, particle_radius: 5.0, ..Default::default() }).unwrap();
+    let extent = |sim: &Simulation| {
+        let xs: Vec<Scalar> = sim.particles().iter().map(|p| p.pos.x).collect();
+        xs.iter().cloned().fold(Scalar::MIN, Scalar::max) - xs.iter().cloned().fold(Scalar::MAX, Scalar::min)
+    };
+    assert_eq!(extent(&sim), 60.0);
+
+    assert!(sim.scale_body_rest(handle, 1.2));
+    for _ in 0..300 {
+        sim.step(1.0 / 60.0);
+    }
+    let grown = extent(&sim);
+    assert!((grown - 72.0).abs() < 1.0, "grown = {grown}");
+    assert!(sim.springs().all(|s| (s.rest_length - 24.0).abs() < 1e-9));
+}
+
+/// 圧力を持つボディの静止状態を拡大すると目標の面積も `factor` の2乗倍になり、
+/// 正の有限値でない倍率は拒否されて何も変わらないことを確認
+#[test]
+fn scaling_rest_state_scales_pressure_target_and_rejects_bad_factors() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let config = SoftBodyConfig { particle_radius: 4.0, target_pressure: Some(10.0), ..Default::default() };
+    let handle = sim.add_regular_polygon(Vec2::new(0.0, 0.0), 60.0, 12, &config).unwrap();
+    let target = |sim: &Simulation| sim.soft_body(handle).unwrap().pressure_constraints[0].target_area;
+    let before = target(&sim);
+
+    assert!(sim.scale_body_rest(handle, 1.5));
+    assert!((target(&sim) - before * 2.25).abs() < 1e-6 * before);
+
+    let rest_lengths: Vec<Scalar> = sim.springs().map(|s| s.rest_length).collect();
+    for factor in [0.0, -1.0, Scalar::NAN, Scalar::INFINITY] {
+        assert!(!sim.scale_body_rest(handle, factor), "{factor}");
+    }
+    assert_eq!(sim.springs().map(|s| s.rest_length).collect::<Vec<_>>(), rest_lengths);
+    assert!((target(&sim) - before * 2.25).abs() < 1e-6 * before);
+    assert!(sim.validate().is_ok());
+}
+
+/// 外周のバネの静止長を縮めて皮を張った正十二角形に内向きの押し込みを与え、1ステップ後に残るへこみの深さを返します
+fn dent_after_poke(outline_scale: Scalar) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let config = SoftBodyConfig { particle_radius: 4.0, stiffness: 0.5, shape_stiffness: 0.05, ..Default::default() };
+    let handle = sim.add_regular_polygon(Vec2::new(0.0, 0.0), 60.0, 12, &config).unwrap();
+    assert!(sim.set_outline_rest_scale(handle, outline_scale));
+    // 張った皮に合わせてボディが縮み、落ち着くのを待つ
+    for _ in 0..200 {
+        sim.step(1.0 / 60.0);
+    }
+    sim.freeze_velocities();
+
+    let indices = sim.soft_body(handle).unwrap().particle_indices.clone();
+    let poked = sim.particles[indices[0]].pos;
+    sim.particles[indices[0]].teleport(poked * 0.75);
+    sim.step(1.0 / 60.0);
+
+    // 隣り合う2頂点の中点より、押し込んだ頂点がどれだけ重心に近いか
+    let center = sim.soft_body(handle).unwrap().centroid(sim.particles());
+    let mid = Vec2::mid(sim.particles[indices[11]].pos, sim.particles[indices[1]].pos);
+    (mid - center).length() - (sim.particles[indices[0]].pos - center).length()
+}
+
+/// 外周の静止長の倍率が外周の辺のバネにだけ掛かり、皮を張ったボディは内向きの押し込みから速く戻ることを確認
+#[test]
+fn pretensioned_outline_resists_pokes() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let handle = sim.add_regular_polygon(Vec2::new(0.0, 0.0), 60.0, 6, &SoftBodyConfig::default()).unwrap();
+    let before = sim.outline_rest_lengths(handle).unwrap();
+    let interior: Vec<Scalar> = sim.soft_body(handle).unwrap().springs.iter().map(|s| s.rest_length).filter(|l| !before.contains(l)).collect();
+    assert_eq!(before.len(), 6);
+    assert!(sim.set_outline_rest_scale(handle, 0.9));
+    let after = sim.outline_rest_lengths(handle).unwrap();
+    for (a, b) in after.iter().zip(&before) {
+        assert!((a - b * 0.9).abs() < 1e-9);
+    }
+    let interior_after: Vec<Scalar> = sim.soft_body(handle).unwrap().springs.iter().map(|s| s.rest_length).filter(|l| !after.contains(l)).collect();
+    assert_eq!(interior, interior_after);
+
+    let chain = sim.add_chain(&[Vec2::new(300.0, 0.0), Vec2::new(340.0, 0.0)], &SoftBodyConfig::default());
+    assert!(!sim.set_outline_rest_scale(chain, 0.9));
+    assert_eq!(sim.outline_rest_lengths(chain), None);
+
+    let (relaxed, taut) = (dent_after_poke(1.0), dent_after_poke(0.85));
+    assert!(taut < relaxed - 1.0, "taut {taut}, relaxed {relaxed}");
+}
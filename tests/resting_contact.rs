@@ -0,0 +1,61 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 床（y = 300）の種類
+#[derive(Debug, Clone, Copy)]
+enum Floor {
+    /// 境界 `bounds` の下の壁
+    Bounds,
+    /// 閉じ込め領域 `containment` の下の辺
+    Containment,
+    /// 床に接して置いた大きな固定質点
+    FixedParticle,
+}
+
+/// 半径と質量の大きく異なる2つの質点を床の上に重ねると、どちらが下でも間隔が `r1 + r2` に落ち着き、そのまま保たれることを確認
+#[test]
+fn different_radius_particles_rest_at_contact_distance() {
+    for floor in [Floor::Bounds, Floor::Containment, Floor::FixedParticle] {
+        settle_stack(floor);
+    }
+}
+
+fn settle_stack(floor: Floor) {
+    // (下の質点の半径, inv_mass), (上の質点の半径, inv_mass)
+    let cases = [((20.0, 4.0), (3.0, 0.1)), ((3.0, 0.1), (20.0, 4.0))];
+    for ((r1, w1), (r2, w2)) in cases {
+        let mut sim = Simulation::new(SimulationConfig {
+            gravity: Vec2::new(0.0, 980.0),
+            bounds: matches!(floor, Floor::Bounds).then_some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))),
+            ..Default::default()
+        });
+        match floor {
+            Floor::Bounds => {}
+            Floor::Containment => {
+                sim.set_containment(vec![Vec2::new(0.0, 0.0), Vec2::new(400.0, 0.0), Vec2::new(400.0, 300.0), Vec2::new(0.0, 300.0)]);
+            }
+            Floor::FixedParticle => {
+                let config = SoftBodyConfig { particle_radius: 50.0, is_fixed: true, ..Default::default() };
+                sim.add_chain(&[Vec2::new(200.0, 350.0)], &config);
+            }
+        }
+        let offset = sim.particle_count();
+        let particle = |y: Scalar, radius: Scalar, inv_mass: Scalar| {
+            (vec![Vec2::new(200.0, y)], SoftBodyConfig { particle_radius: radius, particle_inv_mass: inv_mass, ..Default::default() })
+        };
+        let (points, config) = particle(300.0 - r1, r1, w1);
+        sim.add_chain(&points, &config);
+        let (points, config) = particle(300.0 - 2.0 * r1 - r2 - 5.0, r2, w2);
+        sim.add_chain(&points, &config);
+
+        let separation = |sim: &Simulation| (sim.particles()[offset].pos - sim.particles()[offset + 1].pos).length();
+        for _ in 0..120 {
+            sim.step(1.0 / 60.0);
+        }
+        for _ in 0..600 {
+            sim.step(1.0 / 60.0);
+            let error = separation(&sim) - (r1 + r2);
+            assert!(error.abs() < 1e-6, "{floor:?}, r1 {r1}, r2 {r2}: error {error}");
+            assert!(sim.particles()[offset + 1].vel.length() < 1e-6, "{floor:?}");
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 正面から近づく等質量の2つの質点を、反発係数 `restitution` で衝突させたあとの速度を返します。
+fn head_on(restitution: Scalar) -> (Simulation, Scalar, Scalar) {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 0.0),
+        damping: 1.0,
+        restitution: Some(restitution),
+        ..Default::default()
+    });
+    let single = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 0.0), size: Vec2::new(0.0, 0.0), rows: 1, cols: 1, particle_radius: 5.0, ..Default::default() };
+    sim.add_soft_body(&single(-30.0)).unwrap();
+    sim.add_soft_body(&single(30.0)).unwrap();
+    sim.particles[0].vel = Vec2::new(600.0, 0.0);
+    sim.particles[1].vel = Vec2::new(-200.0, 0.0);
+
+    let initial = sim.total_linear_momentum();
+    for _ in 0..20 {
+        sim.step(1.0 / 60.0);
+        let p = sim.total_linear_momentum();
+        assert!((p - initial).length() < 1e-9, "{p:?} vs {initial:?}");
+    }
+    let (v0, v1) = (sim.particles()[0].vel.x, sim.particles()[1].vel.x);
+    (sim, v0, v1)
+}
+
+/// 反発係数 `1.0` では等質量の2つの質点の速度が入れ替わることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn elastic_head_on_collision_swaps_velocities() {
+    let (_, v0, v1) = head_on(1.0);
+    assert!((v0 - -200.0).abs() < 1e-6, "v0 = {v0}");
+    assert!((v1 - 600.0).abs() < 1e-6, "v1 = {v1}");
+}
+
+/// 反発係数 `0.0` では2つの質点が重心の速度で一緒に動くことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn inelastic_head_on_collision_ends_comoving() {
+    let (sim, v0, v1) = head_on(0.0);
+    assert!((v0 - 200.0).abs() < 1e-6 && (v1 - 200.0).abs() < 1e-6, "v0 = {v0}, v1 = {v1}");
+    let gap = sim.particles()[1].pos.x - sim.particles()[0].pos.x;
+    assert!((gap - 10.0).abs() < 1e-6, "gap = {gap}");
+}
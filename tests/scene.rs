@@ -0,0 +1,36 @@
+use softbody::core::{SceneBuilder, SceneError, ShapeError, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 2つのボディのシーンを構築すると、返されたハンドルが登録順にそれぞれのボディを指すことを確認
+#[test]
+fn build_returns_handles_in_insertion_order() {
+    let square = vec![Vec2::new(200.0, 0.0), Vec2::new(260.0, 0.0), Vec2::new(260.0, 60.0), Vec2::new(200.0, 60.0)];
+    let (sim, handles) = SceneBuilder::new(SimulationConfig::default())
+        .soft_body(SoftBodyConfig { rows: 3, cols: 3, tag: Some(1), ..Default::default() })
+        .convex_body(square.clone(), SoftBodyConfig { tag: Some(2), ..Default::default() })
+        .build()
+        .unwrap();
+
+    assert_eq!(handles.len(), 2);
+    let grid = sim.soft_body(handles[0]).unwrap();
+    assert_eq!(grid.tag(), Some(1));
+    assert_eq!(grid.particle_indices.len(), 9);
+    let convex = sim.soft_body(handles[1]).unwrap();
+    assert_eq!(convex.tag(), Some(2));
+    assert_eq!(convex.outline_points(sim.particles()), square);
+}
+
+/// 不正なボディが複数ある場合は、最初の1つで止まらず全てのエラーが返されることを確認
+#[test]
+fn build_reports_every_invalid_body() {
+    let errors = SceneBuilder::default()
+        .convex_body(vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)], SoftBodyConfig::default())
+        .chain(vec![Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0)], SoftBodyConfig::default())
+        .soft_body(SoftBodyConfig { size: Vec2::new(10.0, 10.0), ..Default::default() })
+        .build()
+        .unwrap_err();
+    assert_eq!(errors, vec![
+        SceneError { index: 0, error: ShapeError::NotEnoughParticles },
+        SceneError { index: 2, error: ShapeError::OverlappingParticles },
+    ]);
+    assert_eq!(errors[1].to_string(), "Body 2: Particle spacing is smaller than the particle diameter.");
+}
@@ -0,0 +1,48 @@
+use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 落下する小さな正方形と、その経路上に置いた固定のセンサー領域のシーンを作ります
+fn scene(with_sensor: bool) -> (Simulation, Option<softbody::core::SoftBodyHandle>, softbody::core::SoftBodyHandle) {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let sensor = with_sensor.then(|| {
+        let zone = [Vec2::new(-60.0, 100.0), Vec2::new(60.0, 100.0), Vec2::new(60.0, 160.0), Vec2::new(-60.0, 160.0)];
+        let config = SoftBodyConfig { is_sensor: true, is_fixed: true, particle_radius: 6.0, ..Default::default() };
+        sim.add_convex_body(&zone, &config).unwrap()
+    });
+    // センサーの左上の角の質点と重なる位置を通るように落とす
+    let square = [Vec2::new(-70.0, 0.0), Vec2::new(-40.0, 0.0), Vec2::new(-40.0, 30.0), Vec2::new(-70.0, 30.0)];
+    let body = sim.add_convex_body(&square, &SoftBodyConfig { particle_radius: 4.0, ..Default::default() }).unwrap();
+    (sim, sensor, body)
+}
+
+/// 落下するボディはセンサーに押し返されずに通り抜け、センサーは内側にある間だけ重なりを報告することを確認
+#[test]
+fn falling_body_passes_through_sensor_and_is_reported() {
+    let (mut sim, sensor, body) = scene(true);
+    let sensor = sensor.unwrap();
+    let (mut reference, _, reference_body) = scene(false);
+    assert!(sim.soft_body(sensor).unwrap().is_sensor());
+    assert!(!sim.soft_body(body).unwrap().is_sensor());
+
+    let mut reported = Vec::new();
+    for _ in 0..90 {
+        sim.step(1.0 / 60.0);
+        reference.step(1.0 / 60.0);
+        // センサーが無い場合と全く同じ軌道をたどる
+        let a: Vec<Vec2> = sim.particles_of_body(body).map(|p| p.pos).collect();
+        let b: Vec<Vec2> = reference.particles_of_body(reference_body).map(|p| p.pos).collect();
+        assert_eq!(a, b);
+
+        let inside = sim.particles_of_body(body).any(|p| p.pos.y > 100.0 && p.pos.y < 160.0 && p.pos.x > -60.0);
+        let overlaps = sim.sensor_overlaps(sensor);
+        assert_eq!(overlaps, if inside { vec![body] } else { vec![] });
+        reported.push(!overlaps.is_empty());
+    }
+    // 入ってから出ていくまで報告される
+    assert!(reported.iter().any(|&r| r));
+    assert!(!reported[0] && !reported[reported.len() - 1]);
+
+    // センサー自身は動かない
+    let zone = sim.soft_body(sensor).unwrap().outline_points(sim.particles());
+    assert_eq!(zone[0], Vec2::new(-60.0, 100.0));
+    assert!(sim.sensor_overlaps(body).is_empty());
+}
@@ -0,0 +1,106 @@
+use softbody::core::{Mat2, Particle, Scalar, ShapeMatchingConstraint, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn particles(points: &[(Scalar, Scalar)]) -> Vec<Particle> {
+    points.iter().map(|&(x, y)| Particle::new(x, y)).collect()
+}
+
+fn distance(a: Mat2, b: Mat2) -> Scalar {
+    (a.c1 - b.c1).length() + (a.c2 - b.c2).length()
+}
+
+/// ほぼ一直線に並んだ質点の集まりを、上下にわずかに揺らしながら解いても、抽出される回転が反転しないことを確認
+#[test]
+fn nearly_collinear_rotation_does_not_flip() {
+    let e = 1e-4;
+    let rest = [(-2.0, e), (-1.0, -e), (0.0, e), (1.0, -e), (2.0, e)];
+    let mirrored: Vec<(Scalar, Scalar)> = rest.iter().map(|&(x, y)| (x, -y)).collect();
+    let mut sc = ShapeMatchingConstraint::new((0..rest.len()).collect(), 0.0, &particles(&rest));
+
+    let identity = Mat2::new(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+    // 鏡映した形状だけを見ると、従来の極分解は上下反転した行列を返す
+    let mut a_pq = Mat2::default();
+    for (&(px, py), &(qx, qy)) in mirrored.iter().zip(&rest) {
+        a_pq.c1 += Vec2::new(px, py) * qx;
+        a_pq.c2 += Vec2::new(px, py) * qy;
+    }
+    assert!(distance(a_pq.polar_decomposition(), identity) > 1.0);
+
+    let mut previous = sc.rotation();
+    for k in 0..10 {
+        let mut current = if k % 2 == 0 { particles(&rest) } else { particles(&mirrored) };
+        sc.solve(&mut current);
+        assert!(distance(sc.rotation(), previous) < 1e-6, "step {k}: {}", sc.rotation());
+        previous = sc.rotation();
+    }
+    assert!(distance(previous, identity) < 1e-6);
+}
+
+/// 退化していない形状では、従来どおり回転がそのまま抽出されることを確認
+#[test]
+fn regular_shape_rotation_is_extracted() {
+    let rest = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    let mut sc = ShapeMatchingConstraint::new((0..4).collect(), 0.0, &particles(&rest));
+    // 90度回転した配置
+    let mut rotated = particles(&[(1.0, -1.0), (1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0)]);
+    sc.solve(&mut rotated);
+    let expected = Mat2::new(Vec2::new(0.0, 1.0), Vec2::new(-1.0, 0.0));
+    assert!(distance(sc.rotation(), expected) < 1e-9, "{}", sc.rotation());
+}
+
+/// 1つの質点に大きな重みを付けると、その質点だけを回したときに抽出される回転が、重みの無い場合よりその質点の回転に近いことを確認
+#[test]
+fn weighted_particle_dominates_rotation() {
+    let rest = [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)];
+    let indices: Vec<usize> = (0..rest.len()).collect();
+    let mut unweighted = ShapeMatchingConstraint::new(indices.clone(), 0.0, &particles(&rest));
+    let mut weighted = ShapeMatchingConstraint::with_weights(indices, vec![50.0, 1.0, 1.0, 1.0], 0.0, &particles(&rest));
+    assert_eq!(unweighted.weights(), None);
+    assert_eq!(weighted.weights(), Some(&[50.0, 1.0, 1.0, 1.0][..]));
+
+    // 0番目の質点だけを原点の周りに 60 度回す
+    let turn = std::f64::consts::FRAC_PI_3 as Scalar;
+    let mut moved = particles(&rest);
+    moved[0].pos = Vec2::new(turn.cos(), turn.sin());
+    unweighted.solve(&mut moved.clone());
+    weighted.solve(&mut moved);
+
+    let angle = |r: Mat2| r.c1.y.atan2(r.c1.x);
+    let (a, b) = (angle(unweighted.rotation()), angle(weighted.rotation()));
+    assert!(a > 0.0 && b > 0.0, "{a}, {b}");
+    assert!((turn - b).abs() < (turn - a).abs(), "weighted {b} should be closer to {turn} than {a}");
+}
+
+/// 床の上で静止したボディを `shape_match_tolerance` 付きで進めると、極分解の回数が大きく減り、
+/// 結果は閾値なしで進めた場合とほとんど変わらないことを確認
+#[test]
+fn tolerance_skips_decompositions_on_settled_scene() {
+    let run = |tolerance: Scalar| {
+        let mut sim = Simulation::new(SimulationConfig {
+            bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))),
+            shape_match_tolerance: tolerance,
+            ..Default::default()
+        });
+        let handle = sim.add_soft_body(&SoftBodyConfig {
+            center: Vec2::new(200.0, 250.0),
+            size: Vec2::new(60.0, 60.0),
+            particle_radius: 5.0,
+            ..Default::default()
+        }).unwrap();
+        for _ in 0..300 {
+            sim.step(1.0 / 60.0);
+        }
+        let before = sim.soft_body(handle).unwrap().shape_constraint.as_ref().unwrap().decomposition_count();
+        for _ in 0..100 {
+            sim.step(1.0 / 60.0);
+        }
+        let after = sim.soft_body(handle).unwrap().shape_constraint.as_ref().unwrap().decomposition_count();
+        (after - before, sim.particles().to_vec())
+    };
+
+    let (exact_count, exact) = run(0.0);
+    let (cached_count, cached) = run(1e-3);
+    assert!(cached_count * 10 <= exact_count, "exact {exact_count}, cached {cached_count}");
+    for (a, b) in exact.iter().zip(&cached) {
+        assert!((a.pos - b.pos).length() < 0.5, "{:?} vs {:?}", a.pos, b.pos);
+    }
+}
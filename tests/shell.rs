@@ -0,0 +1,52 @@
+use softbody::core::{Particle, Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn circle(radius: Scalar, n: usize) -> Vec<Vec2> {
+    (0..n)
+        .map(|i| {
+            let a = i as Scalar / n as Scalar * (std::f64::consts::TAU as Scalar);
+            Vec2::new(radius * a.cos(), radius * a.sin())
+        })
+        .collect()
+}
+
+/// 中心に向けて重い弾を撃ち込み、弾が到達した中心からの最小距離を返す
+fn closest_approach(shell_layers: usize) -> Scalar {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let config = SoftBodyConfig { stiffness: 0.5, shape_stiffness: 0.0, particle_radius: 6.0, shell_layers, ..Default::default() };
+    sim.add_convex_body(&circle(100.0, 24), &config).unwrap();
+    let mut bullet = Particle::new(-160.0, 0.0);
+    bullet.radius = 12.0;
+    bullet.inv_mass = 0.2;
+    bullet.vel = Vec2::new(900.0, 0.0);
+    sim.particles.push(bullet);
+    let bullet = sim.particle_count() - 1;
+
+    let mut closest = Scalar::INFINITY;
+    for _ in 0..60 {
+        sim.step(1.0 / 120.0);
+        closest = closest.min(sim.particles()[bullet].pos.length());
+    }
+    closest
+}
+
+#[test]
+fn shell_layers_add_concentric_rings() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let handle = sim.add_convex_body(&circle(100.0, 12), &SoftBodyConfig { shell_layers: 3, ..Default::default() }).unwrap();
+    let body = sim.soft_body(handle).unwrap();
+    assert_eq!(sim.particle_count(), 36);
+    assert_eq!(body.particle_indices.len(), 36);
+    assert_eq!(body.outline_wires.as_ref().unwrap().len(), 12);
+    assert_eq!(body.shape_constraint.as_ref().unwrap().particle_indices.len(), 36);
+    // 2層目は外周から 3 * 半径 だけ内側
+    assert!((sim.particles()[12].pos.length() - (100.0 - 24.0)).abs() < 1e-9);
+    assert!((sim.particles()[24].pos.length() - (100.0 - 48.0)).abs() < 1e-9);
+}
+
+/// 2層の殻は1層の殻よりも、点状の衝撃が内部へ突き抜けるのを防ぐことを確認
+#[test]
+fn thicker_shell_resists_point_impact() {
+    let single = closest_approach(1);
+    let double = closest_approach(2);
+    assert!(double > single + 10.0, "single = {single}, double = {double}");
+}
@@ -0,0 +1,709 @@
+use softbody::core::{Integrator, Particle, Scalar, ShapeError, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 2つのボディを含むシーンのバウンディングボックスが両方を囲むことを確認
+#[test]
+fn bounding_box_encloses_all_bodies() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(0.0, 0.0),
+        size: Vec2::new(100.0, 100.0),
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(300.0, 200.0),
+        size: Vec2::new(50.0, 50.0),
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+
+    let (min, max) = sim.bounding_box().unwrap();
+    assert_eq!(min, Vec2::new(-55.0, -55.0));
+    assert_eq!(max, Vec2::new(330.0, 230.0));
+    for p in sim.particles() {
+        assert!(p.pos.x - p.radius >= min.x && p.pos.y - p.radius >= min.y);
+        assert!(p.pos.x + p.radius <= max.x && p.pos.y + p.radius <= max.y);
+    }
+}
+
+#[test]
+fn bounding_box_of_empty_simulation_is_none() {
+    let sim = Simulation::new(SimulationConfig::default());
+    assert_eq!(sim.bounding_box(), None);
+}
+
+/// 床の上を水平に滑る質点が、減衰分を除いて水平速度を保つことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn particle_sliding_on_floor_keeps_horizontal_speed() {
+    let mut sim = Simulation::new(SimulationConfig {
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(10_000.0, 500.0))),
+        gravity: Vec2::new(0.0, 600.0),
+        solver_iterations: 8,
+        ..Default::default()
+    });
+    let mut p = Particle::new(100.0, 500.0 - 8.0);
+    p.vel = Vec2::new(200.0, 0.0);
+    sim.particles.push(p);
+
+    let dt = 1.0 / 60.0;
+    let steps = 120;
+    for _ in 0..steps {
+        sim.step(dt);
+    }
+
+    let p = &sim.particles()[0];
+    let expected = 200.0 * sim.config().damping.powi(steps);
+    assert!((p.vel.x - expected).abs() < 1e-6, "vel.x = {}, expected {}", p.vel.x, expected);
+    assert!((p.pos.y - (500.0 - p.radius)).abs() < 1e-9);
+}
+
+/// 角に押し込まれた質点が、両方の辺から押し戻されることを確認
+#[test]
+fn particle_in_corner_is_pushed_out_along_both_normals() {
+    let mut sim = Simulation::new(SimulationConfig {
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(100.0, 100.0))),
+        gravity: Vec2::new(0.0, 0.0),
+        ..Default::default()
+    });
+    let mut p = Particle::new(95.0, 98.0);
+    p.vel = Vec2::new(60.0, 60.0);
+    sim.particles.push(p);
+
+    sim.step(1.0 / 60.0);
+
+    let p = &sim.particles()[0];
+    assert!((p.pos.x - (100.0 - p.radius)).abs() < 1e-9);
+    assert!((p.pos.y - (100.0 - p.radius)).abs() < 1e-9);
+}
+
+#[test]
+fn set_solver_iterations_is_clamped_to_one() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.set_solver_iterations(0);
+    assert_eq!(sim.config().solver_iterations, 1);
+    sim.set_solver_iterations(12);
+    assert_eq!(sim.config().solver_iterations, 12);
+}
+
+#[test]
+fn set_bounds_orders_corners() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.set_bounds(Some((Vec2::new(100.0, 0.0), Vec2::new(0.0, 50.0))));
+    assert_eq!(sim.config().bounds, Some((Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0))));
+    sim.set_bounds(None);
+    assert_eq!(sim.config().bounds, None);
+}
+
+/// 重力の変更が次のステップから反映されることを確認
+#[test]
+fn set_gravity_takes_effect_next_step() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, ..Default::default() });
+    sim.particles.push(Particle::new(0.0, 0.0));
+    let dt = 0.1;
+
+    sim.step(dt);
+    assert_eq!(sim.particles()[0].vel, Vec2::new(0.0, 0.0));
+
+    sim.set_gravity(Vec2::new(10.0, 0.0));
+    sim.step(dt);
+    let vel = sim.particles()[0].vel;
+    assert!((vel.x - 1.0).abs() < 1e-9 && vel.y.abs() < 1e-9);
+}
+
+#[test]
+fn springs_iterates_over_every_body() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 3, ..Default::default() }).unwrap();
+    sim.add_soft_body(&SoftBodyConfig { rows: 2, cols: 4, ..Default::default() }).unwrap();
+
+    let per_body: usize = sim.soft_bodies().iter().map(|sb| sb.springs.len()).sum();
+    assert_eq!(per_body, 12 + 10);
+    assert_eq!(sim.springs().count(), per_body);
+
+    for spring in sim.springs_mut() {
+        spring.stiffness = 0.5;
+    }
+    assert!(sim.springs().all(|s| s.stiffness == 0.5));
+}
+
+/// 質点の格納順序を入れ替えても、衝突解決後の位置が完全に一致することを確認
+#[test]
+fn collision_resolution_is_independent_of_storage_order() {
+    let positions: Vec<Vec2> = (0..12)
+        .map(|i| {
+            let t = i as Scalar;
+            Vec2::new((t * 7.3) % 40.0, (t * 3.1) % 25.0)
+        })
+        .collect();
+    let config = SimulationConfig { gravity: Vec2::new(0.0, 100.0), ..Default::default() };
+
+    let mut original = Simulation::new(config.clone());
+    for p in &positions {
+        original.particles.push(Particle::new(p.x, p.y));
+    }
+
+    // 格納順序を入れ替えたシミュレーション（perm[k] 番目の質点を k 番目に格納）
+    let perm = [7, 2, 11, 0, 5, 9, 1, 10, 3, 8, 6, 4];
+    let mut shuffled = Simulation::new(config);
+    for &k in &perm {
+        shuffled.particles.push(Particle::new(positions[k].x, positions[k].y));
+    }
+
+    for _ in 0..5 {
+        original.step(1.0 / 60.0);
+        shuffled.step(1.0 / 60.0);
+    }
+
+    for (k, &orig_idx) in perm.iter().enumerate() {
+        assert_eq!(shuffled.particles()[k].pos, original.particles()[orig_idx].pos);
+        assert_eq!(shuffled.particles()[k].vel, original.particles()[orig_idx].vel);
+    }
+}
+
+#[test]
+fn with_capacity_reserves_and_counts_match() {
+    let mut sim = Simulation::with_capacity(SimulationConfig::default(), 1000, 10);
+    assert!(sim.particles.capacity() >= 1000);
+    assert_eq!(sim.particle_count(), 0);
+    assert_eq!(sim.soft_body_count(), 0);
+
+    sim.add_soft_body(&SoftBodyConfig { rows: 4, cols: 5, ..Default::default() }).unwrap();
+    sim.add_soft_body(&SoftBodyConfig { rows: 2, cols: 2, ..Default::default() }).unwrap();
+    assert_eq!(sim.particle_count(), 24);
+    assert_eq!(sim.particle_count(), sim.particles().len());
+    assert_eq!(sim.soft_body_count(), 2);
+}
+
+/// `step(0.0)` や負の `dt` が状態を変えず、NaN を生まないことを確認
+#[test]
+fn step_with_non_positive_dt_is_a_no_op() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig::default()).unwrap();
+    sim.step(1.0 / 60.0);
+    let before: Vec<Particle> = sim.particles().to_vec();
+
+    sim.step(0.0);
+    sim.step(-1.0 / 60.0);
+    sim.step(Scalar::NAN);
+
+    assert_eq!(sim.particles(), &before[..]);
+    assert!(sim.particles().iter().all(|p| p.pos.x.is_finite() && p.pos.y.is_finite()));
+}
+
+/// 質点半径が間隔に対して大きすぎるグリッドはエラーになり、シミュレーションを変更しないことを確認
+#[test]
+fn overlapping_grid_particles_are_rejected() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let oversized = SoftBodyConfig {
+        size: Vec2::new(40.0, 40.0),
+        rows: 5,
+        cols: 5,
+        particle_radius: 8.0,
+        ..Default::default()
+    };
+    assert_eq!(sim.add_soft_body(&oversized), Err(ShapeError::OverlappingParticles));
+    assert_eq!(sim.particle_count(), 0);
+    assert_eq!(sim.soft_body_count(), 0);
+
+    // 半径を間隔に合わせれば生成でき、最初のステップで弾け飛ばない
+    let fixed = SoftBodyConfig { particle_radius: 5.0, ..oversized };
+    sim.add_soft_body(&fixed).unwrap();
+    sim.step(1.0 / 60.0);
+    let max_speed = sim.particles().iter().map(|p| p.vel.length()).fold(0.0, Scalar::max);
+    assert!(max_speed < 50.0, "max_speed = {max_speed}");
+}
+
+#[test]
+fn interpolated_positions_blend_prev_and_current() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let mut p = Particle::new(0.0, 0.0);
+    p.vel = Vec2::new(60.0, -30.0);
+    sim.particles.push(p);
+    sim.step(1.0 / 60.0);
+
+    let p = &sim.particles()[0];
+    assert_eq!(sim.interpolated_positions(0.0), vec![p.prev_pos]);
+    assert_eq!(sim.interpolated_positions(1.0), vec![p.pos]);
+    let mid = sim.interpolated_positions(0.5)[0];
+    assert!((mid - Vec2::new(0.5, -0.25)).length() < 1e-12);
+}
+
+/// 各積分方式での自由落下の軌跡が解析解 `y = g t^2 / 2` に一致することを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn free_fall_matches_analytic_solution_for_each_integrator() {
+    let g = 600.0;
+    let dt = 1.0 / 120.0;
+    for integrator in [Integrator::Verlet, Integrator::SemiImplicitEuler] {
+        let mut sim = Simulation::new(SimulationConfig {
+            gravity: Vec2::new(0.0, g),
+            damping: 1.0,
+            integrator,
+            ..Default::default()
+        });
+        sim.particles.push(Particle::new(0.0, 0.0));
+
+        for n in 1..=120 {
+            sim.step(dt);
+            let t = n as Scalar * dt;
+            let p = &sim.particles()[0];
+            let analytic = 0.5 * g * t * t;
+            // 1次の積分なので誤差は g * dt * t / 2 程度
+            assert!((p.pos.y - analytic).abs() <= 0.5 * g * dt * t + 1e-9, "{integrator:?} at t = {t}");
+            assert!((p.vel.y - g * t).abs() < 1e-6, "{integrator:?} at t = {t}");
+        }
+    }
+}
+
+/// 半陰的オイラー法でも、床の上で静止したボディの速度は重力で増え続けずに 0 付近に留まることを確認
+#[test]
+fn semi_implicit_euler_body_resting_on_floor_stays_at_rest() {
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 980.0),
+        bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))),
+        integrator: Integrator::SemiImplicitEuler,
+        ..Default::default()
+    });
+    sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(200.0, 265.0),
+        size: Vec2::new(60.0, 60.0),
+        particle_radius: 5.0,
+        ..Default::default()
+    }).unwrap();
+
+    for _ in 0..300 {
+        sim.step(1.0 / 60.0);
+    }
+    for _ in 0..60 {
+        sim.step(1.0 / 60.0);
+        let max_speed = sim.particles().iter().map(|p| p.vel.length()).fold(0.0, Scalar::max);
+        assert!(max_speed < 1.0, "max speed {max_speed}");
+    }
+}
+
+/// 重なった2つの質点を押し離すとき、`Verlet` では押し戻しが速度として残って離れ続けるが、
+/// `SemiImplicitEuler` では補正が速度に加わらず、押し離した位置で止まることを確認
+#[test]
+fn integrators_differ_in_how_overlap_correction_feeds_velocity() {
+    let run = |integrator: Integrator| {
+        let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, integrator, ..Default::default() });
+        let config = SoftBodyConfig { particle_radius: 10.0, ..Default::default() };
+        sim.add_chain(&[Vec2::new(100.0, 100.0)], &config);
+        sim.add_chain(&[Vec2::new(110.0, 100.0)], &config);
+        let mut gaps = Vec::new();
+        for _ in 0..10 {
+            sim.step(1.0 / 60.0);
+            gaps.push(sim.particles()[1].pos.x - sim.particles()[0].pos.x);
+        }
+        (gaps, sim.particles()[1].vel.x)
+    };
+    let (verlet, verlet_speed) = run(Integrator::Verlet);
+    let (euler, euler_speed) = run(Integrator::SemiImplicitEuler);
+
+    // どちらも最初のステップで接触距離まで押し離される
+    assert!((verlet[0] - 20.0).abs() < 1e-4 && (euler[0] - 20.0).abs() < 1e-4, "{} {}", verlet[0], euler[0]);
+    // Verlet では押し戻し (5 / dt) が速度になり、その後も離れ続ける
+    assert!((verlet_speed - 300.0).abs() < 1e-3, "{verlet_speed}");
+    assert!(verlet[9] > 100.0, "{}", verlet[9]);
+    // 半陰的オイラー法では速度が加わらず、接触距離で止まったまま
+    assert!(euler_speed.abs() < 1e-9, "{euler_speed}");
+    assert!(euler.iter().all(|gap| (gap - 20.0).abs() < 1e-4), "{euler:?}");
+}
+
+/// 爆発の中心に近い質点ほど大きく外向きに飛ばされ、半径外の質点は影響を受けないことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn radial_impulse_falls_off_with_distance() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    for x in [10.0, 80.0, 150.0] {
+        sim.particles.push(Particle::new(x, 0.0));
+    }
+    let mut heavy = Particle::new(-10.0, 0.0);
+    heavy.inv_mass = 0.25;
+    sim.particles.push(heavy);
+
+    sim.apply_radial_impulse(Vec2::new(0.0, 0.0), 100.0, 100.0);
+
+    let v: Vec<Vec2> = sim.particles().iter().map(|p| p.vel).collect();
+    assert!(v[0].x > v[1].x && v[1].x > 0.0);
+    assert!((v[0].x - 90.0).abs() < 1e-9 && (v[1].x - 20.0).abs() < 1e-9);
+    assert_eq!(v[2], Vec2::new(0.0, 0.0));
+    // 同じ距離でも重い質点は遅く、向きは中心から外向き
+    assert!((v[3].x + 90.0 * 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn total_mass_and_center_of_mass_skip_fixed_particles() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    assert_eq!(sim.total_mass(), 0.0);
+    assert_eq!(sim.center_of_mass(), Vec2::new(0.0, 0.0));
+
+    sim.particles.push(Particle::new(0.0, 0.0));
+    let mut heavy = Particle::new(30.0, 0.0);
+    heavy.inv_mass = 0.5;
+    sim.particles.push(heavy);
+    let mut anchor = Particle::new(1000.0, 1000.0);
+    anchor.is_fixed = true;
+    anchor.inv_mass = 0.0;
+    sim.particles.push(anchor);
+
+    assert_eq!(sim.total_mass(), 3.0);
+    assert_eq!(sim.center_of_mass(), Vec2::new(20.0, 0.0));
+}
+
+/// 重力も境界も無い2ボディの衝突で、全運動量がステップの前後で保存されることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn two_body_collision_conserves_linear_momentum() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, ..Default::default() });
+    let grid = |x: Scalar| SoftBodyConfig { center: Vec2::new(x, 0.0), size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() };
+    let left = sim.add_soft_body(&grid(0.0)).unwrap();
+    let right = sim.add_soft_body(&SoftBodyConfig { particle_inv_mass: 0.5, ..grid(60.0) }).unwrap();
+    let left_indices = sim.soft_body(left).unwrap().particle_indices.clone();
+    let right_indices = sim.soft_body(right).unwrap().particle_indices.clone();
+    for i in left_indices {
+        sim.particles[i].vel = Vec2::new(300.0, 20.0);
+    }
+    for i in right_indices {
+        sim.particles[i].vel = Vec2::new(-100.0, 0.0);
+    }
+
+    let initial = sim.total_linear_momentum();
+    let mut collided = false;
+    for _ in 0..30 {
+        sim.step(1.0 / 60.0);
+        let p = sim.total_linear_momentum();
+        assert!((p - initial).length() < 1e-6 * initial.length(), "{p:?} vs {initial:?}");
+        collided |= sim.particles()[0].vel.x < 250.0;
+    }
+    assert!(collided);
+}
+
+/// 1本だけ大きく引き伸ばされたバネの両端の応力が高く、内部の質点の応力が低いことを確認
+#[test]
+fn particle_stress_highlights_stretched_spring() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() }).unwrap();
+    assert!(sim.particle_stress().iter().all(|&s| s == 0.0));
+
+    // 質点 0 と 1 の間のバネ（長さ 20）の静止長を 10 にして、100% 引き伸ばされた状態にする
+    let spring = sim.springs_mut().find(|s| (s.p1_index, s.p2_index) == (0, 1)).unwrap();
+    spring.rest_length = 10.0;
+
+    let stress = sim.particle_stress();
+    assert_eq!(stress.len(), 9);
+    assert!((stress[0] - 1.0).abs() < 1e-12 && (stress[1] - 1.0).abs() < 1e-12);
+    // 中央の質点 (4) とその他の質点は応力なし
+    assert_eq!(stress[4], 0.0);
+    assert!(stress[2..].iter().all(|&s| s < 1e-12));
+}
+
+/// 積み重ねたシーンをウォームスタートすると、速度がほぼ0になり、その後のステップでも大きく動かないことを確認
+#[test]
+fn warm_start_settles_stacked_scene() {
+    let mut sim = Simulation::new(SimulationConfig {
+        bounds: Some((Vec2::new(-200.0, -400.0), Vec2::new(200.0, 100.0))),
+        ..Default::default()
+    });
+    // 床から浮いた位置に2つのボディを縦に積む
+    for y in [20.0, -40.0] {
+        sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(0.0, y), size: Vec2::new(50.0, 50.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() }).unwrap();
+    }
+    let max_speed = |sim: &Simulation| sim.particles().iter().map(|p| p.vel.length()).fold(0.0, Scalar::max);
+
+    sim.warm_start(300, 1.0 / 60.0);
+    // 床まで沈んでいる
+    let lowest = sim.particles().iter().map(|p| p.pos.y + p.radius).fold(Scalar::MIN, Scalar::max);
+    assert!((lowest - 100.0).abs() < 1.0, "lowest = {lowest}");
+
+    // 静止状態に落ち着いているため、その後の数ステップでほとんど動かない
+    let settled: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+        assert!(max_speed(&sim) < 10.0, "max speed after warm start = {}", max_speed(&sim));
+    }
+    let drift = sim.particles().iter().zip(&settled).map(|(p, &start)| (p.pos - start).length()).fold(0.0, Scalar::max);
+    assert!(drift < 1e-3, "drift = {drift}");
+}
+
+/// 同じシーンでも、剛性の倍率が低いほど荷重による変形が大きく、保存された剛性は変わらないことを確認
+#[test]
+fn lower_stiffness_multiplier_deforms_more_under_load() {
+    let sag = |multiplier: Scalar| {
+        let mut sim = Simulation::new(SimulationConfig { stiffness_multiplier: multiplier, ..Default::default() });
+        // 上端を固定した縦のロープの下端に重り
+        let points: Vec<Vec2> = (0..6).map(|i| Vec2::new(0.0, 20.0 * i as Scalar)).collect();
+        sim.add_chain(&points, &SoftBodyConfig { stiffness: 0.5, shape_stiffness: 0.0, particle_radius: 4.0, pin_first: true, ..Default::default() });
+        sim.particles[5].inv_mass = 0.1;
+        for _ in 0..120 {
+            sim.step(1.0 / 60.0);
+        }
+        assert!(sim.springs().all(|s| s.stiffness == 0.5));
+        sim.particles()[5].pos.y - 100.0
+    };
+    let (goopy, normal, rigid) = (sag(0.2), sag(1.0), sag(3.0));
+    assert!(goopy > normal && normal > rigid, "{goopy} {normal} {rigid}");
+    assert!(rigid > 0.0);
+}
+
+/// 返された範囲が実際に生成された質点と一致し、先頭 `cols` 個を固定すると最上段が固定されることを確認
+#[test]
+fn add_soft_body_at_returns_created_particle_range() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.particles.push(Particle::new(500.0, 500.0));
+    let config = SoftBodyConfig { size: Vec2::new(60.0, 40.0), rows: 3, cols: 4, particle_radius: 5.0, ..Default::default() };
+    let (handle, range) = sim.add_soft_body_at(&config).unwrap();
+
+    assert_eq!(range, 1..13);
+    assert_eq!(sim.soft_body(handle).unwrap().particle_indices, range.clone().collect::<Vec<_>>());
+
+    let top_row = range.start..range.start + config.cols;
+    for i in top_row.clone() {
+        sim.particles[i].is_fixed = true;
+        sim.particles[i].inv_mass = 0.0;
+    }
+    let top_y = sim.particles()[range.start].pos.y;
+    for _ in 0..60 {
+        sim.step(1.0 / 60.0);
+    }
+    for i in range {
+        let p = &sim.particles()[i];
+        if top_row.contains(&i) {
+            assert_eq!(p.pos.y, top_y);
+        } else {
+            assert!(p.pos.y > top_y);
+        }
+    }
+    assert!(top_row.clone().all(|i| sim.particles()[i].pos.y == -20.0));
+}
+
+/// 硬いシーンでは柔らかいシーンより多くの反復回数が推奨され、`auto_iterations` が無効なら設定値は変わらないことを確認
+#[test]
+fn stiff_scene_recommends_more_iterations() {
+    let scene = |stiffness: Scalar| {
+        let mut sim = Simulation::new(SimulationConfig::default());
+        sim.add_soft_body(&SoftBodyConfig {
+            size: Vec2::new(200.0, 200.0),
+            rows: 10,
+            cols: 10,
+            stiffness,
+            shape_stiffness: stiffness,
+            bend_stiffness: stiffness,
+            ..Default::default()
+        }).unwrap();
+        sim
+    };
+    let soft = scene(0.05);
+    let stiff = scene(1.0);
+    assert!(stiff.recommended_iterations() > soft.recommended_iterations());
+    assert!(Simulation::new(SimulationConfig::default()).recommended_iterations() >= 1);
+
+    let mut stiff = stiff;
+    stiff.step(1.0 / 60.0);
+    assert_eq!(stiff.config().solver_iterations, 8);
+    assert!(!stiff.config().auto_iterations);
+}
+
+/// `freeze_velocities` の後、重力なしで1ステップ進めても位置が変わらないことを確認
+#[test]
+fn freeze_velocities_stops_all_motion() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 3, ..Default::default() }).unwrap();
+    for p in &mut sim.particles {
+        p.vel = Vec2::new(300.0, -120.0);
+    }
+    sim.step(1.0 / 60.0);
+
+    sim.freeze_velocities();
+    let before: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    assert!(sim.particles().iter().all(|p| p.prev_pos == p.pos && p.vel == Vec2::new(0.0, 0.0)));
+    sim.step(1.0 / 60.0);
+    for (p, b) in sim.particles().iter().zip(&before) {
+        assert!((p.pos - *b).length() < 1e-9, "{:?} moved from {:?}", p.pos, b);
+    }
+}
+
+/// `damp_velocities` が速度と `prev_pos` からの変位を同じ倍率で縮めることを確認
+#[test]
+fn damp_velocities_scales_velocity_and_displacement() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    sim.add_soft_body(&SoftBodyConfig { rows: 1, cols: 1, size: Vec2::new(0.0, 0.0), ..Default::default() }).unwrap();
+    sim.particles[0].vel = Vec2::new(60.0, 0.0);
+    sim.step(1.0 / 60.0);
+    let displacement = sim.particles[0].pos - sim.particles[0].prev_pos;
+    let vel = sim.particles[0].vel;
+
+    sim.damp_velocities(0.5);
+    let p = &sim.particles[0];
+    assert!((p.vel - vel * 0.5).length() < 1e-12);
+    assert!(((p.pos - p.prev_pos) - displacement * 0.5).length() < 1e-12);
+}
+
+/// `step_with_callback` のコールバックが反復回数と同じ回数だけ呼ばれ、拘束の誤差が反復ごとに減っていくことを確認
+#[test]
+fn step_with_callback_sees_each_iteration_converge() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), solver_iterations: 8, ..Default::default() });
+    let points = [Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(40.0, 0.0)];
+    sim.add_chain(&points, &SoftBodyConfig { stiffness: 0.5, shape_stiffness: 0.0, particle_radius: 4.0, ..Default::default() });
+    // 両端を引き伸ばす
+    sim.particles[0].pos.x = -10.0;
+    sim.particles[2].pos.x = 50.0;
+    for p in &mut sim.particles {
+        p.prev_pos = p.pos;
+    }
+    let springs: Vec<(usize, usize, Scalar)> = sim.springs().map(|s| (s.p1_index, s.p2_index, s.rest_length)).collect();
+
+    let mut calls = Vec::new();
+    let mut errors = Vec::new();
+    sim.step_with_callback(1.0 / 60.0, |iteration, particles| {
+        calls.push(iteration);
+        let error = springs.iter()
+            .map(|&(a, b, rest)| ((particles[a].pos - particles[b].pos).length() - rest).abs())
+            .fold(0.0, Scalar::max);
+        errors.push(error);
+    });
+
+    assert_eq!(calls, (0..8).collect::<Vec<_>>());
+    assert!(errors.windows(2).all(|w| w[1] < w[0]), "{errors:?}");
+    assert!(errors[7] < errors[0] * 0.2, "{errors:?}");
+
+    let mut called = false;
+    sim.step_with_callback(0.0, |_, _| called = true);
+    assert!(!called);
+}
+
+/// `apply_config` で反復回数・境界・シードを差し替えると、検証された値が次のステップから使われることを確認
+#[test]
+fn apply_config_validates_and_takes_effect() {
+    let mut sim = Simulation::new(SimulationConfig { solver_iterations: 8, ..Default::default() });
+    sim.add_chain(&[Vec2::new(0.0, 0.0), Vec2::new(20.0, 0.0)], &SoftBodyConfig::default());
+
+    sim.apply_config(SimulationConfig {
+        solver_iterations: 3,
+        bounds: Some((Vec2::new(400.0, 300.0), Vec2::new(0.0, 0.0))),
+        ..sim.config().clone()
+    });
+    assert_eq!(sim.config().bounds, Some((Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0))));
+    let mut calls = 0;
+    sim.step_with_callback(1.0 / 60.0, |_, _| calls += 1);
+    assert_eq!(calls, 3);
+
+    sim.apply_config(SimulationConfig { solver_iterations: 0, ..sim.config().clone() });
+    assert_eq!(sim.config().solver_iterations, 1);
+
+    // シードを変えると、新しく作ったシミュレーションと同じ乱数列になる
+    let body = SoftBodyConfig { size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, jitter: 2.0, ..Default::default() };
+    let mut fresh = Simulation::new(SimulationConfig { seed: 7, ..Default::default() });
+    fresh.add_soft_body(&body).unwrap();
+    let mut reloaded = Simulation::new(SimulationConfig::default());
+    reloaded.apply_config(SimulationConfig { seed: 7, ..Default::default() });
+    reloaded.add_soft_body(&body).unwrap();
+    let positions = |sim: &Simulation| sim.particles().iter().map(|p| p.pos).collect::<Vec<_>>();
+    assert_eq!(positions(&fresh), positions(&reloaded));
+}
+
+/// 重複した頂点を含む多角形や間隔0のグリッドは、NaN を生むボディを作らずにエラーになることを確認
+#[test]
+fn coincident_points_are_rejected() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let square = [Vec2::new(0.0, 0.0), Vec2::new(40.0, 0.0), Vec2::new(40.0, 40.0), Vec2::new(40.0, 40.0), Vec2::new(0.0, 40.0)];
+    assert_eq!(sim.add_convex_body(&square, &SoftBodyConfig::default()), Err(ShapeError::CoincidentPoints));
+    let wrapped = [Vec2::new(0.0, 0.0), Vec2::new(40.0, 0.0), Vec2::new(40.0, 40.0), Vec2::new(0.0, 0.0)];
+    assert_eq!(sim.add_convex_body(&wrapped, &SoftBodyConfig::default()), Err(ShapeError::CoincidentPoints));
+
+    let flat = SoftBodyConfig { size: Vec2::new(40.0, 0.0), rows: 2, cols: 3, particle_radius: 0.0, ..Default::default() };
+    assert_eq!(sim.add_soft_body(&flat), Err(ShapeError::CoincidentPoints));
+    assert!(sim.particles().is_empty());
+}
+
+/// 高く積んだボディは、`collision_iterations` を増やすとバネの剛性を変えずにめり込みが小さくなることを確認
+#[test]
+fn collision_iterations_reduce_stack_penetration() {
+    let penetration = |collision_iterations: usize| {
+        let mut sim = Simulation::new(SimulationConfig {
+            gravity: Vec2::new(0.0, 980.0),
+            solver_iterations: 2,
+            bounds: Some((Vec2::new(0.0, 0.0), Vec2::new(200.0, 400.0))),
+            collision_iterations,
+            ..Default::default()
+        });
+        let mut ranges = Vec::new();
+        for i in 0..8 {
+            let config = SoftBodyConfig {
+                center: Vec2::new(100.0, 380.0 - i as Scalar * 42.0),
+                size: Vec2::new(40.0, 30.0),
+                rows: 3,
+                cols: 3,
+                particle_radius: 5.0,
+                ..Default::default()
+            };
+            ranges.push(sim.add_soft_body_at(&config).unwrap().1);
+        }
+        let stiffness: Vec<Scalar> = sim.springs().map(|s| s.stiffness).collect();
+        for _ in 0..120 {
+            sim.step(1.0 / 60.0);
+        }
+        assert_eq!(sim.springs().map(|s| s.stiffness).collect::<Vec<_>>(), stiffness);
+
+        let particles = sim.particles();
+        let mut deepest: Scalar = 0.0;
+        for (a, range_a) in ranges.iter().enumerate() {
+            for range_b in &ranges[a + 1..] {
+                for i in range_a.clone() {
+                    for j in range_b.clone() {
+                        let overlap = particles[i].radius + particles[j].radius - (particles[i].pos - particles[j].pos).length();
+                        deepest = deepest.max(overlap);
+                    }
+                }
+            }
+        }
+        deepest
+    };
+
+    let single = penetration(1);
+    let repeated = penetration(6);
+    assert!(single > 0.0);
+    assert!(repeated < single * 0.7, "single {single}, repeated {repeated}");
+    // 0 は 1 と同じ
+    assert_eq!(penetration(0), single);
+}
+
+/// `enable_collisions = false` の場合、重なった2つの質点が押し戻されずにすれ違うことを確認
+#[test]
+fn disabled_collisions_let_particles_pass_through() {
+    let dt = 1.0 / 60.0;
+    let run = |enable_collisions: bool| {
+        let config = SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, enable_collisions, ..Default::default() };
+        let mut sim = Simulation::new(config);
+        sim.add_chain(&[Vec2::new(0.0, 0.0)], &SoftBodyConfig::default());
+        sim.add_chain(&[Vec2::new(4.0, 0.0)], &SoftBodyConfig::default());
+        sim.particles[0].set_velocity(Vec2::new(60.0, 0.0), dt);
+        sim.particles[1].set_velocity(Vec2::new(-60.0, 0.0), dt);
+        for _ in 0..30 {
+            sim.step(dt);
+        }
+        (sim.particles[0].pos.x, sim.particles[1].pos.x)
+    };
+
+    let (a, b) = run(false);
+    assert!((a - 30.0).abs() < 1e-9 && (b + 26.0).abs() < 1e-9, "{a}, {b}");
+    let (a, b) = run(true);
+    assert!(a < b, "{a}, {b}");
+}
+
+/// `enable_boundaries = false` の場合、境界があっても質点が外へ出られることを確認
+#[test]
+fn disabled_boundaries_ignore_bounds() {
+    let config = SimulationConfig {
+        bounds: Some((Vec2::new(-100.0, -100.0), Vec2::new(100.0, 100.0))),
+        enable_boundaries: false,
+        ..Default::default()
+    };
+    let mut sim = Simulation::new(config);
+    sim.add_chain(&[Vec2::new(0.0, 90.0)], &SoftBodyConfig::default());
+    for _ in 0..60 {
+        sim.step(1.0 / 60.0);
+    }
+    assert!(sim.particles[0].pos.y > 100.0);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "f32")]
+
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// `f32` でも、床の上に積み重なる2つのボディを数百ステップ進めて NaN や発散が起きず、静止に近づくことを確認
+#[test]
+fn basic_scene_stays_stable_in_single_precision() {
+    assert_eq!(core::mem::size_of::<Scalar>(), 4);
+
+    let (lo, hi) = (Vec2::new(0.0, 0.0), Vec2::new(400.0, 300.0));
+    let mut sim = Simulation::new(SimulationConfig {
+        gravity: Vec2::new(0.0, 980.0),
+        bounds: Some((lo, hi)),
+        ..Default::default()
+    });
+    for center in [Vec2::new(200.0, 240.0), Vec2::new(210.0, 150.0)] {
+        sim.add_soft_body(&SoftBodyConfig {
+            center,
+            size: Vec2::new(60.0, 60.0),
+            particle_radius: 5.0,
+            ..Default::default()
+        }).unwrap();
+    }
+
+    for _ in 0..600 {
+        sim.step(1.0 / 60.0);
+        for p in sim.particles() {
+            assert!(p.pos.x.is_finite() && p.pos.y.is_finite(), "{:?}", p.pos);
+            assert!(p.vel.x.is_finite() && p.vel.y.is_finite(), "{:?}", p.vel);
+        }
+    }
+    for p in sim.particles() {
+        assert!(p.pos.x >= lo.x - 1.0 && p.pos.x <= hi.x + 1.0 && p.pos.y >= lo.y - 1.0 && p.pos.y <= hi.y + 1.0, "{:?}", p.pos);
+        assert!(p.vel.length() < 5.0, "{:?}", p.vel);
+    }
+}
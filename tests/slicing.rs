@@ -0,0 +1,118 @@
+use softbody::core::geometry::polygon_area;
+use softbody::core::{Line, Scalar, Simulation, SimulationConfig, SoftBodyConfig, SoftBodyHandle, Vec2};
+
+fn square_scene() -> (Simulation, SoftBodyHandle) {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let square = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(100.0, 0.0),
+        Vec2::new(100.0, 100.0),
+        Vec2::new(0.0, 100.0),
+    ];
+    let handle = sim.add_convex_body(&square, &SoftBodyConfig::default()).unwrap();
+    (sim, handle)
+}
+
+fn outline(sim: &Simulation, handle: SoftBodyHandle) -> Vec<Vec2> {
+    let body = sim.soft_body(handle).unwrap();
+    body.particle_indices.iter().map(|&i| sim.particles()[i].pos).collect()
+}
+
+/// 正方形を中央で切断すると、面積の合計が元と等しい2つのボディになることを確認
+#[test]
+fn slicing_square_across_middle_yields_two_halves() {
+    let (mut sim, handle) = square_scene();
+    let line = Line::new(Vec2::new(50.0, -10.0), Vec2::new(50.0, 110.0));
+
+    let (a, b) = sim.slice_body(handle, line).unwrap();
+    assert!(sim.soft_body(handle).is_none());
+    assert_eq!(sim.soft_body_count(), 2);
+    assert_eq!(sim.particle_count(), 4 + 4);
+
+    for h in [a, b] {
+        let body = sim.soft_body(h).unwrap();
+        assert_eq!(body.particle_indices.len(), 4);
+        assert_eq!(body.springs.len(), 4);
+        assert_eq!(body.outline_wires.as_ref().unwrap().len(), 4);
+        assert!(body.shape_constraint.is_some());
+    }
+
+    let area_a = polygon_area(&outline(&sim, a));
+    let area_b = polygon_area(&outline(&sim, b));
+    assert!((area_a - 5000.0).abs() < 1e-9);
+    assert!((area_a + area_b - 10_000.0).abs() < 1e-9);
+}
+
+/// 1辺しか横切らない線では切断されないことを確認
+#[test]
+fn slicing_with_line_not_crossing_twice_returns_none() {
+    let (mut sim, handle) = square_scene();
+    let line = Line::new(Vec2::new(50.0, -10.0), Vec2::new(50.0, 50.0));
+
+    assert!(sim.slice_body(handle, line).is_none());
+    assert!(sim.soft_body(handle).is_some());
+    assert_eq!(sim.particle_count(), 4);
+}
+
+/// 外周付きのグリッドは内部の質点を外周から再構築できないため切断されず、シミュレーションも変わらないことを確認
+#[test]
+fn slicing_outlined_grid_returns_none() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let config = SoftBodyConfig { center: Vec2::new(50.0, 50.0), size: Vec2::new(100.0, 100.0), rows: 4, cols: 4, outline: true, ..Default::default() };
+    let handle = sim.add_soft_body(&config).unwrap();
+    let springs = sim.soft_body(handle).unwrap().springs.len();
+    let line = Line::new(Vec2::new(50.0, -10.0), Vec2::new(50.0, 110.0));
+
+    assert!(sim.slice_body(handle, line).is_none());
+    assert_eq!(sim.soft_body_count(), 1);
+    assert_eq!(sim.particle_count(), 16);
+    assert_eq!(sim.soft_body(handle).unwrap().springs.len(), springs);
+}
+
+/// 両端の列を固定した布状のグリッドを作成し、中央の2列の平均の高さを返します。
+fn middle_sag_after_cut(cut: bool) -> (usize, Scalar) {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 600.0), ..Default::default() });
+    let handle = sim
+        .add_soft_body(&SoftBodyConfig {
+            center: Vec2::new(0.0, 0.0),
+            size: Vec2::new(100.0, 100.0),
+            rows: 5,
+            cols: 5,
+            stiffness: 1.0,
+            shape_stiffness: 0.0,
+            particle_radius: 4.0,
+            ..Default::default()
+        })
+        .unwrap();
+    for i in 0..sim.particle_count() {
+        let col = i % 5;
+        if col == 0 || col == 4 {
+            sim.particles[i].is_fixed = true;
+            sim.particles[i].inv_mass = 0.0;
+        }
+    }
+
+    // 2列目と3列目の間 (x = 12.5) を縦に切る
+    let removed = if cut { sim.cut_springs(Line::new(Vec2::new(12.5, -100.0), Vec2::new(12.5, 100.0))) } else { 0 };
+    for _ in 0..60 {
+        sim.step(1.0 / 60.0);
+    }
+
+    let body = sim.soft_body(handle).unwrap();
+    let middle: Vec<Scalar> = body
+        .particle_indices
+        .iter()
+        .filter(|&&i| i % 5 == 2 || i % 5 == 3)
+        .map(|&i| sim.particles()[i].pos.y)
+        .collect();
+    (removed, middle.iter().sum::<Scalar>() / middle.len() as Scalar)
+}
+
+/// 中央を横切る線で横方向のバネだけが切られ、その部分が垂れ下がることを確認
+#[test]
+fn cutting_springs_through_grid_middle_loosens_it() {
+    let (removed, cut_y) = middle_sag_after_cut(true);
+    let (_, intact_y) = middle_sag_after_cut(false);
+    assert_eq!(removed, 5);
+    assert!(cut_y > intact_y + 10.0, "cut = {cut_y}, intact = {intact_y}");
+}
@@ -0,0 +1,54 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn resting_scene() -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(200.0, 200.0), size: Vec2::new(80.0, 80.0), rows: 4, cols: 4, ..Default::default() }).unwrap();
+    sim
+}
+
+fn overlapping_body() -> SoftBodyConfig {
+    SoftBodyConfig { center: Vec2::new(230.0, 180.0), size: Vec2::new(60.0, 60.0), rows: 3, cols: 3, ..Default::default() }
+}
+
+fn max_speed(sim: &Simulation) -> Scalar {
+    sim.particles().iter().map(|p| p.vel.length()).fold(0.0, Scalar::max)
+}
+
+/// 既存のボディに重ねて生成しても、押し出されてから追加されるため、次のステップでどちらのボディも弾け飛ばないことを確認
+#[test]
+fn resolved_spawn_does_not_explode() {
+    // 比較のため、そのまま重ねて追加すると大きな速度が生じる
+    let mut naive = resting_scene();
+    naive.add_soft_body(&overlapping_body()).unwrap();
+    naive.step(1.0 / 60.0);
+    assert!(max_speed(&naive) > 100.0, "{}", max_speed(&naive));
+
+    let mut sim = resting_scene();
+    let before: Vec<Vec2> = sim.particles().iter().map(|p| p.pos).collect();
+    let handle = sim.add_soft_body_resolved(&overlapping_body()).unwrap();
+
+    // 既存の質点は動かず、新しい質点はどの既存の質点とも重ならない
+    let (existing, spawned) = sim.particles().split_at(before.len());
+    assert!(existing.iter().zip(&before).all(|(p, &pos)| p.pos == pos));
+    for a in spawned {
+        assert_eq!(a.pos, a.prev_pos);
+        for b in existing {
+            assert!((a.pos - b.pos).length() >= a.radius + b.radius - 1e-9);
+        }
+    }
+    assert_eq!(sim.soft_body(handle).unwrap().particle_indices.len(), 9);
+
+    sim.step(1.0 / 60.0);
+    assert!(max_speed(&sim) < 1e-6, "{}", max_speed(&sim));
+}
+
+/// 重ならない位置に生成した場合は `add_soft_body` と同じ位置に置かれることを確認
+#[test]
+fn resolved_spawn_without_overlap_is_unchanged() {
+    let mut sim = resting_scene();
+    let config = SoftBodyConfig { center: Vec2::new(400.0, 200.0), ..overlapping_body() };
+    sim.add_soft_body_resolved(&config).unwrap();
+    let mut plain = resting_scene();
+    plain.add_soft_body(&config).unwrap();
+    assert_eq!(sim.particles(), plain.particles());
+}
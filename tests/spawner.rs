@@ -0,0 +1,52 @@
+use softbody::core::{ShapeKind, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn small_grid() -> SoftBodyConfig {
+    SoftBodyConfig { size: Vec2::new(20.0, 20.0), particle_radius: 4.0, ..Default::default() }
+}
+
+/// 十分な時間だけステップを進めると、期待どおりの数のボディが出現位置に生成されることを確認
+#[test]
+fn spawner_emits_expected_number_of_bodies_at_position() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let position = Vec2::new(100.0, 50.0);
+    let spawner = sim.add_spawner(position, 0.5, small_grid(), ShapeKind::Grid { rows: 2, cols: 2 });
+
+    // 0.5 秒間隔で 2.1 秒 → 4 個
+    let mut counts = Vec::new();
+    for _ in 0..126 {
+        sim.step(1.0 / 60.0);
+        counts.push(sim.soft_bodies().len());
+        // 生成された直後のボディは出現位置を中心にしている
+        if counts.len() >= 2 && counts[counts.len() - 1] > counts[counts.len() - 2] {
+            let body = sim.soft_bodies().last().unwrap();
+            let center = body.particle_indices.iter().fold(Vec2::new(0.0, 0.0), |acc, &i| acc + sim.particles()[i].pos) * 0.25;
+            assert!((center - position).length() < 1e-9, "{center:?}");
+        }
+    }
+    assert_eq!(sim.soft_bodies().len(), 4);
+    assert_eq!(sim.spawner(spawner).unwrap().spawned(), 4);
+
+    // 多角形は相対座標の頂点を出現位置に合わせて生成する
+    let square = vec![Vec2::new(-10.0, -10.0), Vec2::new(10.0, -10.0), Vec2::new(10.0, 10.0), Vec2::new(-10.0, 10.0)];
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    sim.add_spawner(position, 0.1, SoftBodyConfig { particle_radius: 4.0, ..Default::default() }, ShapeKind::Polygon(square));
+    sim.step(0.1);
+    assert_eq!(sim.particles()[0].pos, Vec2::new(90.0, 40.0));
+}
+
+/// 生成数の上限に達すると生成が止まり、スポナーを取り除いても生成済みのボディは残ることを確認
+#[test]
+fn spawner_respects_max_bodies_and_removal() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let spawner = sim.add_spawner(Vec2::new(0.0, 0.0), 0.1, small_grid(), ShapeKind::Grid { rows: 2, cols: 2 });
+    sim.spawner_mut(spawner).unwrap().max_bodies = Some(2);
+    // 1 ステップで複数の間隔を跨いでも上限を超えない
+    sim.step(0.35);
+    assert_eq!(sim.soft_bodies().len(), 2);
+    sim.step(1.0);
+    assert_eq!(sim.soft_bodies().len(), 2);
+
+    assert!(sim.remove_spawner(spawner));
+    assert!(!sim.remove_spawner(spawner));
+    assert_eq!(sim.soft_bodies().len(), 2);
+}
@@ -0,0 +1,58 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, SoftBodyHandle, Vec2};
+
+fn scene() -> (Simulation, SoftBodyHandle) {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let body = sim.add_soft_body(&SoftBodyConfig {
+        center: Vec2::new(100.0, 100.0),
+        size: Vec2::new(60.0, 40.0),
+        rows: 3,
+        cols: 3,
+        ..Default::default()
+    }).unwrap();
+    // 質量を不均一にし、並進速度を与える
+    for (i, p) in sim.particles_of_body_mut(body).enumerate() {
+        p.inv_mass = 1.0 / (1.0 + i as Scalar);
+        p.vel = Vec2::new(10.0, -5.0);
+    }
+    (sim, body)
+}
+
+/// 角速度を与えると、角運動量が `omega * I` になり、運動量は変わらないことを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn angular_velocity_sets_expected_angular_momentum() {
+    let (mut sim, body) = scene();
+    let momentum = sim.total_linear_momentum();
+    let spin_before = sim.body_angular_momentum(body).unwrap();
+    assert!(spin_before.abs() < 1e-9);
+
+    let masses: Vec<(Vec2, Scalar)> = sim.particles_of_body(body).map(|p| (p.pos, 1.0 / p.inv_mass)).collect();
+    let total: Scalar = masses.iter().map(|&(_, m)| m).sum();
+    let center = masses.iter().fold(Vec2::new(0.0, 0.0), |acc, &(pos, m)| acc + pos * m) * (1.0 / total);
+    let inertia: Scalar = masses.iter().map(|&(pos, m)| m * (pos - center).length_squared()).sum();
+
+    assert!(sim.apply_angular_velocity(body, 2.0));
+    let spin = sim.body_angular_momentum(body).unwrap();
+    assert!((spin - 2.0 * inertia).abs() < 1e-9 * inertia, "{spin} vs {}", 2.0 * inertia);
+    assert!((sim.total_linear_momentum() - momentum).length() < 1e-9);
+
+    // トルク（角力積）はそのまま角運動量に加わる
+    assert!(sim.apply_torque(body, 500.0));
+    let spin_after = sim.body_angular_momentum(body).unwrap();
+    assert!((spin_after - spin - 500.0).abs() < 1e-6, "{spin_after}");
+    assert!((sim.total_linear_momentum() - momentum).length() < 1e-9);
+}
+
+/// 存在しないボディや、慣性モーメントの無いボディは拒否されることを確認
+#[test]
+fn spin_rejects_missing_or_degenerate_bodies() {
+    let (mut sim, body) = scene();
+    let single = sim.add_soft_body(&SoftBodyConfig { rows: 1, cols: 1, ..Default::default() }).unwrap();
+    assert!(sim.apply_angular_velocity(single, 1.0));
+    assert!(!sim.apply_torque(single, 1.0));
+
+    assert!(sim.remove_soft_body(body));
+    assert!(!sim.apply_angular_velocity(body, 1.0));
+    assert!(!sim.apply_torque(body, 1.0));
+    assert_eq!(sim.body_angular_momentum(body), None);
+}
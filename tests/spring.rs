@@ -0,0 +1,76 @@
+use softbody::core::{Particle, Simulation, SimulationConfig, SoftBodyConfig, Spring, Vec2};
+
+/// 静止長のバネは `0.0`、2倍に伸びたバネは上限の `1.0` になることを確認
+#[test]
+fn stretch_ratio_is_zero_at_rest_and_clamped_when_doubled() {
+    let mut particles = vec![Particle::new(0.0, 0.0), Particle::new(10.0, 0.0)];
+    let spring = Spring::new(0, 1, 1.0, &particles);
+    assert_eq!(spring.stretch_ratio(&particles), 0.0);
+
+    particles[1].pos.x = 20.0;
+    assert!((spring.stretch_ratio(&particles) - 1.0).abs() < 1e-12);
+    // 倍率を小さくすると上限に達しない
+    assert!((spring.stretch_ratio_scaled(&particles, 0.5) - 0.5).abs() < 1e-12);
+
+    // 縮んだ場合も伸縮の大きさとして扱う
+    particles[1].pos.x = 9.0;
+    assert!((spring.stretch_ratio(&particles) - 0.3).abs() < 1e-12);
+}
+
+/// `fast_length` を有効にしても、通常の大きさのシーンでは結果がほぼ変わらないことを確認
+#[test]
+fn fast_length_solver_matches_default() {
+    let run = |fast_length: bool| {
+        let mut sim = Simulation::new(SimulationConfig { fast_length, ..Default::default() });
+        sim.add_soft_body(&SoftBodyConfig::default()).unwrap();
+        for _ in 0..60 {
+            sim.step(1.0 / 60.0);
+        }
+        sim.particles().iter().map(|p| p.pos).collect::<Vec<_>>()
+    };
+    for (a, b) in run(false).iter().zip(run(true)) {
+        assert!((*a - b).length() < 1e-6);
+    }
+}
+
+/// 強い熱源の近くのバネは、熱源から離れた同じバネより補正が弱く、熱源を取り除くと元に戻ることを確認
+#[test]
+fn spring_near_heat_source_corrects_less() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), ..Default::default() });
+    let config = SoftBodyConfig { stiffness: 0.5, shape_stiffness: 0.0, ..Default::default() };
+    let near = sim.add_chain(&[Vec2::new(0.0, 0.0), Vec2::new(40.0, 0.0)], &config);
+    let far = sim.add_chain(&[Vec2::new(1000.0, 0.0), Vec2::new(1040.0, 0.0)], &config);
+    let heat = sim.add_heat_source(Vec2::new(20.0, 0.0), 100.0, 5.0);
+    assert!(sim.heat_at(Vec2::new(20.0, 0.0)) > 4.9);
+    assert_eq!(sim.heat_at(Vec2::new(1020.0, 0.0)), 0.0);
+
+    let stretch = |sim: &mut Simulation| {
+        // 両方のバネを静止長 40 から 60 に伸ばす
+        for (a, b) in [(0, 1), (2, 3)] {
+            let target = sim.particles[a].pos + Vec2::new(60.0, 0.0);
+            sim.particles[b].teleport(target);
+        }
+    };
+    let length = |sim: &Simulation, handle| {
+        let body = sim.soft_body(handle).unwrap();
+        let (a, b) = (body.particle_indices[0], body.particle_indices[1]);
+        (sim.particles[a].pos - sim.particles[b].pos).length()
+    };
+
+    stretch(&mut sim);
+    sim.step(1.0 / 60.0);
+    let scale = sim.soft_body(near).unwrap().springs[0].thermal_stiffness_scale;
+    assert!(scale < 0.5, "{scale}");
+    assert_eq!(sim.soft_body(far).unwrap().springs[0].thermal_stiffness_scale, 1.0);
+    let (near_len, far_len) = (length(&sim, near), length(&sim, far));
+    assert!(near_len > far_len + 1.0, "near {near_len}, far {far_len}");
+
+    // 熱源を取り除くと元の剛性に戻る
+    assert!(sim.remove_heat_source(heat));
+    assert!(!sim.remove_heat_source(heat));
+    sim.freeze_velocities();
+    stretch(&mut sim);
+    sim.step(1.0 / 60.0);
+    assert_eq!(sim.soft_body(near).unwrap().springs[0].thermal_stiffness_scale, 1.0);
+    assert!((length(&sim, near) - length(&sim, far)).abs() < 1e-9);
+}
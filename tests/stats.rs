@@ -0,0 +1,31 @@
+use softbody::core::{FixedSpring, Scalar, SimStats, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 既知の構成のシーンで、`stats` の各項目がボディとシミュレーションの拘束の合計になることを確認
+#[test]
+fn stats_aggregate_bodies_and_simulation_constraints() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    assert_eq!(sim.stats(), SimStats::default());
+
+    // 3x3 の格子: バネ 12 本、形状維持拘束 1 つ
+    sim.add_soft_body(&SoftBodyConfig { rows: 3, cols: 3, ..Default::default() }).unwrap();
+    // 正方形: 外周のバネ 4 本、曲げ拘束 4 つ、外周ワイヤー 4 本、形状維持拘束 1 つ
+    let square = [Vec2::new(200.0, 0.0), Vec2::new(260.0, 0.0), Vec2::new(260.0, 60.0), Vec2::new(200.0, 60.0)];
+    sim.add_convex_body(&square, &SoftBodyConfig { bend_stiffness: 0.5, ..Default::default() }).unwrap();
+    // 4 点のチェーン: バネ 3 本と曲げバネ 2 本、角度拘束 2 つ、形状維持拘束なし
+    let chain: Vec<Vec2> = (0..4).map(|i| Vec2::new(400.0 + 20.0 * i as Scalar, 0.0)).collect();
+    sim.add_chain(&chain, &SoftBodyConfig { shape_stiffness: 0.0, bend_stiffness: 0.3, angle_limits: Some((2.0, 3.2)), ..Default::default() });
+    // シミュレーションが持つバネ
+    sim.connect(0, 9, 0.5);
+    let anchor = FixedSpring::new(13, Vec2::new(400.0, -50.0), 0.5, sim.particles());
+    sim.add_fixed_spring(anchor);
+
+    assert_eq!(sim.stats(), SimStats {
+        particle_count: 17,
+        body_count: 3,
+        spring_count: 12 + 4 + 5 + 2,
+        bend_constraint_count: 4,
+        angle_constraint_count: 2,
+        shape_constraint_count: 2,
+        collider_count: 4,
+    });
+}
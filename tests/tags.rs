@@ -0,0 +1,37 @@
+use softbody::core::{Scalar, Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+fn tagged(x: Scalar, tag: u64) -> SoftBodyConfig {
+    SoftBodyConfig {
+        center: Vec2::new(x, 0.0),
+        size: Vec2::new(40.0, 40.0),
+        rows: 3,
+        cols: 3,
+        particle_radius: 5.0,
+        tag: Some(tag),
+        ..Default::default()
+    }
+}
+
+/// 他のボディの追加・削除の前後で、ボディのタグが保たれることを確認
+#[test]
+fn tag_survives_adding_and_removing_other_bodies() {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    let first = sim.add_soft_body(&tagged(0.0, 1)).unwrap();
+    let second = sim.add_soft_body(&tagged(100.0, 2)).unwrap();
+    let third = sim.add_convex_body(
+        &[Vec2::new(200.0, 0.0), Vec2::new(240.0, 0.0), Vec2::new(220.0, 30.0)],
+        &tagged(0.0, 3),
+    ).unwrap();
+    let untagged = sim.add_soft_body(&SoftBodyConfig { tag: None, ..tagged(300.0, 0) }).unwrap();
+
+    assert!(sim.remove_soft_body(first));
+    assert!(!sim.remove_soft_body(first));
+    sim.add_soft_body(&tagged(400.0, 4)).unwrap();
+
+    assert_eq!(sim.soft_body(second).unwrap().tag(), Some(2));
+    assert_eq!(sim.soft_body(third).unwrap().tag(), Some(3));
+    assert_eq!(sim.soft_body(untagged).unwrap().tag(), None);
+    let mut tags: Vec<u64> = sim.soft_bodies().iter().filter_map(|sb| sb.tag()).collect();
+    tags.sort();
+    assert_eq!(tags, vec![2, 3, 4]);
+}
@@ -0,0 +1,51 @@
+use softbody::core::{Particle, Scalar, Simulation, SimulationConfig, SoftBodyConfig, ValidationError, Vec2};
+
+fn healthy_sim() -> Simulation {
+    let mut sim = Simulation::new(SimulationConfig::default());
+    sim.add_soft_body(&SoftBodyConfig { size: Vec2::new(40.0, 40.0), rows: 3, cols: 3, particle_radius: 5.0, ..Default::default() }).unwrap();
+    sim.particles.push(Particle::new(200.0, 0.0));
+    sim.connect(0, 9, 0.5);
+    sim
+}
+
+#[test]
+fn healthy_simulation_validates() {
+    let mut sim = healthy_sim();
+    assert_eq!(sim.validate(), Ok(()));
+    for _ in 0..30 {
+        sim.step(1.0 / 60.0);
+    }
+    assert_eq!(sim.validate(), Ok(()));
+}
+
+/// バネのインデックスを壊すと、対応するエラーが返ることを確認
+#[test]
+fn corrupted_spring_index_is_reported() {
+    let mut sim = healthy_sim();
+    let handle = sim.soft_bodies()[0].handle();
+    sim.springs_mut().nth(3).unwrap().p2_index = 99;
+
+    let errors = sim.validate().unwrap_err();
+    assert_eq!(errors, vec![ValidationError::SpringIndexOutOfBounds { body: Some(handle), spring: 3 }]);
+}
+
+#[test]
+fn every_problem_is_listed() {
+    let mut sim = healthy_sim();
+    sim.particles[4].vel.x = Scalar::NAN;
+    sim.springs_mut().last().unwrap().rest_length = 0.0;
+
+    let errors = sim.validate().unwrap_err();
+    assert_eq!(errors, vec![
+        ValidationError::NonFiniteParticle { index: 4 },
+        ValidationError::InvalidRestLength { body: None, spring: 0, rest_length: 0.0 },
+    ]);
+    assert!(!errors[0].to_string().is_empty());
+
+    // 形状維持拘束の範囲外のインデックス（質点を直接削除して壊す）
+    let mut sim = healthy_sim();
+    let handle = sim.soft_bodies()[0].handle();
+    sim.particles.truncate(8);
+    let errors = sim.validate().unwrap_err();
+    assert!(errors.contains(&ValidationError::ShapeIndexOutOfBounds { body: handle, index: 8 }));
+}
@@ -0,0 +1,30 @@
+use softbody::core::{Simulation, SimulationConfig, SoftBodyConfig, Vec2};
+
+/// 一様に並進するボディでは、内側のどの点で求めた速度も並進速度に等しく、外側の点では `None` になることを確認
+#[test]
+#[cfg_attr(feature = "f32", ignore = "f64 の精度を前提とした許容誤差")]
+fn translating_body_has_uniform_velocity_field() {
+    let mut sim = Simulation::new(SimulationConfig { gravity: Vec2::new(0.0, 0.0), damping: 1.0, ..Default::default() });
+    let grid = sim.add_soft_body(&SoftBodyConfig { center: Vec2::new(100.0, 100.0), size: Vec2::new(80.0, 60.0), rows: 4, cols: 5, ..Default::default() }).unwrap();
+    let square = [Vec2::new(300.0, 50.0), Vec2::new(380.0, 50.0), Vec2::new(380.0, 130.0), Vec2::new(300.0, 130.0)];
+    let ring = sim.add_convex_body(&square, &SoftBodyConfig::default()).unwrap();
+
+    let velocity = Vec2::new(40.0, -15.0);
+    for p in &mut sim.particles {
+        p.vel = velocity;
+    }
+    sim.step(1.0 / 60.0);
+
+    for handle in [grid, ring] {
+        let body = sim.soft_body(handle).unwrap();
+        let center = body.centroid(sim.particles());
+        for offset in [Vec2::new(0.0, 0.0), Vec2::new(13.0, 7.0), Vec2::new(-21.0, -18.0)] {
+            let sampled = body.velocity_at(center + offset, sim.particles()).unwrap();
+            assert!((sampled - velocity).length() < 1e-9, "{sampled:?}");
+        }
+        // 質点の位置ではその質点の速度
+        let first = &sim.particles()[body.particle_indices[0]];
+        assert_eq!(body.velocity_at(first.pos, sim.particles()), Some(first.vel));
+        assert_eq!(body.velocity_at(center + Vec2::new(500.0, 0.0), sim.particles()), None);
+    }
+}